@@ -5,5 +5,8 @@ fn main() {
         println!("cargo:rustc-link-search=framework=/Library/Frameworks");
     }
 
-    tauri_build::build()
+    // ヘッドレス版（guiフィーチャーなし）はTauriのコンテキストを生成しない
+    if std::env::var_os("CARGO_FEATURE_GUI").is_some() {
+        tauri_build::build()
+    }
 }
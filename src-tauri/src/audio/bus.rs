@@ -0,0 +1,152 @@
+//! 出力ごとのオーディオバス
+//!
+//! 1つのAudio出力（デバイス）に対してシンクを1つだけ開き、メディアアイテムと
+//! LTCをaudiomixerで合成して送る。同じデバイスを複数のシンクで開くと
+//! ASIO等では失敗し、開けても別々のストリームになって同期が崩れるため。
+//!
+//! ```text
+//! メディアアイテム ─┐
+//! LTC (appsrc) ─────┴→ audiomixer → capsfilter → audioconvert → audioresample → audiosink
+//! ```
+//!
+//! バスはデバイスのチャンネル数のインターリーブで、チャンネル配置は持たない
+//! (channel-mask=0)。ステレオの素材は先頭の2チャンネル、LTCは設定された
+//! チャンネルにそのまま割り当てられる。
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+use super::sink::create_audio_sink;
+use crate::error::{AppError, AppResult};
+use crate::types::OutputTarget;
+
+/// バスのサンプルレート
+pub const SAMPLE_RATE: u32 = 48000;
+
+/// 出力チャンネルの指定がない場合のチャンネル数
+const DEFAULT_CHANNELS: u32 = 2;
+
+/// 出力のaudiomixerのエレメント名
+pub fn mixer_name(output_id: &str) -> String {
+    format!("audiomix_{}", output_id)
+}
+
+/// バスのチャンネル数
+///
+/// 出力チャンネルの指定（ASIO等）があればその数、なければステレオ。
+/// LTCのチャンネルがそれを超える場合はLTCのチャンネルまで広げる。
+pub fn channel_count(output: &OutputTarget, ltc_channel: Option<u32>) -> u32 {
+    let channels = output
+        .audio_channels
+        .as_ref()
+        .map(|c| c.len() as u32)
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_CHANNELS);
+    ltc_channel.map_or(channels, |c| channels.max(c + 1))
+}
+
+/// バス（およびLTC）のcaps
+pub fn caps(channels: u32) -> gst::Caps {
+    let mut caps = gst::Caps::builder("audio/x-raw")
+        .field("format", "F32LE")
+        .field("layout", "interleaved")
+        .field("rate", SAMPLE_RATE as i32)
+        .field("channels", channels as i32);
+    // 3チャンネル以上は配置なし（ステレオまでは既定の配置がある）
+    if channels > 2 {
+        caps = caps.field("channel-mask", gst::Bitmask::new(0));
+    }
+    caps.build()
+}
+
+/// 出力のオーディオバスをパイプラインに追加
+pub fn add_output_bus(
+    pipeline: &gst::Pipeline,
+    output: &OutputTarget,
+    channels: u32,
+) -> AppResult<()> {
+    let mixer = gst::ElementFactory::make("audiomixer")
+        .name(mixer_name(&output.id))
+        .build()
+        .map_err(|e| AppError::GStreamer(format!("Failed to create audiomixer: {:?}", e)))?;
+    let filter = gst::ElementFactory::make("capsfilter")
+        .property("caps", caps(channels))
+        .build()
+        .map_err(|e| AppError::GStreamer(format!("Failed to create capsfilter: {:?}", e)))?;
+    let convert = gst::ElementFactory::make("audioconvert")
+        .build()
+        .map_err(|e| AppError::GStreamer(format!("Failed to create audioconvert: {:?}", e)))?;
+    let resample = gst::ElementFactory::make("audioresample")
+        .build()
+        .map_err(|e| AppError::GStreamer(format!("Failed to create audioresample: {:?}", e)))?;
+    let sink = create_audio_sink(output)
+        .map_err(|e| AppError::GStreamer(format!("Failed to create audio sink: {:?}", e)))?;
+
+    let elements = [&mixer, &filter, &convert, &resample, &sink];
+    pipeline
+        .add_many(elements)
+        .map_err(|e| AppError::Pipeline(format!("Failed to add audio bus: {:?}", e)))?;
+    gst::Element::link_many(elements)
+        .map_err(|e| AppError::Pipeline(format!("Failed to link audio bus: {:?}", e)))?;
+
+    Ok(())
+}
+
+/// 出力のバスの入力パッドを要求
+///
+/// 動的パッド（decodebin）からも呼ばれるため、バスは事前に追加しておく。
+pub fn request_pad(pipeline: &gst::Pipeline, output: &OutputTarget) -> AppResult<gst::Pad> {
+    let mixer = pipeline
+        .by_name(&mixer_name(&output.id))
+        .ok_or_else(|| AppError::Pipeline(format!("No audio bus for output '{}'", output.name)))?;
+    mixer
+        .request_pad_simple("sink_%u")
+        .ok_or_else(|| AppError::Pipeline("Failed to request audiomixer pad".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OutputType;
+
+    fn output(audio_channels: Option<Vec<u32>>) -> OutputTarget {
+        OutputTarget {
+            id: "a".to_string(),
+            name: "Audio".to_string(),
+            output_type: OutputType::Audio,
+            audio_channels,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_channel_count() {
+        assert_eq!(channel_count(&output(None), None), 2);
+        assert_eq!(channel_count(&output(None), Some(1)), 2);
+        // LTCを3チャンネル目に載せるとステレオ出力を広げる
+        assert_eq!(channel_count(&output(None), Some(2)), 3);
+        assert_eq!(channel_count(&output(Some(vec![0, 1, 2, 3])), Some(3)), 4);
+        assert_eq!(channel_count(&output(Some(vec![4, 5])), None), 2);
+        assert_eq!(channel_count(&output(Some(Vec::new())), None), 2);
+    }
+
+    #[test]
+    fn test_caps() {
+        gst::init().unwrap();
+
+        let stereo = caps(2);
+        let s = stereo.structure(0).unwrap();
+        assert_eq!(s.name(), "audio/x-raw");
+        assert_eq!(s.get::<i32>("channels").unwrap(), 2);
+        assert_eq!(s.get::<i32>("rate").unwrap(), SAMPLE_RATE as i32);
+        assert!(!s.has_field("channel-mask"));
+
+        let multi = caps(4);
+        let s = multi.structure(0).unwrap();
+        assert_eq!(s.get::<i32>("channels").unwrap(), 4);
+        assert_eq!(
+            s.get::<gst::Bitmask>("channel-mask").unwrap(),
+            gst::Bitmask::new(0)
+        );
+    }
+}
@@ -1 +1,3 @@
+pub mod bus;
 pub mod sink;
+pub mod source;
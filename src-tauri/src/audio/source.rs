@@ -0,0 +1,90 @@
+use gstreamer as gst;
+
+use crate::types::AudioDriver;
+
+/// オーディオ入力ソースを作成
+pub fn create_audio_source(
+    driver: Option<&AudioDriver>,
+    device: Option<&str>,
+) -> Result<gst::Element, gst::glib::BoolError> {
+    let driver = driver.cloned().unwrap_or(AudioDriver::Auto);
+
+    match driver {
+        #[cfg(target_os = "windows")]
+        AudioDriver::Asio => create_asio_source(device),
+
+        #[cfg(target_os = "windows")]
+        AudioDriver::Wasapi => create_wasapi_source(device),
+
+        #[cfg(target_os = "windows")]
+        AudioDriver::Auto => {
+            // ASIO を優先、なければ WASAPI
+            if gst::ElementFactory::find("asiosrc").is_some() {
+                create_asio_source(device)
+            } else {
+                create_wasapi_source(device)
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        AudioDriver::CoreAudio | AudioDriver::Auto => {
+            let mut builder = gst::ElementFactory::make("osxaudiosrc");
+            if let Some(device) = device.and_then(|d| d.parse::<i32>().ok()) {
+                builder = builder.property("device", device);
+            }
+            builder.build()
+        }
+
+        #[cfg(target_os = "linux")]
+        AudioDriver::Jack => gst::ElementFactory::make("jackaudiosrc").build(),
+
+        #[cfg(target_os = "linux")]
+        AudioDriver::Alsa => create_alsa_source(device),
+
+        #[cfg(target_os = "linux")]
+        AudioDriver::Auto => {
+            // JACK を優先、なければ ALSA
+            if gst::ElementFactory::find("jackaudiosrc").is_some() {
+                gst::ElementFactory::make("jackaudiosrc").build()
+            } else {
+                create_alsa_source(device)
+            }
+        }
+
+        #[allow(unreachable_patterns)]
+        _ => gst::ElementFactory::make("autoaudiosrc").build(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn create_alsa_source(device: Option<&str>) -> Result<gst::Element, gst::glib::BoolError> {
+    let mut builder = gst::ElementFactory::make("alsasrc");
+
+    if let Some(device) = device {
+        builder = builder.property("device", device);
+    }
+
+    builder.build()
+}
+
+#[cfg(target_os = "windows")]
+fn create_asio_source(device: Option<&str>) -> Result<gst::Element, gst::glib::BoolError> {
+    let mut builder = gst::ElementFactory::make("asiosrc");
+
+    if let Some(device) = device {
+        builder = builder.property("device-clsid", device);
+    }
+
+    builder.build()
+}
+
+#[cfg(target_os = "windows")]
+fn create_wasapi_source(device: Option<&str>) -> Result<gst::Element, gst::glib::BoolError> {
+    let mut builder = gst::ElementFactory::make("wasapisrc").property("low-latency", true);
+
+    if let Some(device) = device {
+        builder = builder.property("device", device);
+    }
+
+    builder.build()
+}
@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    tauri_live_player_lib::run_headless()
+}
@@ -0,0 +1,10 @@
+use tauri::State;
+
+use crate::control::{self, ControlInput};
+use crate::state::AppState;
+
+/// UIのホットキーに対応するバインディングを実行（一致した数を返す）
+#[tauri::command]
+pub async fn trigger_hotkey(state: State<'_, AppState>, id: String) -> Result<usize, String> {
+    control::dispatch(state.inner(), &ControlInput::Hotkey { id: &id }).map_err(|e| e.to_string())
+}
@@ -0,0 +1,100 @@
+use tauri::State;
+
+use crate::dmx;
+use crate::state::AppState;
+use crate::types::*;
+
+/// プロジェクト設定のArt-Net入力を起動（実行中なら再起動）
+#[tauri::command]
+pub async fn start_art_net(state: State<'_, AppState>) -> Result<(), String> {
+    let config = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.settings.art_net.clone())
+        .unwrap_or_default();
+
+    dmx::restart_art_net(state.inner(), &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_art_net(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut input) = state.art_net.lock().take() {
+        input.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_art_net_status(state: State<'_, AppState>) -> Result<ArtNetStatus, String> {
+    Ok(state
+        .art_net
+        .lock()
+        .as_ref()
+        .map(|input| input.status())
+        .unwrap_or_default())
+}
+
+/// プロジェクト設定のsACN入力を起動（実行中なら再起動）
+#[tauri::command]
+pub async fn start_sacn(state: State<'_, AppState>) -> Result<(), String> {
+    let config = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.settings.sacn.clone())
+        .unwrap_or_default();
+
+    dmx::restart_sacn(state.inner(), &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_sacn(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut input) = state.sacn.lock().take() {
+        input.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_sacn_status(state: State<'_, AppState>) -> Result<SacnStatus, String> {
+    Ok(state
+        .sacn
+        .lock()
+        .as_ref()
+        .map(|input| input.status())
+        .unwrap_or_default())
+}
+
+/// プロジェクト設定のArtTimeCode送信を起動（実行中なら再起動）
+#[tauri::command]
+pub async fn start_art_timecode_output(state: State<'_, AppState>) -> Result<(), String> {
+    let config = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.settings.art_timecode_output.clone())
+        .unwrap_or_default();
+
+    dmx::restart_art_timecode_output(state.inner(), &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_art_timecode_output(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut output) = state.art_timecode_output.lock().take() {
+        output.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_art_timecode_output_status(
+    state: State<'_, AppState>,
+) -> Result<ArtTimecodeOutputStatus, String> {
+    Ok(state
+        .art_timecode_output
+        .lock()
+        .as_ref()
+        .map(|output| output.status())
+        .unwrap_or_default())
+}
@@ -0,0 +1,135 @@
+//! プロジェクトの部分的な編集（project::editing）
+//!
+//! どの操作も編集履歴に記録され、projectChangedイベントを発行する。
+
+use tauri::State;
+
+use crate::project::{self, editing, relink};
+use crate::state::AppState;
+use crate::types::*;
+
+/// Cueを追加（indexを省略すると末尾）
+#[tauri::command]
+pub async fn add_cue(
+    state: State<'_, AppState>,
+    cue: Cue,
+    index: Option<usize>,
+) -> Result<Cue, String> {
+    project::edit(state.inner(), "Add cue", |project| {
+        editing::add_cue(project, cue, index)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_cue(state: State<'_, AppState>, cue: Cue) -> Result<Cue, String> {
+    project::edit_coalesced(state.inner(), "Update cue", |project| {
+        editing::update_cue(project, cue)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_cue(state: State<'_, AppState>, cue_id: String) -> Result<Cue, String> {
+    project::edit(state.inner(), "Remove cue", |project| {
+        editing::remove_cue(project, &cue_id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Cueを指定位置（移動後の0始まりのインデックス）へ移動
+#[tauri::command]
+pub async fn move_cue(
+    state: State<'_, AppState>,
+    cue_id: String,
+    index: usize,
+) -> Result<(), String> {
+    project::edit(state.inner(), "Move cue", |project| {
+        editing::move_cue(project, &cue_id, index)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Cueを新しいIDで複製して直後に挿入
+#[tauri::command]
+pub async fn duplicate_cue(state: State<'_, AppState>, cue_id: String) -> Result<Cue, String> {
+    project::edit(state.inner(), "Duplicate cue", |project| {
+        editing::duplicate_cue(project, &cue_id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_media_item(
+    state: State<'_, AppState>,
+    cue_id: String,
+    mut item: MediaItem,
+    index: Option<usize>,
+) -> Result<MediaItem, String> {
+    let media_root = state.local_config.lock().media_root.clone();
+    relink::record_size(&mut item, media_root.as_deref());
+    project::edit(state.inner(), "Add media item", |project| {
+        editing::add_media_item(project, &cue_id, item, index)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_media_item(
+    state: State<'_, AppState>,
+    cue_id: String,
+    mut item: MediaItem,
+) -> Result<MediaItem, String> {
+    let media_root = state.local_config.lock().media_root.clone();
+    relink::record_size(&mut item, media_root.as_deref());
+    project::edit_coalesced(state.inner(), "Update media item", |project| {
+        editing::update_media_item(project, &cue_id, item)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_media_item(
+    state: State<'_, AppState>,
+    cue_id: String,
+    item_id: String,
+) -> Result<MediaItem, String> {
+    project::edit(state.inner(), "Remove media item", |project| {
+        editing::remove_media_item(project, &cue_id, &item_id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_output(
+    state: State<'_, AppState>,
+    output: OutputTarget,
+) -> Result<OutputTarget, String> {
+    project::edit(state.inner(), "Add output", |project| {
+        editing::add_output(project, output)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// 出力先を置き換える（明るさは現在の値を残す）
+#[tauri::command]
+pub async fn update_output(
+    state: State<'_, AppState>,
+    output: OutputTarget,
+) -> Result<OutputTarget, String> {
+    project::edit_coalesced(state.inner(), "Update output", |project| {
+        editing::update_output(project, output)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_output(
+    state: State<'_, AppState>,
+    output_id: String,
+) -> Result<OutputTarget, String> {
+    project::edit(state.inner(), "Remove output", |project| {
+        editing::remove_output(project, &output_id)
+    })
+    .map_err(|e| e.to_string())
+}
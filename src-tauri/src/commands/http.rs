@@ -0,0 +1,36 @@
+use tauri::State;
+
+use crate::http;
+use crate::state::AppState;
+use crate::types::*;
+
+/// プロジェクト設定のHTTP APIサーバーを起動（実行中なら再起動）
+#[tauri::command]
+pub async fn start_http_server(state: State<'_, AppState>) -> Result<(), String> {
+    let config = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.settings.http.clone())
+        .unwrap_or_default();
+
+    http::restart(state.inner(), &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_http_server(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut server) = state.http.lock().take() {
+        server.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_http_status(state: State<'_, AppState>) -> Result<HttpStatus, String> {
+    Ok(state
+        .http
+        .lock()
+        .as_ref()
+        .map(|server| server.status())
+        .unwrap_or_default())
+}
@@ -0,0 +1,73 @@
+use tauri::State;
+
+use crate::local_config;
+use crate::project;
+use crate::state::AppState;
+use crate::types::*;
+
+#[tauri::command]
+pub async fn get_local_config(state: State<'_, AppState>) -> Result<LocalConfig, String> {
+    Ok(state.local_config.lock().clone())
+}
+
+/// このPCの設定を適用し、設定ファイルに保存
+///
+/// 出力先・受信ポート・同期モードの上書きは次のCue・プロジェクト読み込みから反映。
+/// スレーブIDが無い場合は生成済みのIDを残す
+#[tauri::command]
+pub async fn set_local_config(
+    state: State<'_, AppState>,
+    mut config: LocalConfig,
+) -> Result<(), String> {
+    if config.slave_id.is_none() {
+        config.slave_id = state.local_config.lock().slave_id.clone();
+    }
+    local_config::save(&config).map_err(|e| e.to_string())?;
+    *state.local_config.lock() = config;
+    Ok(())
+}
+
+/// 指定フォルダ（省略時はこのPCのメディアルート）
+fn root_or_default(state: &AppState, media_root: Option<String>) -> Result<String, String> {
+    media_root
+        .or_else(|| state.local_config.lock().media_root.clone())
+        .ok_or_else(|| "No media root specified".to_string())
+}
+
+/// 現在のプロジェクトのメディアパスをメディアルートからの相対パスに変換
+#[tauri::command]
+pub async fn make_project_paths_relative(
+    state: State<'_, AppState>,
+    media_root: Option<String>,
+) -> Result<PathConversion, String> {
+    let media_root = root_or_default(state.inner(), media_root)?;
+
+    project::edit(state.inner(), "Make media paths relative", |project| {
+        let (converted, skipped) = local_config::make_relative(project, &media_root);
+        Ok(PathConversion {
+            project: project.clone(),
+            converted,
+            skipped,
+        })
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// 現在のプロジェクトの相対パスをメディアルート基準の絶対パスに変換
+#[tauri::command]
+pub async fn make_project_paths_absolute(
+    state: State<'_, AppState>,
+    media_root: Option<String>,
+) -> Result<PathConversion, String> {
+    let media_root = root_or_default(state.inner(), media_root)?;
+
+    project::edit(state.inner(), "Make media paths absolute", |project| {
+        let converted = local_config::make_absolute(project, &media_root);
+        Ok(PathConversion {
+            project: project.clone(),
+            converted,
+            skipped: Vec::new(),
+        })
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,42 @@
+use tauri::State;
+
+use crate::midi;
+use crate::state::AppState;
+use crate::types::*;
+
+/// プロジェクト設定のMIDI入力を起動（実行中なら再起動）
+#[tauri::command]
+pub async fn start_midi_input(state: State<'_, AppState>) -> Result<(), String> {
+    let config = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.settings.midi.clone())
+        .unwrap_or_default();
+
+    midi::restart(state.inner(), &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_midi_input(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut input) = state.midi.lock().take() {
+        input.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_midi_status(state: State<'_, AppState>) -> Result<MidiStatus, String> {
+    Ok(state
+        .midi
+        .lock()
+        .as_ref()
+        .map(|input| input.status())
+        .unwrap_or_default())
+}
+
+/// MIDI入力ポート名の一覧
+#[tauri::command]
+pub async fn list_midi_ports() -> Result<Vec<String>, String> {
+    midi::input::list_ports().map_err(|e| e.to_string())
+}
@@ -1,3 +1,12 @@
+pub mod control;
+pub mod dmx;
+pub mod edit;
+pub mod http;
+pub mod local;
+pub mod midi;
+pub mod osc;
 pub mod output;
 pub mod player;
 pub mod project;
+pub mod sync;
+pub mod timecode;
@@ -0,0 +1,36 @@
+use tauri::State;
+
+use crate::osc;
+use crate::state::AppState;
+use crate::types::*;
+
+/// プロジェクト設定のOSCサーバーを起動（実行中なら再起動）
+#[tauri::command]
+pub async fn start_osc_server(state: State<'_, AppState>) -> Result<(), String> {
+    let config = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.settings.osc.clone())
+        .unwrap_or_default();
+
+    osc::restart(state.inner(), &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_osc_server(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut server) = state.osc.lock().take() {
+        server.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_osc_status(state: State<'_, AppState>) -> Result<OscStatus, String> {
+    Ok(state
+        .osc
+        .lock()
+        .as_ref()
+        .map(|server| server.status())
+        .unwrap_or_default())
+}
@@ -1,13 +1,19 @@
 use tauri::{AppHandle, State};
 
 use crate::audio::sink::{list_asio_devices, AsioDevice};
+use crate::local_config;
 use crate::output::manager::OutputManager;
 use crate::state::AppState;
 use crate::types::*;
 
 #[tauri::command]
-pub async fn get_monitors(app: AppHandle) -> Result<Vec<MonitorInfo>, String> {
-    OutputManager::get_monitor_list(&app).map_err(|e| e.to_string())
+pub async fn get_monitors(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<MonitorInfo>, String> {
+    let monitors = OutputManager::get_monitor_list(&app).map_err(|e| e.to_string())?;
+    *state.monitors.lock() = monitors.clone();
+    Ok(monitors)
 }
 
 #[tauri::command]
@@ -17,6 +23,18 @@ pub async fn open_output_window(
     config: OutputTarget,
     monitor: Option<MonitorInfo>,
 ) -> Result<(), String> {
+    // このPCの上書きでディスプレイ番号が変わった場合はモニターを選び直す
+    let overridden = local_config::apply_output(&config, &state.local_config.lock());
+    let monitor = if overridden.display_index != config.display_index {
+        overridden
+            .display_index
+            .and_then(|index| state.monitors.lock().get(index).cloned())
+            .or(monitor)
+    } else {
+        monitor
+    };
+    let config = overridden;
+
     // Create the output window
     let native_handle = {
         let mut manager = state.output_manager.lock();
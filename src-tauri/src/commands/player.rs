@@ -3,6 +3,7 @@ use tauri::{AppHandle, State};
 
 use crate::output::manager::OutputManager;
 use crate::output::native_handle::NativeHandle;
+use crate::playback;
 use crate::state::AppState;
 use crate::types::*;
 
@@ -34,6 +35,8 @@ pub async fn play_test_video(
             offset: None,
             trim_start: None,
             trim_end: None,
+            size: None,
+            sha256: None,
         }],
         duration: 0.0,
         loop_playback: false,
@@ -75,109 +78,36 @@ pub async fn load_cue(
     app: AppHandle,
     cue_index: usize,
 ) -> Result<(), String> {
-    // モニター一覧を取得
+    // モニター一覧を更新（バックグラウンドからのload_cueでも使用）
     let monitors = OutputManager::get_monitor_list(&app).map_err(|e| e.to_string())?;
+    *state.monitors.lock() = monitors;
 
-    // OutputManagerからネイティブハンドルを収集
-    let native_handles: HashMap<String, NativeHandle> = {
-        let output_manager = state.output_manager.lock();
-        let open_ids = output_manager.get_open_output_ids();
-        tracing::debug!(
-            "[load_cue] Open output IDs in OutputManager: {:?}",
-            open_ids
-        );
-
-        let handles: HashMap<String, NativeHandle> = open_ids
-            .iter()
-            .filter_map(|id| {
-                output_manager
-                    .get_native_handle(id)
-                    .map(|handle| (id.clone(), handle))
-            })
-            .collect();
-
-        tracing::debug!(
-            "[load_cue] Native handles collected: {:?}",
-            handles.keys().collect::<Vec<_>>()
-        );
-        handles
-    };
-
-    let mut player_guard = state.player.lock();
-    let project_guard = state.project.lock();
-
-    let project = project_guard
-        .as_ref()
-        .ok_or_else(|| "No project loaded".to_string())?;
-
-    let cue = project
-        .cues
-        .get(cue_index)
-        .ok_or_else(|| "Cue not found".to_string())?;
-
-    let player = player_guard
-        .as_mut()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-
-    player
-        .load_cue(cue, &project.outputs, &monitors, &native_handles)
-        .map_err(|e| e.to_string())?;
-
-    *state.current_cue_index.lock() = cue_index as i32;
-
-    Ok(())
+    playback::load_cue(&state, cue_index).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn play(state: State<'_, AppState>) -> Result<(), String> {
-    let player_guard = state.player.lock();
-    let player = player_guard
-        .as_ref()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-    player.play().map_err(|e| e.to_string())
+    playback::play(&state).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn pause(state: State<'_, AppState>) -> Result<(), String> {
-    let player_guard = state.player.lock();
-    let player = player_guard
-        .as_ref()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-    player.pause().map_err(|e| e.to_string())
+    playback::pause(&state).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn stop(state: State<'_, AppState>) -> Result<(), String> {
-    let player_guard = state.player.lock();
-    let player = player_guard
-        .as_ref()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-    player.stop().map_err(|e| e.to_string())
+    playback::stop(&state).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn seek(state: State<'_, AppState>, position: f64) -> Result<(), String> {
-    let player_guard = state.player.lock();
-    let player = player_guard
-        .as_ref()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-    player.seek(position).map_err(|e| e.to_string())
+    playback::seek(&state, position).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn set_master_brightness(state: State<'_, AppState>, value: f64) -> Result<(), String> {
-    let mut player_guard = state.player.lock();
-    let player = player_guard
-        .as_mut()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-    player.set_master_brightness(value);
-
-    // プロジェクトの値も更新
-    if let Some(project) = state.project.lock().as_mut() {
-        project.master_brightness = value;
-    }
-
-    Ok(())
+    playback::set_master_brightness(&state, value).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -186,76 +116,29 @@ pub async fn set_output_brightness(
     output_id: String,
     value: Option<f64>,
 ) -> Result<(), String> {
-    let mut player_guard = state.player.lock();
-    let player = player_guard
-        .as_mut()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-    player.set_output_brightness(&output_id, value);
-
-    // プロジェクトの値も更新
-    if let Some(project) = state.project.lock().as_mut() {
-        if let Some(output) = project.outputs.iter_mut().find(|o| o.id == output_id) {
-            output.brightness = value;
-        }
-    }
-
-    Ok(())
+    playback::set_output_brightness(&state, &output_id, value).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn set_master_volume(state: State<'_, AppState>, value: f64) -> Result<(), String> {
-    let mut player_guard = state.player.lock();
-    let player = player_guard
-        .as_mut()
-        .ok_or_else(|| "Player not initialized".to_string())?;
-    player.set_master_volume(value);
-
-    // プロジェクトの値も更新
-    if let Some(project) = state.project.lock().as_mut() {
-        project.master_volume = value;
-    }
-
-    Ok(())
+    playback::set_master_volume(&state, value).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn get_player_state(state: State<'_, AppState>) -> Result<PlayerState, String> {
-    let player_guard = state.player.lock();
-
-    let (status, current_time, duration) = match player_guard.as_ref() {
-        Some(player) => {
-            let status = match player.state() {
-                gstreamer::State::Null => PlayerStatus::Idle,
-                gstreamer::State::Ready => PlayerStatus::Ready,
-                gstreamer::State::Paused => PlayerStatus::Paused,
-                gstreamer::State::Playing => PlayerStatus::Playing,
-                _ => PlayerStatus::Idle,
-            };
-            let pos = player.position().unwrap_or(0.0);
-            let dur = player.duration().unwrap_or(0.0);
-            // デバッグ: 最初の数回だけログ出力（高頻度ポーリングなので）
-            static COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
-            let count = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            if count < 20 || count % 100 == 0 {
-                tracing::debug!(
-                    "get_player_state: status={:?}, position={:.3}s, duration={:.3}s",
-                    status,
-                    pos,
-                    dur
-                );
-            }
-            (status, pos, dur)
-        }
-        None => (PlayerStatus::Idle, 0.0, 0.0),
-    };
+    let player_state = playback::player_state(&state);
 
-    let current_cue_index = *state.current_cue_index.lock();
+    // デバッグ: 最初の数回だけログ出力（高頻度ポーリングなので）
+    static COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+    let count = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    if count < 20 || count % 100 == 0 {
+        tracing::debug!(
+            "get_player_state: status={:?}, position={:.3}s, duration={:.3}s",
+            player_state.status,
+            player_state.current_time,
+            player_state.duration
+        );
+    }
 
-    Ok(PlayerState {
-        status,
-        current_cue_index,
-        current_time,
-        duration,
-        error: None,
-    })
+    Ok(player_state)
 }
@@ -1,34 +1,190 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
+use crate::error::AppResult;
+use crate::local_config;
+use crate::pipeline::probe::MediaProber;
+use crate::project::{self, backup, collect, recovery, relink, validation};
 use crate::state::AppState;
+use crate::sync;
 use crate::types::*;
 
 #[tauri::command]
 pub async fn load_project(state: State<'_, AppState>, path: String) -> Result<Project, String> {
     let path = PathBuf::from(path);
 
-    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let project = project::read(&path).map_err(|e| e.to_string())?;
+    project::activate(state.inner(), project.clone());
+    *state.project_path.lock() = Some(path);
 
-    let project: Project = serde_json::from_str(&content).map_err(|e| e.to_string())?;
-
-    *state.project.lock() = Some(project.clone());
+    // このPCに同期モードが設定されていれば同期を開始
+    // （プロジェクトは読み込み済みなので、同期を開始できなくてもエラーにしない）
+    let local = state.local_config.lock().clone();
+    if local.sync_role.is_some() {
+        let sync_config = local_config::apply_sync(&project.sync, &local);
+        if let Err(e) = sync::restart(state.inner(), &sync_config) {
+            tracing::warn!(
+                "[Project] Failed to start sync for '{}': {}",
+                project.name,
+                e
+            );
+        }
+    }
 
     Ok(project)
 }
 
+/// 現在のプロジェクトを検証し、見つかった問題を重大な順に返す
+///
+/// probe_media（省略時true）でメディアをデコードできるかも調べる。
+/// ファイル数に応じて時間がかかる。
 #[tauri::command]
-pub async fn save_project(state: State<'_, AppState>, path: Option<String>) -> Result<(), String> {
-    let project_guard = state.project.lock();
-    let project = project_guard
+pub async fn validate_project(
+    state: State<'_, AppState>,
+    probe_media: Option<bool>,
+) -> Result<Vec<ValidationFinding>, String> {
+    let project = state
+        .project
+        .lock()
+        .clone()
+        .ok_or_else(|| "No project loaded".to_string())?;
+    let local = state.local_config.lock().clone();
+    let monitors = state.monitors.lock().clone();
+
+    tokio::task::spawn_blocking(move || -> Result<Vec<ValidationFinding>, String> {
+        if !probe_media.unwrap_or(true) {
+            return Ok(validation::validate(&project, &local, &monitors, None));
+        }
+        let prober = MediaProber::new().map_err(|e| e.to_string())?;
+        let mut probe = |path: &Path| prober.probe(path);
+        Ok(validation::validate(
+            &project,
+            &local,
+            &monitors,
+            Some(&mut probe),
+        ))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 現在のプロジェクトのメディアを収集先フォルダに集め、相対パスのプロジェクトと
+/// SHA-256のマニフェストを書き出す（modeの省略時はフォルダ）
+///
+/// 進捗はcollectProgressイベントで通知する。現在のプロジェクトは変更しない。
+#[tauri::command]
+pub async fn collect_project(
+    state: State<'_, AppState>,
+    destination: String,
+    mode: Option<CollectMode>,
+) -> Result<CollectResult, String> {
+    let project = state
+        .project
+        .lock()
+        .clone()
+        .ok_or_else(|| "No project loaded".to_string())?;
+    let media_root = state.local_config.lock().media_root.clone();
+    let stem = match state
+        .project_path
+        .lock()
         .as_ref()
+        .and_then(|p| p.file_stem())
+    {
+        Some(stem) => stem.to_string_lossy().into_owned(),
+        None => collect::file_stem(&project.name),
+    };
+    let events = state.events.clone();
+
+    tokio::task::spawn_blocking(move || {
+        collect::collect(
+            &project,
+            media_root.as_deref(),
+            Path::new(&destination),
+            &stem,
+            mode.unwrap_or_default(),
+            &mut |progress| events.emit(AppEvent::CollectProgress(progress)),
+        )
+        .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 見つからないメディアごとに、検索フォルダ以下から再リンクの候補を探す
+///
+/// match_hash（省略時false）で記録されたSHA-256と内容を照合する。
+#[tauri::command]
+pub async fn find_relinks(
+    state: State<'_, AppState>,
+    search_folders: Vec<String>,
+    match_hash: Option<bool>,
+) -> Result<Vec<RelinkProposal>, String> {
+    let project = state
+        .project
+        .lock()
+        .clone()
+        .ok_or_else(|| "No project loaded".to_string())?;
+    let media_root = state.local_config.lock().media_root.clone();
+    let folders: Vec<PathBuf> = search_folders.into_iter().map(PathBuf::from).collect();
+
+    tokio::task::spawn_blocking(move || {
+        relink::propose(
+            &project,
+            media_root.as_deref(),
+            &folders,
+            match_hash.unwrap_or(false),
+        )
+        .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// メディアのパスをまとめて置き換え（1回の編集として記録）、変更したアイテム数を返す
+#[tauri::command]
+pub async fn apply_relinks(
+    state: State<'_, AppState>,
+    relinks: Vec<Relink>,
+) -> Result<usize, String> {
+    let media_root = state.local_config.lock().media_root.clone();
+    project::edit(state.inner(), "Relink media", |project| {
+        relink::apply(project, media_root.as_deref(), &relinks)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// プロジェクトを保存（pathを省略すると最後に読み込み・保存したファイルに上書き）
+#[tauri::command]
+pub async fn save_project(state: State<'_, AppState>, path: Option<String>) -> Result<(), String> {
+    // 書き出し中も再生・編集を止めないようにロックを持たずに書く
+    let project = state
+        .project
+        .lock()
+        .clone()
         .ok_or_else(|| "No project to save".to_string())?;
 
-    let path = path.ok_or_else(|| "No path specified".to_string())?;
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => state
+            .project_path
+            .lock()
+            .clone()
+            .ok_or_else(|| "No path specified".to_string())?,
+    };
 
-    let content = serde_json::to_string_pretty(project).map_err(|e| e.to_string())?;
+    let target = path.clone();
+    tokio::task::spawn_blocking(move || project::write(&project, &target))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
 
-    std::fs::write(path, content).map_err(|e| e.to_string())?;
+    // 別名で保存した場合は元のリカバリーファイルを片付ける
+    let previous = state.project_path.lock().replace(path.clone());
+    if previous.as_ref() != Some(&path) {
+        if let Some(old) = recovery::recovery_path(previous.as_deref()) {
+            recovery::remove(&old);
+        }
+    }
 
     Ok(())
 }
@@ -36,6 +192,7 @@ pub async fn save_project(state: State<'_, AppState>, path: Option<String>) -> R
 #[tauri::command]
 pub async fn new_project(state: State<'_, AppState>, name: String) -> Result<Project, String> {
     let project = Project {
+        schema_version: project::migration::CURRENT_SCHEMA_VERSION,
         id: uuid::Uuid::new_v4().to_string(),
         name,
         master_brightness: 100.0,
@@ -43,9 +200,14 @@ pub async fn new_project(state: State<'_, AppState>, name: String) -> Result<Pro
         outputs: Vec::new(),
         cues: Vec::new(),
         settings: ProjectSettings::default(),
+        sync: SyncConfig::default(),
+        bindings: Vec::new(),
+        macros: Vec::new(),
     };
 
     *state.project.lock() = Some(project.clone());
+    *state.project_path.lock() = None;
+    project::replaced(state.inner());
 
     Ok(project)
 }
@@ -55,8 +217,136 @@ pub async fn get_project(state: State<'_, AppState>) -> Result<Option<Project>,
     Ok(state.project.lock().clone())
 }
 
+/// プロジェクト全体を置き換える（編集履歴に記録）
 #[tauri::command]
 pub async fn update_project(state: State<'_, AppState>, project: Project) -> Result<(), String> {
-    *state.project.lock() = Some(project);
+    // 確認と置き換えの間に別の編集・読み込みが入らないよう、ロックは1回で取る
+    let unchanged = {
+        let mut current = state.project.lock();
+        let Some(existing) = current.as_ref() else {
+            *current = Some(project);
+            drop(current);
+            project::replaced(state.inner());
+            return Ok(());
+        };
+        // 内容が同じなら履歴に残さない
+        serde_json::to_value(existing).map_err(|e| e.to_string())?
+            == serde_json::to_value(&project).map_err(|e| e.to_string())?
+    };
+    if unchanged {
+        return Ok(());
+    }
+
+    project::edit_coalesced(state.inner(), "Update project", |current| {
+        *current = project;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// 直前の編集を元に戻す
+#[tauri::command]
+pub async fn undo(state: State<'_, AppState>) -> Result<Project, String> {
+    project::undo(state.inner()).map_err(|e| e.to_string())
+}
+
+/// 元に戻した編集をやり直す
+#[tauri::command]
+pub async fn redo(state: State<'_, AppState>) -> Result<Project, String> {
+    project::redo(state.inner()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_history(state: State<'_, AppState>) -> Result<HistoryStatus, String> {
+    Ok(state.history.lock().status())
+}
+
+/// 現在のプロジェクトファイル（未保存ならNone）
+#[tauri::command]
+pub async fn get_project_path(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state
+        .project_path
+        .lock()
+        .as_ref()
+        .map(|p| p.to_string_lossy().into_owned()))
+}
+
+/// プロジェクトファイルのバックアップ一覧（新しい順）
+///
+/// pathを省略すると現在のプロジェクトファイル。
+#[tauri::command]
+pub async fn list_backups(
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<Vec<BackupInfo>, String> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => match state.project_path.lock().clone() {
+            Some(path) => path,
+            None => return Ok(Vec::new()),
+        },
+    };
+
+    backup::list(&path).map_err(|e| e.to_string())
+}
+
+/// バックアップを現在のプロジェクトファイルに戻して読み込む
+///
+/// 置き換える前のファイルもバックアップに残す。
+#[tauri::command]
+pub async fn restore_backup(
+    state: State<'_, AppState>,
+    backup_path: String,
+) -> Result<Project, String> {
+    let path = state
+        .project_path
+        .lock()
+        .clone()
+        .ok_or_else(|| "The project has not been saved".to_string())?;
+
+    // 読み込みと書き出しはロックを持たずに済ませてから差し替える
+    let restored = tokio::task::spawn_blocking(move || -> AppResult<Project> {
+        let restored = project::read(Path::new(&backup_path))?;
+        project::write(&restored, &path)?;
+        Ok(restored)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    project::activate(state.inner(), restored.clone());
+
+    Ok(restored)
+}
+
+/// 前回異常終了した時のリカバリーファイル（なければNone）
+#[tauri::command]
+pub async fn get_recovery(state: State<'_, AppState>) -> Result<Option<RecoveryInfo>, String> {
+    Ok(state.recovery.lock().clone())
+}
+
+/// リカバリーファイルからプロジェクト・Cue・再生位置を復元
+#[tauri::command]
+pub async fn restore_recovery(state: State<'_, AppState>) -> Result<Project, String> {
+    let info = state
+        .recovery
+        .lock()
+        .clone()
+        .ok_or_else(|| "No recovery file".to_string())?;
+
+    let project =
+        recovery::restore(state.inner(), Path::new(&info.path)).map_err(|e| e.to_string())?;
+    *state.recovery.lock() = None;
+    // 復元した内容はこのセッションの自動保存が引き継ぐ
+    recovery::remove(Path::new(&info.path));
+
+    Ok(project)
+}
+
+/// リカバリーファイルを破棄
+#[tauri::command]
+pub async fn discard_recovery(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(info) = state.recovery.lock().take() {
+        recovery::remove(Path::new(&info.path));
+    }
     Ok(())
 }
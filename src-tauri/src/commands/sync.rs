@@ -0,0 +1,64 @@
+use tauri::State;
+
+use crate::project;
+use crate::state::AppState;
+use crate::sync;
+use crate::types::*;
+
+/// 同期設定をプロジェクトに保存し、その設定で同期を再起動
+#[tauri::command]
+pub async fn set_sync_config(state: State<'_, AppState>, config: SyncConfig) -> Result<(), String> {
+    if state.project.lock().is_some() {
+        project::edit(state.inner(), "Sync settings", |project| {
+            project.sync = config.clone();
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+    }
+
+    sync::restart(state.inner(), &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_sync(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut sync) = state.sync.lock().take() {
+        sync.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_sync_status(state: State<'_, AppState>) -> Result<SyncStatus, String> {
+    Ok(state
+        .sync
+        .lock()
+        .as_ref()
+        .map(|sync| sync.status())
+        .unwrap_or_default())
+}
+
+/// マスターから見たスレーブ一覧
+#[tauri::command]
+pub async fn get_slaves(state: State<'_, AppState>) -> Result<Vec<SlavePeer>, String> {
+    Ok(state
+        .sync
+        .lock()
+        .as_ref()
+        .map(|sync| sync.slaves())
+        .unwrap_or_default())
+}
+
+/// スレーブへリモート操作（再読み込み・再同期・暗転）を送信
+#[tauri::command]
+pub async fn send_slave_command(
+    state: State<'_, AppState>,
+    slave_id: String,
+    command: SlaveCommand,
+) -> Result<(), String> {
+    let sync_guard = state.sync.lock();
+    let sync = sync_guard
+        .as_ref()
+        .ok_or_else(|| "Sync is not running".to_string())?;
+    sync.send_command(&slave_id, command)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,44 @@
+use tauri::State;
+
+use crate::state::AppState;
+use crate::timecode::LtcChase;
+use crate::types::*;
+
+/// プロジェクト設定のLTCチェイスを開始（実行中なら再起動）
+#[tauri::command]
+pub async fn start_ltc_chase(state: State<'_, AppState>) -> Result<(), String> {
+    let config = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.settings.ltc_chase.clone())
+        .unwrap_or_default();
+
+    let mut chase_guard = state.ltc_chase.lock();
+    if let Some(mut existing) = chase_guard.take() {
+        existing.stop();
+    }
+
+    let chase = LtcChase::start(&config, state.inner()).map_err(|e| e.to_string())?;
+    *chase_guard = Some(chase);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_ltc_chase(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(mut chase) = state.ltc_chase.lock().take() {
+        chase.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_ltc_chase_status(state: State<'_, AppState>) -> Result<LtcChaseStatus, String> {
+    Ok(state
+        .ltc_chase
+        .lock()
+        .as_ref()
+        .map(|chase| chase.status())
+        .unwrap_or_default())
+}
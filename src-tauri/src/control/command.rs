@@ -0,0 +1,146 @@
+//! プレイヤー操作の実行
+
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::control::fade;
+use crate::control::trigger::{self, InputValue};
+use crate::error::{AppError, AppResult};
+use crate::playback;
+use crate::state::AppState;
+use crate::types::{ControlAction, LevelTarget, MacroStep};
+
+/// マクロの入れ子の上限（自分を呼び出すマクロで止まらなくなるのを防ぐ）
+const MAX_MACRO_DEPTH: usize = 8;
+
+/// 値が確定したプレイヤー操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    Go,
+    Play,
+    Pause,
+    Stop,
+    Seek(f64),
+    LoadCue(usize),
+    GoCue(usize),
+    MasterBrightness(f64),
+    MasterVolume(f64),
+    /// valueがNoneならマスターに連動
+    OutputBrightness {
+        output_id: String,
+        value: Option<f64>,
+    },
+    Fade {
+        target: LevelTarget,
+        value: f64,
+        duration: Duration,
+    },
+    /// Noneなら切り替え
+    Blackout(Option<bool>),
+    RunMacro(String),
+}
+
+impl ControlCommand {
+    /// 直接値を設定するレベル操作の対象
+    fn level_target(&self) -> Option<LevelTarget> {
+        match self {
+            ControlCommand::MasterBrightness(_) => Some(LevelTarget::MasterBrightness),
+            ControlCommand::MasterVolume(_) => Some(LevelTarget::MasterVolume),
+            ControlCommand::OutputBrightness { output_id, .. } => {
+                Some(LevelTarget::OutputBrightness {
+                    output_id: output_id.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 操作を実行
+pub fn apply(state: &AppState, command: ControlCommand) -> AppResult<()> {
+    // 直接の値設定は実行中のフェードより優先
+    if let Some(target) = command.level_target() {
+        fade::cancel(state, &target);
+    }
+
+    match command {
+        ControlCommand::Go => playback::go(state),
+        ControlCommand::Play => playback::play(state),
+        ControlCommand::Pause => playback::pause(state),
+        ControlCommand::Stop => playback::stop(state),
+        ControlCommand::Seek(position) => playback::seek(state, position),
+        ControlCommand::LoadCue(index) => playback::load_cue(state, index),
+        ControlCommand::GoCue(index) => playback::go_cue(state, index),
+        ControlCommand::MasterBrightness(value) => playback::set_master_brightness(state, value),
+        ControlCommand::MasterVolume(value) => playback::set_master_volume(state, value),
+        ControlCommand::OutputBrightness { output_id, value } => {
+            playback::set_output_brightness(state, &output_id, value)
+        }
+        ControlCommand::Fade {
+            target,
+            value,
+            duration,
+        } => fade::start(state, target, value, duration),
+        ControlCommand::Blackout(enabled) => {
+            let enabled = enabled.unwrap_or_else(|| !playback::blackout(state));
+            playback::set_blackout(state, enabled)
+        }
+        ControlCommand::RunMacro(macro_id) => {
+            let steps = macro_steps(state, &macro_id)?;
+            let state = state.clone();
+            std::thread::Builder::new()
+                .name("control-macro".to_string())
+                .spawn(move || run_steps(&state, &macro_id, steps, 0))
+                .map_err(AppError::Io)?;
+            Ok(())
+        }
+    }
+}
+
+fn macro_steps(state: &AppState, macro_id: &str) -> AppResult<Vec<MacroStep>> {
+    state
+        .project
+        .lock()
+        .as_ref()
+        .and_then(|p| p.macros.iter().find(|m| m.id == macro_id))
+        .map(|m| m.steps.clone())
+        .ok_or_else(|| AppError::NotFound(format!("Macro not found: {}", macro_id)))
+}
+
+/// マクロのステップを順に実行（失敗したステップは警告を出して続ける）
+fn run_steps(state: &AppState, macro_id: &str, steps: Vec<MacroStep>, depth: usize) {
+    info!("[Control] Running macro '{}'", macro_id);
+
+    for step in steps {
+        if step.delay_ms > 0 {
+            std::thread::sleep(Duration::from_millis(step.delay_ms));
+        }
+
+        // 入れ子のマクロは同じスレッドで続けて実行する
+        if let ControlAction::RunMacro { macro_id: inner } = &step.action {
+            if depth + 1 >= MAX_MACRO_DEPTH {
+                warn!("[Control] Macro '{}' nested too deeply", inner);
+                continue;
+            }
+            match macro_steps(state, inner) {
+                Ok(inner_steps) => run_steps(state, inner, inner_steps, depth + 1),
+                Err(e) => warn!("[Control] Macro '{}': {}", macro_id, e),
+            }
+            continue;
+        }
+
+        let Some(command) = trigger::resolve_action(&step.action, InputValue::default()) else {
+            warn!(
+                "[Control] Macro '{}': {:?} requires a value",
+                macro_id, step.action
+            );
+            continue;
+        };
+        if let Err(e) = apply(state, command.clone()) {
+            warn!(
+                "[Control] Macro '{}': {:?} failed: {}",
+                macro_id, command, e
+            );
+        }
+    }
+}
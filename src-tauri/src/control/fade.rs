@@ -0,0 +1,99 @@
+//! レベルのフェード
+//!
+//! 対象ごとに世代番号を持ち、同じ対象に新しいフェードや直接の値設定が
+//! 来たら古いフェードは次のステップで終了する。
+
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::error::{AppError, AppResult};
+use crate::playback;
+use crate::state::AppState;
+use crate::types::LevelTarget;
+
+/// フェードの更新間隔
+const STEP_INTERVAL: Duration = Duration::from_millis(40);
+
+/// プロジェクト上の現在値
+fn current_level(state: &AppState, target: &LevelTarget) -> AppResult<f64> {
+    let project_guard = state.project.lock();
+    let project = project_guard
+        .as_ref()
+        .ok_or_else(|| AppError::InvalidState("No project loaded".to_string()))?;
+
+    match target {
+        LevelTarget::MasterBrightness => Ok(project.master_brightness),
+        LevelTarget::MasterVolume => Ok(project.master_volume),
+        LevelTarget::OutputBrightness { output_id } => project
+            .outputs
+            .iter()
+            .find(|o| &o.id == output_id)
+            .map(|o| o.brightness.unwrap_or(project.master_brightness))
+            .ok_or_else(|| AppError::NotFound(format!("Output not found: {}", output_id))),
+    }
+}
+
+fn set_level(state: &AppState, target: &LevelTarget, value: f64) -> AppResult<()> {
+    match target {
+        LevelTarget::MasterBrightness => playback::set_master_brightness(state, value),
+        LevelTarget::MasterVolume => playback::set_master_volume(state, value),
+        LevelTarget::OutputBrightness { output_id } => {
+            playback::set_output_brightness(state, output_id, Some(value))
+        }
+    }
+}
+
+/// 世代を進めて返す（古いフェードを打ち切る）
+fn next_generation(state: &AppState, target: &LevelTarget) -> u64 {
+    let mut fades = state.fades.lock();
+    let generation = fades.entry(target.clone()).or_default();
+    *generation += 1;
+    *generation
+}
+
+/// 対象の実行中フェードを打ち切る
+pub fn cancel(state: &AppState, target: &LevelTarget) {
+    if let Some(generation) = state.fades.lock().get_mut(target) {
+        *generation += 1;
+    }
+}
+
+/// 現在値から目標値へのフェードを開始
+pub fn start(
+    state: &AppState,
+    target: LevelTarget,
+    value: f64,
+    duration: Duration,
+) -> AppResult<()> {
+    let from = current_level(state, &target)?;
+    let generation = next_generation(state, &target);
+
+    if duration.is_zero() {
+        return set_level(state, &target, value);
+    }
+
+    let state = state.clone();
+    std::thread::Builder::new()
+        .name("control-fade".to_string())
+        .spawn(move || {
+            let started = Instant::now();
+            loop {
+                std::thread::sleep(STEP_INTERVAL);
+                if state.fades.lock().get(&target) != Some(&generation) {
+                    return;
+                }
+
+                let progress = (started.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0);
+                if let Err(e) = set_level(&state, &target, from + (value - from) * progress) {
+                    warn!("[Control] Fade {:?} stopped: {}", target, e);
+                    return;
+                }
+                if progress >= 1.0 {
+                    return;
+                }
+            }
+        })
+        .map_err(AppError::Io)?;
+
+    Ok(())
+}
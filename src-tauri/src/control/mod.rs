@@ -0,0 +1,148 @@
+//! 外部制御の共通ディスパッチャー
+//!
+//! OSC・MIDI・DMX・HTTP・ホットキー・タイムコードの入力を `ControlInput` として
+//! 受け取り、プロジェクトのバインディング（`Project::bindings`）に一致した操作を
+//! 実行する。プロトコルごとの既定の対応（OSCのアドレス表・MIDIバインディング・
+//! DMXパッチ・MSC）も `ControlCommand` に変換して `apply` で実行するため、
+//! プレイヤー操作の呼び出しはこのモジュールに集まる。
+//!
+//! 同じ入力で操作が二重に実行されないよう、バインディングを既定の対応より優先する。
+//! OSC・MIDIは入力に一致するバインディングがあれば既定の対応を使わず、
+//! DMXはバインディングのトリガーに使われているチャンネルをパッチから外す。
+
+pub mod command;
+pub mod fade;
+pub mod trigger;
+
+use parking_lot::Mutex;
+
+pub use command::{apply, ControlCommand};
+pub use trigger::ControlInput;
+
+use crate::error::AppResult;
+use crate::state::AppState;
+use crate::types::{FrameRate, Timecode};
+
+/// プロジェクトのバインディングのうち入力に一致するものを実行
+///
+/// 一致したバインディングの数を返す。
+pub fn dispatch(state: &AppState, input: &ControlInput) -> AppResult<usize> {
+    let commands = match state.project.lock().as_ref() {
+        Some(project) => trigger::resolve(&project.bindings, input),
+        None => return Ok(0),
+    };
+
+    let count = commands.len();
+    for command in commands {
+        apply(state, command)?;
+    }
+    Ok(count)
+}
+
+/// バインディングを実行し、一致するものが無ければ既定の対応の操作を実行
+///
+/// 既定の対応（OSCのアドレス表・MIDIバインディング）は一致しなかった場合だけ評価する。
+pub fn dispatch_or(
+    state: &AppState,
+    input: &ControlInput,
+    defaults: impl FnOnce() -> AppResult<Vec<ControlCommand>>,
+) -> AppResult<usize> {
+    let matched = dispatch(state, input)?;
+    if matched > 0 {
+        return Ok(matched);
+    }
+
+    let commands = defaults()?;
+    for command in commands {
+        apply(state, command)?;
+    }
+    Ok(0)
+}
+
+/// プロジェクトのバインディングがトリガーに使っているDMXチャンネル (1〜512)
+///
+/// DMXパッチはこれらのチャンネルを無視する。
+pub fn bound_dmx_addresses(state: &AppState, universe: u16) -> Vec<u16> {
+    state
+        .project
+        .lock()
+        .as_ref()
+        .map(|project| trigger::dmx_addresses(&project.bindings, universe))
+        .unwrap_or_default()
+}
+
+/// タイムコードトリガー用に前回受信したタイムコードを保持
+#[derive(Default)]
+pub struct TimecodeTriggers {
+    last: Mutex<Option<Timecode>>,
+}
+
+impl TimecodeTriggers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 受信したタイムコードで、前回から通過したタイムコードトリガーを実行
+    pub fn on_timecode(
+        &self,
+        state: &AppState,
+        timecode: Timecode,
+        frame_rate: FrameRate,
+    ) -> AppResult<usize> {
+        let Some(from) = self.last.lock().replace(timecode) else {
+            return Ok(0);
+        };
+        dispatch(
+            state,
+            &ControlInput::Timecode {
+                from,
+                to: timecode,
+                frame_rate,
+            },
+        )
+    }
+
+    /// ロケート等で前回値を破棄
+    pub fn reset(&self) {
+        *self.last.lock() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ControlAction, ControlBinding, ControlMacro, ControlTrigger, Project};
+
+    #[test]
+    fn test_bindings_take_precedence() {
+        let state = AppState::new();
+        *state.project.lock() = Some(Project {
+            bindings: vec![ControlBinding {
+                trigger: ControlTrigger::Hotkey {
+                    id: "f1".to_string(),
+                },
+                action: ControlAction::RunMacro {
+                    macro_id: "empty".to_string(),
+                },
+            }],
+            macros: vec![ControlMacro {
+                id: "empty".to_string(),
+                name: "Empty".to_string(),
+                steps: Vec::new(),
+            }],
+            ..Default::default()
+        });
+
+        let mut defaults = Vec::new();
+        for id in ["f1", "f2"] {
+            let matched = dispatch_or(&state, &ControlInput::Hotkey { id }, || {
+                defaults.push(id);
+                Ok(Vec::new())
+            })
+            .unwrap();
+            assert_eq!(matched, usize::from(id == "f1"));
+        }
+        // 既定の対応はバインディングが一致しなかった入力だけ
+        assert_eq!(defaults, vec!["f2"]);
+    }
+}
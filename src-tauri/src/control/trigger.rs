@@ -0,0 +1,389 @@
+//! 入力とバインディングの照合
+
+use std::time::Duration;
+
+use crate::control::ControlCommand;
+use crate::midi::mapping::{self as midi_mapping, MidiMessage};
+use crate::types::{ControlAction, ControlBinding, ControlTrigger, FrameRate, Timecode};
+
+/// 制御プロトコルから届いた入力
+#[derive(Debug, Clone, Copy)]
+pub enum ControlInput<'a> {
+    /// valueは最初の数値引数
+    Osc {
+        address: &'a str,
+        value: Option<f64>,
+    },
+    Midi(&'a MidiMessage),
+    /// 1ユニバース分のDMXデータ（previousが空なら受信開始直後）
+    Dmx {
+        universe: u16,
+        previous: &'a [u8],
+        current: &'a [u8],
+    },
+    Http {
+        name: &'a str,
+        value: Option<f64>,
+    },
+    #[cfg(feature = "gui")]
+    Hotkey {
+        id: &'a str,
+    },
+    /// 前回受信したタイムコードから今回のタイムコードまで
+    Timecode {
+        from: Timecode,
+        to: Timecode,
+        frame_rate: FrameRate,
+    },
+}
+
+/// 入力から取り出した値
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputValue {
+    /// 0〜100
+    pub level: Option<f64>,
+    /// Cueインデックス (0始まり)
+    pub cue: Option<usize>,
+    /// ボタンを離した時の0（値を使う操作以外は実行しない）
+    pub release: bool,
+}
+
+impl InputValue {
+    /// OSC・HTTPの数値引数（Cue番号は1始まり）
+    fn from_number(value: Option<f64>) -> Self {
+        Self {
+            level: value.map(|v| v.clamp(0.0, 100.0)),
+            cue: value.filter(|v| *v >= 1.0).map(|v| v as usize - 1),
+            release: value == Some(0.0),
+        }
+    }
+}
+
+/// トリガーが入力に一致すれば入力の値を返す
+fn matches(trigger: &ControlTrigger, input: &ControlInput) -> Option<InputValue> {
+    match (trigger, input) {
+        (ControlTrigger::Osc { address }, ControlInput::Osc { address: a, value }) => {
+            (address == a).then(|| InputValue::from_number(*value))
+        }
+        (ControlTrigger::Midi { message: trigger }, ControlInput::Midi(message)) => {
+            midi_mapping::matches(trigger, message).then(|| InputValue {
+                level: Some(message.level()),
+                cue: Some(message.index()),
+                release: false,
+            })
+        }
+        (
+            ControlTrigger::Dmx {
+                universe,
+                address,
+                threshold,
+            },
+            ControlInput::Dmx {
+                universe: u,
+                previous,
+                current,
+            },
+        ) => {
+            if universe.is_some_and(|universe| universe != *u) || *address == 0 {
+                return None;
+            }
+            // 受信開始時点でしきい値を超えていても発火しない
+            let index = *address as usize - 1;
+            let before = *previous.get(index)?;
+            let value = *current.get(index)?;
+            (before < *threshold && value >= *threshold).then(|| InputValue {
+                level: Some(value as f64 * 100.0 / 255.0),
+                cue: None,
+                release: false,
+            })
+        }
+        (ControlTrigger::Http { name }, ControlInput::Http { name: n, value }) => {
+            (name == n).then(|| InputValue::from_number(*value))
+        }
+        #[cfg(feature = "gui")]
+        (ControlTrigger::Hotkey { id }, ControlInput::Hotkey { id: i }) => {
+            (id == i).then(InputValue::default)
+        }
+        (
+            ControlTrigger::Timecode { timecode },
+            ControlInput::Timecode {
+                from,
+                to,
+                frame_rate,
+            },
+        ) => {
+            let at = timecode.to_frames(*frame_rate);
+            let from = from.to_frames(*frame_rate);
+            let to = to.to_frames(*frame_rate);
+            // 1秒を超える飛びはロケートとみなし、途中のトリガーは発火しない
+            let passed = from < at && at <= to && to - from <= frame_rate.nominal_fps() as u64;
+            passed.then(InputValue::default)
+        }
+        _ => None,
+    }
+}
+
+/// 操作を入力の値で具体化（値が必要なのに無い場合はNone）
+pub fn resolve_action(action: &ControlAction, value: InputValue) -> Option<ControlCommand> {
+    let uses_level = matches!(
+        action,
+        ControlAction::MasterBrightness { value: None }
+            | ControlAction::MasterVolume { value: None }
+            | ControlAction::OutputBrightness { value: None, .. }
+    );
+    if value.release && !uses_level {
+        return None;
+    }
+
+    Some(match action {
+        ControlAction::Go => ControlCommand::Go,
+        ControlAction::Play => ControlCommand::Play,
+        ControlAction::Pause => ControlCommand::Pause,
+        ControlAction::Stop => ControlCommand::Stop,
+        ControlAction::LoadCue { cue } => ControlCommand::LoadCue(cue.or(value.cue)?),
+        ControlAction::GoCue { cue } => ControlCommand::GoCue(cue.or(value.cue)?),
+        ControlAction::MasterBrightness { value: v } => {
+            ControlCommand::MasterBrightness(v.or(value.level)?)
+        }
+        ControlAction::MasterVolume { value: v } => {
+            ControlCommand::MasterVolume(v.or(value.level)?)
+        }
+        ControlAction::OutputBrightness {
+            output_id,
+            value: v,
+        } => ControlCommand::OutputBrightness {
+            output_id: output_id.clone(),
+            value: Some(v.or(value.level)?),
+        },
+        ControlAction::Fade {
+            target,
+            value,
+            duration_ms,
+        } => ControlCommand::Fade {
+            target: target.clone(),
+            value: value.clamp(0.0, 100.0),
+            duration: Duration::from_millis(*duration_ms),
+        },
+        ControlAction::Blackout { enabled } => ControlCommand::Blackout(*enabled),
+        ControlAction::RunMacro { macro_id } => ControlCommand::RunMacro(macro_id.clone()),
+    })
+}
+
+/// 入力に一致するバインディングの操作を列挙
+pub fn resolve(bindings: &[ControlBinding], input: &ControlInput) -> Vec<ControlCommand> {
+    bindings
+        .iter()
+        .filter_map(|binding| {
+            let value = matches(&binding.trigger, input)?;
+            resolve_action(&binding.action, value)
+        })
+        .collect()
+}
+
+/// ユニバースのDMXトリガーが使うチャンネル
+pub fn dmx_addresses(bindings: &[ControlBinding], universe: u16) -> Vec<u16> {
+    bindings
+        .iter()
+        .filter_map(|binding| match binding.trigger {
+            ControlTrigger::Dmx {
+                universe: u,
+                address,
+                ..
+            } if u.is_none_or(|u| u == universe) => Some(address),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MidiTrigger;
+
+    fn binding(trigger: ControlTrigger, action: ControlAction) -> ControlBinding {
+        ControlBinding { trigger, action }
+    }
+
+    #[test]
+    fn test_osc_and_http_values() {
+        let bindings = vec![
+            binding(
+                ControlTrigger::Osc {
+                    address: "/show/level".to_string(),
+                },
+                ControlAction::MasterBrightness { value: None },
+            ),
+            binding(
+                ControlTrigger::Http {
+                    name: "cue".to_string(),
+                },
+                ControlAction::GoCue { cue: None },
+            ),
+        ];
+
+        let osc = ControlInput::Osc {
+            address: "/show/level",
+            value: Some(150.0),
+        };
+        assert_eq!(
+            resolve(&bindings, &osc),
+            vec![ControlCommand::MasterBrightness(100.0)]
+        );
+
+        // 値が必要な操作は値なしでは実行しない
+        let osc = ControlInput::Osc {
+            address: "/show/level",
+            value: None,
+        };
+        assert!(resolve(&bindings, &osc).is_empty());
+
+        let http = ControlInput::Http {
+            name: "cue",
+            value: Some(3.0),
+        };
+        assert_eq!(resolve(&bindings, &http), vec![ControlCommand::GoCue(2)]);
+
+        // ボタンを離した時の0はレベル操作にだけ使う
+        let osc = ControlInput::Osc {
+            address: "/show/level",
+            value: Some(0.0),
+        };
+        assert_eq!(
+            resolve(&bindings, &osc),
+            vec![ControlCommand::MasterBrightness(0.0)]
+        );
+        let http = ControlInput::Http {
+            name: "cue",
+            value: Some(0.0),
+        };
+        assert!(resolve(&bindings, &http).is_empty());
+    }
+
+    #[test]
+    fn test_midi_and_hotkey() {
+        let bindings = vec![
+            binding(
+                ControlTrigger::Midi {
+                    message: MidiTrigger::Note {
+                        channel: Some(1),
+                        note: Some(60),
+                    },
+                },
+                ControlAction::Blackout { enabled: None },
+            ),
+            binding(
+                ControlTrigger::Hotkey {
+                    id: "f1".to_string(),
+                },
+                ControlAction::RunMacro {
+                    macro_id: "intro".to_string(),
+                },
+            ),
+        ];
+
+        let note = MidiMessage::parse(&[0x90, 60, 100]).unwrap();
+        assert_eq!(
+            resolve(&bindings, &ControlInput::Midi(&note)),
+            vec![ControlCommand::Blackout(None)]
+        );
+        let other = MidiMessage::parse(&[0x91, 60, 100]).unwrap();
+        assert!(resolve(&bindings, &ControlInput::Midi(&other)).is_empty());
+
+        assert_eq!(
+            resolve(&bindings, &ControlInput::Hotkey { id: "f1" }),
+            vec![ControlCommand::RunMacro("intro".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_dmx_threshold_crossing() {
+        let bindings = vec![binding(
+            ControlTrigger::Dmx {
+                universe: Some(1),
+                address: 10,
+                threshold: 128,
+            },
+            ControlAction::Go,
+        )];
+
+        let mut low = vec![0u8; 512];
+        let mut high = vec![0u8; 512];
+        low[9] = 100;
+        high[9] = 200;
+
+        let input = |previous, current| ControlInput::Dmx {
+            universe: 1,
+            previous,
+            current,
+        };
+        assert_eq!(
+            resolve(&bindings, &input(&low, &high)),
+            vec![ControlCommand::Go]
+        );
+        assert!(resolve(&bindings, &input(&high, &high)).is_empty());
+        assert!(resolve(&bindings, &input(&[], &high)).is_empty());
+
+        let other_universe = ControlInput::Dmx {
+            universe: 2,
+            previous: &low,
+            current: &high,
+        };
+        assert!(resolve(&bindings, &other_universe).is_empty());
+    }
+
+    #[test]
+    fn test_dmx_addresses() {
+        let dmx = |universe, address| {
+            binding(
+                ControlTrigger::Dmx {
+                    universe,
+                    address,
+                    threshold: 128,
+                },
+                ControlAction::Go,
+            )
+        };
+        let bindings = vec![
+            dmx(Some(1), 10),
+            dmx(None, 4),
+            dmx(Some(2), 5),
+            binding(
+                ControlTrigger::Hotkey {
+                    id: "f1".to_string(),
+                },
+                ControlAction::Go,
+            ),
+        ];
+        assert_eq!(dmx_addresses(&bindings, 1), vec![10, 4]);
+        assert_eq!(dmx_addresses(&bindings, 2), vec![4, 5]);
+    }
+
+    #[test]
+    fn test_timecode_passing() {
+        let at = Timecode {
+            hours: 1,
+            minutes: 0,
+            seconds: 10,
+            frames: 0,
+        };
+        let bindings = vec![binding(
+            ControlTrigger::Timecode { timecode: at },
+            ControlAction::Go,
+        )];
+        let rate = FrameRate::Fps25;
+        let frames = at.to_frames(rate);
+        let input = |from: u64, to: u64| ControlInput::Timecode {
+            from: Timecode::from_frames(from, rate),
+            to: Timecode::from_frames(to, rate),
+            frame_rate: rate,
+        };
+
+        assert_eq!(
+            resolve(&bindings, &input(frames - 1, frames)),
+            vec![ControlCommand::Go]
+        );
+        assert!(resolve(&bindings, &input(frames, frames + 1)).is_empty());
+        // ロケートで飛び越えた場合は発火しない
+        assert!(resolve(&bindings, &input(frames - 100, frames + 10)).is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+//! ArtTimeCode
+//!
+//! Art-Net上のタイムコード。受信はArtNetInputがChaseRunnerへ渡し、
+//! 送信はCuePlayerの再生位置から生成してブロードキャストする。
+
+use parking_lot::Mutex;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::error::{AppError, AppResult};
+use crate::playback;
+use crate::state::AppState;
+#[cfg(feature = "gui")]
+use crate::types::ArtTimecodeOutputStatus;
+use crate::types::{ArtTimecodeOutputConfig, FrameRate, PlayerStatus, Timecode};
+
+/// ArtTimeCodeのOpCode（リトルエンディアン）
+pub const OP_TIME_CODE: u16 = 0x9700;
+
+/// ArtTimeCodeのパケット長
+const PACKET_SIZE: usize = 19;
+
+/// 停止中に現在位置を送り直す間隔
+const IDLE_INTERVAL: Duration = Duration::from_secs(1);
+
+fn rate_from_type(value: u8) -> Option<FrameRate> {
+    match value {
+        0 => Some(FrameRate::Fps24),
+        1 => Some(FrameRate::Fps25),
+        2 => Some(FrameRate::Fps2997Df),
+        3 => Some(FrameRate::Fps30),
+        _ => None,
+    }
+}
+
+fn type_from_rate(rate: FrameRate) -> u8 {
+    match rate {
+        FrameRate::Fps24 => 0,
+        FrameRate::Fps25 => 1,
+        FrameRate::Fps2997Df => 2,
+        FrameRate::Fps30 => 3,
+    }
+}
+
+/// ArtTimeCodeパケット（ヘッダー以降）を解釈
+pub fn parse(buf: &[u8]) -> Option<(Timecode, FrameRate)> {
+    if buf.len() < PACKET_SIZE {
+        return None;
+    }
+    let rate = rate_from_type(buf[18])?;
+    Some((
+        Timecode {
+            frames: buf[14],
+            seconds: buf[15],
+            minutes: buf[16],
+            hours: buf[17],
+        },
+        rate,
+    ))
+}
+
+/// ArtTimeCodeパケットを生成
+pub fn encode(timecode: Timecode, rate: FrameRate, stream_id: u8) -> [u8; PACKET_SIZE] {
+    let mut buf = [0u8; PACKET_SIZE];
+    buf[..8].copy_from_slice(b"Art-Net\0");
+    buf[8..10].copy_from_slice(&OP_TIME_CODE.to_le_bytes());
+    buf[11] = 14;
+    buf[13] = stream_id;
+    buf[14] = timecode.frames;
+    buf[15] = timecode.seconds;
+    buf[16] = timecode.minutes;
+    buf[17] = timecode.hours;
+    buf[18] = type_from_rate(rate);
+    buf
+}
+
+/// 送信スレッドと共有する状態
+#[derive(Default)]
+struct OutputShared {
+    packets_sent: u64,
+    timecode: Option<Timecode>,
+}
+
+/// ArtTimeCode送信サービス
+pub struct ArtTimecodeOutput {
+    #[cfg(feature = "gui")]
+    target: SocketAddr,
+    #[cfg(feature = "gui")]
+    shared: Arc<Mutex<OutputShared>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ArtTimecodeOutput {
+    pub fn start(config: &ArtTimecodeOutputConfig, state: AppState) -> AppResult<Self> {
+        let target = (config.target_address.as_str(), config.port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| {
+                AppError::Dmx(format!(
+                    "Invalid timecode target: {}",
+                    config.target_address
+                ))
+            })?;
+
+        let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+        socket.set_broadcast(true)?;
+
+        let shared = Arc::new(Mutex::new(OutputShared::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let config = config.clone();
+            let shared = shared.clone();
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name("art-timecode-output".to_string())
+                .spawn(move || Self::run(socket, target, config, state, shared, running))
+                .map_err(AppError::Io)?
+        };
+
+        info!(
+            "[ArtTimeCode] Sending to {} ({:?}, start={})",
+            target, config.frame_rate, config.start_timecode
+        );
+
+        Ok(Self {
+            #[cfg(feature = "gui")]
+            target,
+            #[cfg(feature = "gui")]
+            shared,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run(
+        socket: UdpSocket,
+        target: SocketAddr,
+        config: ArtTimecodeOutputConfig,
+        state: AppState,
+        shared: Arc<Mutex<OutputShared>>,
+        running: Arc<AtomicBool>,
+    ) {
+        let rate = config.frame_rate;
+        let frame_duration = Duration::from_secs_f64(1.0 / rate.fps());
+        let start_frames = config.start_timecode.to_frames(rate);
+        let mut last_sent: Option<(Timecode, Instant)> = None;
+
+        while running.load(Ordering::Relaxed) {
+            std::thread::sleep(frame_duration);
+
+            let player_state = playback::player_state(&state);
+            if player_state.current_cue_index < 0 {
+                continue;
+            }
+            let frames = (player_state.current_time.max(0.0) * rate.fps()).floor() as u64;
+            let timecode = Timecode::from_frames(start_frames + frames, rate);
+
+            // 再生中は毎フレーム、それ以外は位置が変わった時と一定間隔で送る
+            let playing = player_state.status == PlayerStatus::Playing;
+            let due = match last_sent {
+                Some((last, at)) => playing || last != timecode || at.elapsed() >= IDLE_INTERVAL,
+                None => true,
+            };
+            if !due {
+                continue;
+            }
+
+            if let Err(e) = socket.send_to(&encode(timecode, rate, config.stream_id), target) {
+                warn!("[ArtTimeCode] Failed to send to {}: {}", target, e);
+                continue;
+            }
+            last_sent = Some((timecode, Instant::now()));
+
+            let mut shared = shared.lock();
+            shared.packets_sent += 1;
+            shared.timecode = Some(timecode);
+        }
+
+        debug!("[ArtTimeCode] Send thread finished");
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> ArtTimecodeOutputStatus {
+        let shared = self.shared.lock();
+        ArtTimecodeOutputStatus {
+            running: self.running.load(Ordering::Relaxed),
+            target: Some(self.target.to_string()),
+            packets_sent: shared.packets_sent,
+            timecode: shared.timecode,
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[ArtTimeCode] Stopped");
+        }
+    }
+}
+
+impl Drop for ArtTimecodeOutput {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let timecode = Timecode {
+            hours: 10,
+            minutes: 20,
+            seconds: 30,
+            frames: 24,
+        };
+        let buf = encode(timecode, FrameRate::Fps25, 0);
+        assert_eq!(u16::from_le_bytes([buf[8], buf[9]]), OP_TIME_CODE);
+        assert_eq!(parse(&buf), Some((timecode, FrameRate::Fps25)));
+
+        let mut invalid = buf;
+        invalid[18] = 9;
+        assert_eq!(parse(&invalid), None);
+    }
+}
@@ -0,0 +1,360 @@
+//! Art-Net入力
+//!
+//! ArtDmxパケットのうち設定ユニバースのものを受信し、DmxMapperで
+//! プレイヤー操作に変換する。卓からはブロードキャストか、このPC宛ての
+//! ユニキャストで送る。ArtTimeCodeはChaseRunnerへ渡して再生位置を追従させる。
+
+use parking_lot::Mutex;
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::control::{self, ControlInput, TimecodeTriggers};
+use crate::dmx::art_timecode::{self, OP_TIME_CODE};
+use crate::dmx::mapping::DmxMapper;
+use crate::error::{AppError, AppResult};
+use crate::state::AppState;
+use crate::timecode::chase_runner::{ChaseFeed, ChaseRunner};
+#[cfg(feature = "gui")]
+use crate::types::ArtNetStatus;
+use crate::types::{ArtNetConfig, FrameRate, Timecode};
+
+/// パケット先頭の識別子
+const ART_NET_ID: &[u8; 8] = b"Art-Net\0";
+
+/// ArtDmxのOpCode（リトルエンディアン）
+const OP_DMX: u16 = 0x5000;
+
+/// ArtDmxのヘッダー長
+const DMX_HEADER_SIZE: usize = 18;
+
+/// 停止確認のためのタイムアウト
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 受信したArt-Netパケット
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArtNetPacket<'a> {
+    Dmx {
+        sequence: u8,
+        /// 15bitのPort-Address
+        universe: u16,
+        data: &'a [u8],
+    },
+    TimeCode {
+        timecode: Timecode,
+        frame_rate: FrameRate,
+    },
+    /// 対応していないOpCode
+    Other(u16),
+}
+
+impl<'a> ArtNetPacket<'a> {
+    /// Art-Netパケットを解釈（Art-Net以外はNone）
+    pub fn parse(buf: &'a [u8]) -> Option<Self> {
+        if buf.len() < 10 || &buf[..8] != ART_NET_ID {
+            return None;
+        }
+        let op_code = u16::from_le_bytes([buf[8], buf[9]]);
+
+        match op_code {
+            OP_DMX => {
+                if buf.len() < DMX_HEADER_SIZE {
+                    return None;
+                }
+                let universe = u16::from_le_bytes([buf[14], buf[15] & 0x7F]);
+                let length = u16::from_be_bytes([buf[16], buf[17]]) as usize;
+                let data = buf.get(DMX_HEADER_SIZE..DMX_HEADER_SIZE + length.min(512))?;
+                Some(ArtNetPacket::Dmx {
+                    sequence: buf[12],
+                    universe,
+                    data,
+                })
+            }
+            OP_TIME_CODE => {
+                art_timecode::parse(buf).map(|(timecode, frame_rate)| ArtNetPacket::TimeCode {
+                    timecode,
+                    frame_rate,
+                })
+            }
+            other => Some(ArtNetPacket::Other(other)),
+        }
+    }
+}
+
+/// 受信スレッドと共有する状態
+#[derive(Default)]
+struct ArtNetShared {
+    packets_received: u64,
+    dmx_frames: u64,
+    last_source: Option<String>,
+    channel_values: Vec<u8>,
+    errors: u64,
+    last_error: Option<String>,
+}
+
+/// Art-Net入力サービス
+pub struct ArtNetInput {
+    #[cfg(feature = "gui")]
+    address: String,
+    timecode_chase: Option<ChaseRunner>,
+    #[cfg(feature = "gui")]
+    shared: Arc<Mutex<ArtNetShared>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ArtNetInput {
+    pub fn start(config: &ArtNetConfig, state: AppState) -> AppResult<Self> {
+        let socket = UdpSocket::bind((config.bind_address.as_str(), config.port))
+            .map_err(|e| AppError::Dmx(format!("Failed to bind Art-Net port: {}", e)))?;
+        socket.set_broadcast(true)?;
+        socket.set_read_timeout(Some(POLL_INTERVAL))?;
+        let address = socket.local_addr()?.to_string();
+
+        // チェイスの開始位置・しきい値はLTCチェイスの設定を共用
+        let (timecode_feed, timecode_chase) = if config.timecode_chase {
+            let chase_config = state
+                .project
+                .lock()
+                .as_ref()
+                .map(|p| p.settings.ltc_chase.clone())
+                .unwrap_or_default();
+            let feed = ChaseFeed::new("ArtTimeCodeChase", &chase_config);
+            let runner = ChaseRunner::start(
+                "art-timecode-chase",
+                feed.clone(),
+                state.player.clone(),
+                None,
+            )?;
+            (Some(feed), Some(runner))
+        } else {
+            (None, None)
+        };
+
+        let shared = Arc::new(Mutex::new(ArtNetShared::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let config = config.clone();
+            let shared = shared.clone();
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name("artnet-input".to_string())
+                .spawn(move || Self::run(socket, config, timecode_feed, state, shared, running))
+                .map_err(AppError::Io)?
+        };
+
+        info!(
+            "[ArtNet] Listening on {} (universe {}, start address {})",
+            address, config.universe, config.patch.start_address
+        );
+
+        Ok(Self {
+            #[cfg(feature = "gui")]
+            address,
+            timecode_chase,
+            #[cfg(feature = "gui")]
+            shared,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run(
+        socket: UdpSocket,
+        config: ArtNetConfig,
+        timecode_feed: Option<ChaseFeed>,
+        state: AppState,
+        shared: Arc<Mutex<ArtNetShared>>,
+        running: Arc<AtomicBool>,
+    ) {
+        let mut mapper = DmxMapper::new(config.patch.clone());
+        let timecode_triggers = TimecodeTriggers::new();
+        // バインディングのしきい値判定用の前回フレーム
+        let mut previous: Vec<u8> = Vec::new();
+        let mut buf = [0u8; 1024];
+
+        let record_error = |error: String| {
+            warn!("[ArtNet] {}", error);
+            let mut shared = shared.lock();
+            shared.errors += 1;
+            shared.last_error = Some(error);
+        };
+
+        while running.load(Ordering::Relaxed) {
+            let (len, source) = match socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    continue
+                }
+                Err(e) => {
+                    warn!("[ArtNet] Receive error: {}", e);
+                    std::thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+            };
+
+            let Some(packet) = ArtNetPacket::parse(&buf[..len]) else {
+                continue;
+            };
+            shared.lock().packets_received += 1;
+
+            let (universe, data) = match packet {
+                ArtNetPacket::Dmx { universe, data, .. } => (universe, data),
+                ArtNetPacket::TimeCode {
+                    timecode,
+                    frame_rate,
+                } => {
+                    if let Some(feed) = &timecode_feed {
+                        feed.on_timecode(timecode, Some(frame_rate));
+                    }
+                    if let Err(e) = timecode_triggers.on_timecode(&state, timecode, frame_rate) {
+                        record_error(format!("Timecode trigger at {} failed: {}", timecode, e));
+                    }
+                    continue;
+                }
+                ArtNetPacket::Other(_) => continue,
+            };
+            if universe != config.universe {
+                continue;
+            }
+
+            let bound = control::bound_dmx_addresses(&state, universe);
+            let commands = mapper.on_frame(data, &bound);
+            {
+                let mut shared = shared.lock();
+                shared.dmx_frames += 1;
+                shared.last_source = Some(source.to_string());
+                shared.channel_values = mapper.values();
+            }
+
+            for command in commands {
+                debug!("[ArtNet] {:?}", command);
+                if let Err(e) = control::apply(&state, command.clone()) {
+                    record_error(format!("{:?} failed: {}", command, e));
+                }
+            }
+
+            let input = ControlInput::Dmx {
+                universe,
+                previous: &previous,
+                current: data,
+            };
+            if let Err(e) = control::dispatch(&state, &input) {
+                record_error(format!("DMX binding failed: {}", e));
+            }
+            previous.clear();
+            previous.extend_from_slice(data);
+        }
+
+        debug!("[ArtNet] Receive thread finished");
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> ArtNetStatus {
+        let shared = self.shared.lock();
+        ArtNetStatus {
+            running: self.running.load(Ordering::Relaxed),
+            address: Some(self.address.clone()),
+            packets_received: shared.packets_received,
+            dmx_frames: shared.dmx_frames,
+            last_source: shared.last_source.clone(),
+            channel_values: shared.channel_values.clone(),
+            errors: shared.errors,
+            last_error: shared.last_error.clone(),
+            timecode_chase: self.timecode_chase.as_ref().map(|chase| chase.status()),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[ArtNet] Stopped");
+        }
+        if let Some(mut chase) = self.timecode_chase.take() {
+            chase.stop();
+        }
+    }
+}
+
+impl Drop for ArtNetInput {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control::ControlCommand;
+
+    /// テスト用のArtDmxパケット
+    fn art_dmx(universe: u16, data: &[u8]) -> Vec<u8> {
+        let mut buf = ART_NET_ID.to_vec();
+        buf.extend_from_slice(&OP_DMX.to_le_bytes());
+        buf.extend_from_slice(&[0, 14, 1, 0]);
+        buf.extend_from_slice(&universe.to_le_bytes());
+        buf.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        buf.extend_from_slice(data);
+        buf
+    }
+
+    #[test]
+    fn test_parse_art_dmx() {
+        let buf = art_dmx(0x0123, &[10, 20, 30, 40]);
+        assert_eq!(
+            ArtNetPacket::parse(&buf),
+            Some(ArtNetPacket::Dmx {
+                sequence: 1,
+                universe: 0x0123,
+                data: &[10, 20, 30, 40]
+            })
+        );
+
+        // ArtPoll
+        let mut poll = ART_NET_ID.to_vec();
+        poll.extend_from_slice(&0x2000u16.to_le_bytes());
+        assert_eq!(
+            ArtNetPacket::parse(&poll),
+            Some(ArtNetPacket::Other(0x2000))
+        );
+
+        assert_eq!(ArtNetPacket::parse(b"not art-net"), None);
+        // 長さがデータより長い
+        let mut truncated = art_dmx(0, &[1, 2]);
+        truncated[17] = 4;
+        assert_eq!(ArtNetPacket::parse(&truncated), None);
+    }
+
+    #[test]
+    fn test_receive_from_local_client() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender
+            .send_to(&art_dmx(1, &[255; 4]), receiver.local_addr().unwrap())
+            .unwrap();
+
+        let mut buf = [0u8; 1024];
+        let (len, _) = receiver.recv_from(&mut buf).unwrap();
+        let Some(ArtNetPacket::Dmx { universe, data, .. }) = ArtNetPacket::parse(&buf[..len])
+        else {
+            panic!("not an ArtDmx packet");
+        };
+        assert_eq!(universe, 1);
+
+        let mut mapper = DmxMapper::new(Default::default());
+        assert!(mapper
+            .on_frame(data, &[])
+            .contains(&ControlCommand::MasterBrightness(100.0)));
+    }
+}
@@ -0,0 +1,237 @@
+//! DMX値とプレイヤー操作の対応
+//!
+//! レベル系のチャンネルは値が変わった時だけ適用し、GOは128をまたいで
+//! 上がった瞬間に1回だけ実行する。プロジェクトのバインディングのトリガーに
+//! 使われているチャンネルはバインディングを優先し、パッチでは操作しない。
+//!
+//! 受信開始前の明るさ・音量は `LocalLevels` に保存しておき、送信元が途絶えて
+//! ローカル操作に戻す場合に復元する。
+
+use crate::control::ControlCommand;
+use crate::error::AppResult;
+use crate::playback;
+use crate::state::AppState;
+use crate::types::{DmxChannelFunction, DmxPatch};
+
+/// GOとみなすしきい値
+const GO_THRESHOLD: u8 = 128;
+
+fn level(value: u8) -> f64 {
+    value as f64 * 100.0 / 255.0
+}
+
+/// パッチに従ってDMXフレームを操作に変換
+pub struct DmxMapper {
+    patch: DmxPatch,
+    /// 前回のチャンネル値（未受信はNone）
+    last: Vec<Option<u8>>,
+}
+
+impl DmxMapper {
+    pub fn new(patch: DmxPatch) -> Self {
+        let last = vec![None; patch.channels.len()];
+        Self { patch, last }
+    }
+
+    /// 前回値を破棄（次のフレームで全チャンネルを適用し直す）
+    pub fn reset(&mut self) {
+        self.last.iter_mut().for_each(|v| *v = None);
+    }
+
+    /// パッチしたチャンネルの現在値
+    pub fn values(&self) -> Vec<u8> {
+        self.last.iter().map(|v| v.unwrap_or(0)).collect()
+    }
+
+    /// 1ユニバース分のDMXデータ（スタートコードを除く）を処理
+    ///
+    /// `bound` はバインディングが使うチャンネル (1〜512) で、パッチでは操作しない。
+    pub fn on_frame(&mut self, data: &[u8], bound: &[u16]) -> Vec<ControlCommand> {
+        let start = self.patch.start_address.clamp(1, 512) as usize - 1;
+        let mut commands = Vec::new();
+
+        // GOより前にCue選択を確定させるため、先に全チャンネルの値を読む
+        let values: Vec<Option<u8>> = (0..self.patch.channels.len())
+            .map(|i| data.get(start + i).copied())
+            .collect();
+        let is_bound = |i: usize| bound.contains(&((start + i + 1) as u16));
+        let selected = self
+            .patch
+            .channels
+            .iter()
+            .zip(&values)
+            .enumerate()
+            .find(|(i, (function, _))| **function == DmxChannelFunction::CueSelect && !is_bound(*i))
+            .and_then(|(_, (_, value))| *value);
+
+        for (i, function) in self.patch.channels.iter().enumerate() {
+            // 短いフレームで届かなかったチャンネルは前回値を保持
+            let Some(value) = values[i] else {
+                continue;
+            };
+            let previous = self.last[i];
+            self.last[i] = Some(value);
+            if previous == Some(value) || is_bound(i) {
+                continue;
+            }
+
+            match function {
+                DmxChannelFunction::MasterBrightness => {
+                    commands.push(ControlCommand::MasterBrightness(level(value)))
+                }
+                DmxChannelFunction::MasterVolume => {
+                    commands.push(ControlCommand::MasterVolume(level(value)))
+                }
+                DmxChannelFunction::OutputBrightness { output_id } => {
+                    commands.push(ControlCommand::OutputBrightness {
+                        output_id: output_id.clone(),
+                        value: Some(level(value)),
+                    })
+                }
+                DmxChannelFunction::CueSelect => {}
+                DmxChannelFunction::Go => {
+                    // 受信開始時に既に上がっている場合は発火しない
+                    let was_low = previous.is_some_and(|p| p < GO_THRESHOLD);
+                    if was_low && value >= GO_THRESHOLD {
+                        commands.push(match selected {
+                            Some(cue) if cue > 0 => ControlCommand::GoCue(cue as usize - 1),
+                            _ => ControlCommand::Go,
+                        });
+                    }
+                }
+            }
+        }
+
+        commands
+    }
+}
+
+/// DMXで制御する前のローカルの値
+#[derive(Debug, Clone)]
+pub struct LocalLevels {
+    master_brightness: Option<f64>,
+    master_volume: Option<f64>,
+    outputs: Vec<(String, Option<f64>)>,
+}
+
+impl LocalLevels {
+    /// パッチで上書きされる値をプロジェクトから保存
+    pub fn capture(state: &AppState, patch: &DmxPatch) -> Self {
+        let project_guard = state.project.lock();
+        let project = project_guard.as_ref();
+        let patched = |f: &DmxChannelFunction| patch.channels.contains(f);
+
+        Self {
+            master_brightness: project
+                .filter(|_| patched(&DmxChannelFunction::MasterBrightness))
+                .map(|p| p.master_brightness),
+            master_volume: project
+                .filter(|_| patched(&DmxChannelFunction::MasterVolume))
+                .map(|p| p.master_volume),
+            outputs: patch
+                .channels
+                .iter()
+                .filter_map(|f| match f {
+                    DmxChannelFunction::OutputBrightness { output_id } => Some(output_id),
+                    _ => None,
+                })
+                .filter_map(|id| {
+                    project
+                        .and_then(|p| p.outputs.iter().find(|o| &o.id == id))
+                        .map(|o| (o.id.clone(), o.brightness))
+                })
+                .collect(),
+        }
+    }
+
+    /// 保存した値に戻す
+    pub fn restore(&self, state: &AppState) -> AppResult<()> {
+        if let Some(value) = self.master_brightness {
+            playback::set_master_brightness(state, value)?;
+        }
+        if let Some(value) = self.master_volume {
+            playback::set_master_volume(state, value)?;
+        }
+        for (output_id, value) in &self.outputs {
+            playback::set_output_brightness(state, output_id, *value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(start: usize, values: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; 512];
+        data[start - 1..start - 1 + values.len()].copy_from_slice(values);
+        data
+    }
+
+    #[test]
+    fn test_levels_only_on_change() {
+        let mut mapper = DmxMapper::new(DmxPatch {
+            start_address: 101,
+            ..Default::default()
+        });
+
+        let commands = mapper.on_frame(&frame(101, &[255, 0, 0, 0]), &[]);
+        assert_eq!(
+            commands,
+            vec![
+                ControlCommand::MasterBrightness(100.0),
+                ControlCommand::MasterVolume(0.0)
+            ]
+        );
+        assert!(mapper
+            .on_frame(&frame(101, &[255, 0, 0, 0]), &[])
+            .is_empty());
+        assert_eq!(mapper.values(), vec![255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_cue_select_and_go_edge() {
+        let mut mapper = DmxMapper::new(DmxPatch::default());
+
+        // 受信開始時点でGOが上がっていても発火しない
+        let commands = mapper.on_frame(&frame(1, &[0, 0, 0, 255]), &[]);
+        assert!(!commands.contains(&ControlCommand::Go));
+
+        mapper.on_frame(&frame(1, &[0, 0, 3, 0]), &[]);
+        assert_eq!(
+            mapper.on_frame(&frame(1, &[0, 0, 3, 200]), &[]),
+            vec![ControlCommand::GoCue(2)]
+        );
+        // 押しっぱなしでは再発火しない
+        assert!(mapper.on_frame(&frame(1, &[0, 0, 3, 255]), &[]).is_empty());
+
+        mapper.on_frame(&frame(1, &[0, 0, 0, 0]), &[]);
+        assert_eq!(
+            mapper.on_frame(&frame(1, &[0, 0, 0, 128]), &[]),
+            vec![ControlCommand::Go]
+        );
+    }
+
+    #[test]
+    fn test_bound_channels_are_skipped() {
+        let mut mapper = DmxMapper::new(DmxPatch::default());
+
+        // 明るさ(1)とGO(4)はバインディングが使う
+        assert_eq!(
+            mapper.on_frame(&frame(1, &[255, 0, 3, 0]), &[1, 4]),
+            vec![ControlCommand::MasterVolume(0.0)]
+        );
+        assert!(mapper
+            .on_frame(&frame(1, &[0, 0, 3, 200]), &[1, 4])
+            .is_empty());
+        assert_eq!(mapper.values(), vec![0, 0, 3, 200]);
+
+        // Cue選択(3)がバインディングに使われていれば次のCueへ
+        mapper.on_frame(&frame(1, &[0, 0, 3, 0]), &[3]);
+        assert_eq!(
+            mapper.on_frame(&frame(1, &[0, 0, 3, 200]), &[3]),
+            vec![ControlCommand::Go]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! DMX入力
+//!
+//! 照明卓からArt-Net・sACNで送られたDMX値を、フィクスチャとしてのパッチに従って
+//! 明るさ・音量・Cue選択とGOに割り当てる。Art-Net上のタイムコードの
+//! 受信（チェイス）と送信も扱う。
+
+pub mod art_timecode;
+pub mod artnet;
+pub mod mapping;
+pub mod sacn;
+
+pub use art_timecode::ArtTimecodeOutput;
+pub use artnet::ArtNetInput;
+pub use sacn::SacnInput;
+
+use crate::error::AppResult;
+use crate::state::AppState;
+use crate::types::{ArtNetConfig, ArtTimecodeOutputConfig, SacnConfig};
+
+/// 実行中のArt-Net入力を停止し、指定の設定で起動し直す
+pub fn restart_art_net(state: &AppState, config: &ArtNetConfig) -> AppResult<()> {
+    let mut art_net_guard = state.art_net.lock();
+    if let Some(mut existing) = art_net_guard.take() {
+        existing.stop();
+    }
+    *art_net_guard = Some(ArtNetInput::start(config, state.clone())?);
+    Ok(())
+}
+
+/// 実行中のsACN入力を停止し、指定の設定で起動し直す
+pub fn restart_sacn(state: &AppState, config: &SacnConfig) -> AppResult<()> {
+    let mut sacn_guard = state.sacn.lock();
+    if let Some(mut existing) = sacn_guard.take() {
+        existing.stop();
+    }
+    *sacn_guard = Some(SacnInput::start(config, state.clone())?);
+    Ok(())
+}
+
+/// 実行中のArtTimeCode送信を停止し、指定の設定で起動し直す
+pub fn restart_art_timecode_output(
+    state: &AppState,
+    config: &ArtTimecodeOutputConfig,
+) -> AppResult<()> {
+    let mut output_guard = state.art_timecode_output.lock();
+    if let Some(mut existing) = output_guard.take() {
+        existing.stop();
+    }
+    *output_guard = Some(ArtTimecodeOutput::start(config, state.clone())?);
+    Ok(())
+}
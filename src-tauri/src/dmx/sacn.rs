@@ -0,0 +1,523 @@
+//! sACN (E1.31) 入力
+//!
+//! マルチキャスト (239.255.<universe>) とユニキャストのデータパケットを受信する。
+//! 複数の送信元がある場合は優先度の最も高いものを採用し（同じ優先度なら
+//! 採用中の送信元を維持）、全ての送信元が途絶えたら設定に従って値を保持するか
+//! ローカル操作に戻す。
+
+use parking_lot::Mutex;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::control::{self, ControlInput};
+use crate::dmx::mapping::{DmxMapper, LocalLevels};
+use crate::error::{AppError, AppResult};
+use crate::state::AppState;
+#[cfg(feature = "gui")]
+use crate::types::SacnStatus;
+use crate::types::{DmxSourceLoss, SacnConfig, SacnSource};
+
+/// sACNのポート
+pub const SACN_PORT: u16 = 5568;
+
+/// ACNパケット識別子
+const ACN_PACKET_ID: &[u8; 12] = b"ASC-E1.17\0\0\0";
+
+const VECTOR_ROOT_E131_DATA: u32 = 0x0000_0004;
+const VECTOR_E131_DATA_PACKET: u32 = 0x0000_0002;
+
+/// データパケットのDMXデータ開始位置（スタートコードの次）
+const DMX_DATA_OFFSET: usize = 126;
+
+/// オプションのStream_Terminatedビット
+const OPTION_STREAM_TERMINATED: u8 = 0x40;
+/// オプションのPreview_Dataビット
+const OPTION_PREVIEW: u8 = 0x80;
+
+/// 停止確認・途絶判定の周期
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// ユニバースのマルチキャストグループ
+pub fn multicast_group(universe: u16) -> Ipv4Addr {
+    let [hi, lo] = universe.to_be_bytes();
+    Ipv4Addr::new(239, 255, hi, lo)
+}
+
+/// E1.31データパケット
+#[derive(Debug, Clone, PartialEq)]
+pub struct SacnPacket<'a> {
+    pub cid: [u8; 16],
+    pub source_name: String,
+    pub priority: u8,
+    pub sequence: u8,
+    pub options: u8,
+    pub universe: u16,
+    pub start_code: u8,
+    pub data: &'a [u8],
+}
+
+impl<'a> SacnPacket<'a> {
+    /// データパケットを解釈（それ以外はNone）
+    pub fn parse(buf: &'a [u8]) -> Option<Self> {
+        if buf.len() < DMX_DATA_OFFSET || &buf[4..16] != ACN_PACKET_ID {
+            return None;
+        }
+        let u32_at = |i: usize| u32::from_be_bytes([buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]);
+        if u32_at(18) != VECTOR_ROOT_E131_DATA || u32_at(40) != VECTOR_E131_DATA_PACKET {
+            return None;
+        }
+
+        let name = &buf[44..108];
+        let name_len = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+        let mut cid = [0u8; 16];
+        cid.copy_from_slice(&buf[22..38]);
+
+        // プロパティ数はスタートコードを含む
+        let count = u16::from_be_bytes([buf[123], buf[124]]) as usize;
+        let end = (DMX_DATA_OFFSET + count.saturating_sub(1).min(512)).min(buf.len());
+
+        Some(Self {
+            cid,
+            source_name: String::from_utf8_lossy(&name[..name_len]).into_owned(),
+            priority: buf[108],
+            sequence: buf[111],
+            options: buf[112],
+            universe: u16::from_be_bytes([buf[113], buf[114]]),
+            start_code: buf[125],
+            data: &buf[DMX_DATA_OFFSET..end],
+        })
+    }
+}
+
+/// 送信元ごとの受信状態
+#[derive(Debug, Clone)]
+struct SourceState {
+    cid: [u8; 16],
+    name: String,
+    address: SocketAddr,
+    priority: u8,
+    sequence: u8,
+    last_seen: Instant,
+}
+
+/// 送信元の選択
+pub struct SourceArbiter {
+    timeout: Duration,
+    sources: Vec<SourceState>,
+    active: Option<[u8; 16]>,
+}
+
+impl SourceArbiter {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            sources: Vec::new(),
+            active: None,
+        }
+    }
+
+    /// パケットを受信し、そのデータを採用すべきかを返す
+    pub fn on_packet(&mut self, packet: &SacnPacket, address: SocketAddr, now: Instant) -> bool {
+        if packet.options & OPTION_STREAM_TERMINATED != 0 {
+            self.sources.retain(|s| s.cid != packet.cid);
+            self.select();
+            return false;
+        }
+
+        match self.sources.iter_mut().find(|s| s.cid == packet.cid) {
+            Some(source) => {
+                // 直近20以内の古いシーケンスは順序が入れ替わったパケットとして破棄
+                let diff = packet.sequence.wrapping_sub(source.sequence) as i8;
+                if diff <= 0 && diff > -20 {
+                    return false;
+                }
+                source.sequence = packet.sequence;
+                source.priority = packet.priority;
+                source.address = address;
+                source.last_seen = now;
+            }
+            None => {
+                info!(
+                    "[sACN] Source appeared: {} ({}, priority {})",
+                    packet.source_name, address, packet.priority
+                );
+                self.sources.push(SourceState {
+                    cid: packet.cid,
+                    name: packet.source_name.clone(),
+                    address,
+                    priority: packet.priority,
+                    sequence: packet.sequence,
+                    last_seen: now,
+                });
+            }
+        }
+
+        self.expire(now);
+        self.active == Some(packet.cid)
+    }
+
+    /// タイムアウトした送信元を除き、採用する送信元を選び直す
+    pub fn expire(&mut self, now: Instant) {
+        let timeout = self.timeout;
+        self.sources.retain(|s| {
+            let alive = now.saturating_duration_since(s.last_seen) <= timeout;
+            if !alive {
+                info!("[sACN] Source lost: {} ({})", s.name, s.address);
+            }
+            alive
+        });
+        self.select();
+    }
+
+    fn select(&mut self) {
+        let Some(highest) = self.sources.iter().map(|s| s.priority).max() else {
+            self.active = None;
+            return;
+        };
+        let keep = self
+            .sources
+            .iter()
+            .any(|s| Some(s.cid) == self.active && s.priority == highest);
+        if !keep {
+            self.active = self
+                .sources
+                .iter()
+                .find(|s| s.priority == highest)
+                .map(|s| s.cid);
+        }
+    }
+
+    /// 採用中の送信元があるか
+    pub fn has_active(&self) -> bool {
+        self.active.is_some()
+    }
+
+    pub fn sources(&self) -> Vec<SacnSource> {
+        self.sources
+            .iter()
+            .map(|s| SacnSource {
+                cid: uuid::Uuid::from_bytes(s.cid).to_string(),
+                name: s.name.clone(),
+                address: s.address.to_string(),
+                priority: s.priority,
+                active: Some(s.cid) == self.active,
+            })
+            .collect()
+    }
+}
+
+/// 受信スレッドと共有する状態
+#[derive(Default)]
+struct SacnShared {
+    packets_received: u64,
+    dmx_frames: u64,
+    sources: Vec<SacnSource>,
+    channel_values: Vec<u8>,
+    errors: u64,
+    last_error: Option<String>,
+}
+
+/// sACN入力サービス
+pub struct SacnInput {
+    #[cfg(feature = "gui")]
+    address: String,
+    #[cfg(feature = "gui")]
+    shared: Arc<Mutex<SacnShared>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SacnInput {
+    /// 受信ポートを開く
+    ///
+    /// 同じPCの照明ソフト等もE1.31のマルチキャストを受けられるよう
+    /// SO_REUSEADDR を付ける。
+    fn bind() -> std::io::Result<UdpSocket> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, SACN_PORT).into())?;
+        Ok(socket.into())
+    }
+
+    pub fn start(config: &SacnConfig, state: AppState) -> AppResult<Self> {
+        if !(1..=63999).contains(&config.universe) {
+            return Err(AppError::Dmx(format!(
+                "Invalid sACN universe: {}",
+                config.universe
+            )));
+        }
+
+        let socket =
+            Self::bind().map_err(|e| AppError::Dmx(format!("Failed to bind sACN port: {}", e)))?;
+        socket.set_read_timeout(Some(POLL_INTERVAL))?;
+
+        if config.multicast {
+            let interface = match &config.interface {
+                Some(addr) => addr
+                    .parse()
+                    .map_err(|_| AppError::Dmx(format!("Invalid interface address: {}", addr)))?,
+                None => Ipv4Addr::UNSPECIFIED,
+            };
+            socket
+                .join_multicast_v4(&multicast_group(config.universe), &interface)
+                .map_err(|e| AppError::Dmx(format!("Failed to join multicast group: {}", e)))?;
+        }
+
+        let address = match config.multicast {
+            true => format!("{}:{}", multicast_group(config.universe), SACN_PORT),
+            false => socket.local_addr()?.to_string(),
+        };
+
+        let shared = Arc::new(Mutex::new(SacnShared::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let config = config.clone();
+            let shared = shared.clone();
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name("sacn-input".to_string())
+                .spawn(move || Self::run(socket, config, state, shared, running))
+                .map_err(AppError::Io)?
+        };
+
+        info!(
+            "[sACN] Listening on {} (universe {}, start address {})",
+            address, config.universe, config.patch.start_address
+        );
+
+        Ok(Self {
+            #[cfg(feature = "gui")]
+            address,
+            #[cfg(feature = "gui")]
+            shared,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run(
+        socket: UdpSocket,
+        config: SacnConfig,
+        state: AppState,
+        shared: Arc<Mutex<SacnShared>>,
+        running: Arc<AtomicBool>,
+    ) {
+        let mut mapper = DmxMapper::new(config.patch.clone());
+        let mut arbiter = SourceArbiter::new(Duration::from_millis(config.source_timeout_ms));
+        let mut local_levels: Option<LocalLevels> = None;
+        // バインディングのしきい値判定用の前回フレーム
+        let mut previous: Vec<u8> = Vec::new();
+        let mut buf = [0u8; 1144];
+
+        let record_error = |error: String| {
+            warn!("[sACN] {}", error);
+            let mut shared = shared.lock();
+            shared.errors += 1;
+            shared.last_error = Some(error);
+        };
+
+        while running.load(Ordering::Relaxed) {
+            let received = match socket.recv_from(&mut buf) {
+                Ok(received) => Some(received),
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    None
+                }
+                Err(e) => {
+                    warn!("[sACN] Receive error: {}", e);
+                    std::thread::sleep(POLL_INTERVAL);
+                    None
+                }
+            };
+
+            let now = Instant::now();
+            let had_active = arbiter.has_active();
+            let mut frame = None;
+
+            if let Some((len, source)) = received {
+                if let Some(packet) = SacnPacket::parse(&buf[..len]) {
+                    shared.lock().packets_received += 1;
+                    // DMX以外のスタートコード・プレビューデータは使わない
+                    if packet.universe == config.universe
+                        && packet.start_code == 0
+                        && packet.options & OPTION_PREVIEW == 0
+                        && arbiter.on_packet(&packet, source, now)
+                    {
+                        frame = Some(packet.data.to_vec());
+                    }
+                }
+            }
+            arbiter.expire(now);
+
+            // 最初の送信元を採用した時点のローカル値を保存
+            if !had_active && arbiter.has_active() && config.source_loss == DmxSourceLoss::Release {
+                local_levels = Some(LocalLevels::capture(&state, &config.patch));
+            }
+
+            if had_active && !arbiter.has_active() {
+                warn!("[sACN] All sources lost ({:?})", config.source_loss);
+                previous.clear();
+                if let Some(levels) = local_levels.take() {
+                    mapper.reset();
+                    if let Err(e) = levels.restore(&state) {
+                        record_error(format!("Failed to release to local control: {}", e));
+                    }
+                }
+            }
+
+            let accepted = frame.is_some();
+            let commands = frame
+                .as_ref()
+                .map(|data| {
+                    let bound = control::bound_dmx_addresses(&state, config.universe);
+                    mapper.on_frame(data, &bound)
+                })
+                .unwrap_or_default();
+            {
+                let mut shared = shared.lock();
+                shared.sources = arbiter.sources();
+                shared.channel_values = mapper.values();
+                if accepted {
+                    shared.dmx_frames += 1;
+                }
+            }
+
+            for command in commands {
+                debug!("[sACN] {:?}", command);
+                if let Err(e) = control::apply(&state, command.clone()) {
+                    record_error(format!("{:?} failed: {}", command, e));
+                }
+            }
+
+            if let Some(data) = frame {
+                let input = ControlInput::Dmx {
+                    universe: config.universe,
+                    previous: &previous,
+                    current: &data,
+                };
+                if let Err(e) = control::dispatch(&state, &input) {
+                    record_error(format!("DMX binding failed: {}", e));
+                }
+                previous = data;
+            }
+        }
+
+        debug!("[sACN] Receive thread finished");
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> SacnStatus {
+        let shared = self.shared.lock();
+        SacnStatus {
+            running: self.running.load(Ordering::Relaxed),
+            address: Some(self.address.clone()),
+            packets_received: shared.packets_received,
+            dmx_frames: shared.dmx_frames,
+            sources: shared.sources.clone(),
+            channel_values: shared.channel_values.clone(),
+            errors: shared.errors,
+            last_error: shared.last_error.clone(),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[sACN] Stopped");
+        }
+    }
+}
+
+impl Drop for SacnInput {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テスト用のデータパケット
+    fn data_packet(cid: u8, priority: u8, sequence: u8, options: u8, data: &[u8]) -> Vec<u8> {
+        let mut buf = vec![0u8; DMX_DATA_OFFSET];
+        buf[0..2].copy_from_slice(&0x0010u16.to_be_bytes());
+        buf[4..16].copy_from_slice(ACN_PACKET_ID);
+        buf[18..22].copy_from_slice(&VECTOR_ROOT_E131_DATA.to_be_bytes());
+        buf[22..38].copy_from_slice(&[cid; 16]);
+        buf[40..44].copy_from_slice(&VECTOR_E131_DATA_PACKET.to_be_bytes());
+        buf[44..48].copy_from_slice(b"Desk");
+        buf[108] = priority;
+        buf[111] = sequence;
+        buf[112] = options;
+        buf[113..115].copy_from_slice(&1u16.to_be_bytes());
+        buf[123..125].copy_from_slice(&(data.len() as u16 + 1).to_be_bytes());
+        buf.extend_from_slice(data);
+        buf
+    }
+
+    fn addr() -> SocketAddr {
+        "192.168.1.10:5568".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_data_packet() {
+        let buf = data_packet(1, 100, 7, 0, &[1, 2, 3]);
+        let packet = SacnPacket::parse(&buf).unwrap();
+        assert_eq!(packet.source_name, "Desk");
+        assert_eq!(packet.priority, 100);
+        assert_eq!(packet.sequence, 7);
+        assert_eq!(packet.universe, 1);
+        assert_eq!(packet.data, &[1, 2, 3]);
+        assert_eq!(multicast_group(0x0102), Ipv4Addr::new(239, 255, 1, 2));
+        assert_eq!(SacnPacket::parse(&buf[..100]), None);
+    }
+
+    #[test]
+    fn test_priority_and_sequence() {
+        let mut arbiter = SourceArbiter::new(Duration::from_millis(2500));
+        let now = Instant::now();
+
+        let low = data_packet(1, 100, 1, 0, &[0]);
+        let high = data_packet(2, 150, 1, 0, &[0]);
+        assert!(arbiter.on_packet(&SacnPacket::parse(&low).unwrap(), addr(), now));
+        assert!(arbiter.on_packet(&SacnPacket::parse(&high).unwrap(), addr(), now));
+
+        let low = data_packet(1, 100, 2, 0, &[0]);
+        assert!(!arbiter.on_packet(&SacnPacket::parse(&low).unwrap(), addr(), now));
+
+        // 古いシーケンスは破棄
+        let stale = data_packet(2, 150, 0, 0, &[0]);
+        assert!(!arbiter.on_packet(&SacnPacket::parse(&stale).unwrap(), addr(), now));
+
+        // 優先度の高い送信元が終了したら低い方へ
+        let terminated = data_packet(2, 150, 2, OPTION_STREAM_TERMINATED, &[0]);
+        assert!(!arbiter.on_packet(&SacnPacket::parse(&terminated).unwrap(), addr(), now));
+        let low = data_packet(1, 100, 3, 0, &[0]);
+        assert!(arbiter.on_packet(&SacnPacket::parse(&low).unwrap(), addr(), now));
+    }
+
+    #[test]
+    fn test_source_timeout() {
+        let mut arbiter = SourceArbiter::new(Duration::from_millis(2500));
+        let now = Instant::now();
+        let packet = data_packet(1, 100, 1, 0, &[0]);
+        arbiter.on_packet(&SacnPacket::parse(&packet).unwrap(), addr(), now);
+        assert!(arbiter.has_active());
+
+        arbiter.expire(now + Duration::from_millis(2000));
+        assert!(arbiter.has_active());
+        arbiter.expire(now + Duration::from_millis(3000));
+        assert!(!arbiter.has_active());
+        assert!(arbiter.sources().is_empty());
+    }
+}
@@ -14,6 +14,7 @@ pub enum AppError {
     #[error("Project error: {0}")]
     Project(String),
 
+    #[cfg(feature = "gui")]
     #[error("File error: {0}")]
     File(String),
 
@@ -32,6 +33,18 @@ pub enum AppError {
     #[error("Spout error: {0}")]
     Spout(String),
 
+    #[error("DMX error: {0}")]
+    Dmx(String),
+
+    #[error("MIDI error: {0}")]
+    Midi(String),
+
+    #[error("OSC error: {0}")]
+    Osc(String),
+
+    #[error("Sync error: {0}")]
+    Sync(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
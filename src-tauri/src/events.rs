@@ -0,0 +1,42 @@
+//! イベントバス
+//!
+//! バックグラウンドスレッドから発行したイベントを購読者へブロードキャストする。
+//! Tauriアプリではsetupで購読し、フロントエンドへ "app-event" として転送する。
+
+use tokio::sync::broadcast;
+
+use crate::types::AppEvent;
+
+/// 購読者が追いつけない場合に保持するイベント数
+const EVENT_CAPACITY: usize = 256;
+
+/// フロントエンドへ転送する際のイベント名
+#[cfg(feature = "gui")]
+pub const APP_EVENT: &str = "app-event";
+
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<AppEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CAPACITY);
+        Self { sender }
+    }
+
+    /// イベントを発行（購読者がいなければ破棄）
+    pub fn emit(&self, event: AppEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
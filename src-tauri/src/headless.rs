@@ -0,0 +1,350 @@
+//! ヘッドレス実行
+//!
+//! Webviewを使わずにプロジェクトを読み込み、CuePlayer・出力パイプライン・
+//! 外部制御（OSC・HTTP・MIDI・DMX）とマルチPC同期を動かす。バックステージの
+//! レンダーノードやスレーブ機など、UIの不要なマシン向け。ログは標準出力へ出す。
+//!
+//! Display出力はOutputManagerのウィンドウを使わず、GStreamerのビデオシンクが
+//! 直接描画する。Tauriに依存しないので、バイナリは `--no-default-features`
+//! （guiフィーチャーなし）でビルドする。
+
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::error::{AppError, AppResult};
+use crate::local_config;
+use crate::playback;
+use crate::project;
+use crate::state::AppState;
+use crate::sync;
+use crate::types::{LocalConfig, PlayerState, SyncMode};
+
+/// 再生状態をログに出す間隔
+const STATUS_INTERVAL: Duration = Duration::from_millis(500);
+
+pub const USAGE: &str = "\
+Usage: tauri-live-player-headless --project <PATH> [OPTIONS]
+
+Options:
+  -p, --project <PATH>       Project file to load
+  -c, --cue <N>              Cue to load on startup (1-based)
+      --play                 Start playing the loaded cue
+  -r, --role <ROLE>          Sync role: off, master or slave (overrides local settings)
+      --control-port <PORT>  Start the OSC server (UDP/TCP) on this port
+  -h, --help                 Print this help";
+
+/// コマンドライン引数
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessArgs {
+    pub project: PathBuf,
+    /// 起動時に読み込むCue（0始まり）
+    pub cue: Option<usize>,
+    pub play: bool,
+    pub role: Option<SyncMode>,
+    pub control_port: Option<u16>,
+}
+
+impl HeadlessArgs {
+    /// 引数を解釈（`--help` の場合はNone）
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut project = None;
+        let mut cue = None;
+        let mut play = false;
+        let mut role = None;
+        let mut control_port = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // --flag=value と --flag value の両方を受け付ける
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = |name: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} requires a value", name))
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-p" | "--project" => project = Some(PathBuf::from(value(&flag)?)),
+                "-c" | "--cue" => {
+                    let n: usize = value(&flag)?
+                        .parse()
+                        .map_err(|_| "--cue must be a positive integer".to_string())?;
+                    if n == 0 {
+                        return Err("--cue is 1-based".to_string());
+                    }
+                    cue = Some(n - 1);
+                }
+                "--play" => play = true,
+                "-r" | "--role" => {
+                    role = Some(match value(&flag)?.to_lowercase().as_str() {
+                        "off" => SyncMode::Off,
+                        "master" => SyncMode::Master,
+                        "slave" => SyncMode::Slave,
+                        other => return Err(format!("Unknown role: {}", other)),
+                    })
+                }
+                "--control-port" => {
+                    control_port = Some(
+                        value(&flag)?
+                            .parse()
+                            .map_err(|_| "--control-port must be a port number".to_string())?,
+                    )
+                }
+                other if !other.starts_with('-') && project.is_none() => {
+                    project = Some(PathBuf::from(other))
+                }
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+
+        let project = project.ok_or_else(|| "--project is required".to_string())?;
+        if play && cue.is_none() {
+            return Err("--play requires --cue".to_string());
+        }
+
+        Ok(Some(Self {
+            project,
+            cue,
+            play,
+            role,
+            control_port,
+        }))
+    }
+
+    /// コマンドラインの指定をこのPCの設定に上書き
+    pub fn apply_local(&self, local: &mut LocalConfig) {
+        if let Some(role) = self.role {
+            local.sync_role = Some(role);
+        }
+        if let Some(port) = self.control_port {
+            local.ports.osc = Some(port);
+        }
+    }
+}
+
+/// プロジェクトを読み込んで再生し、Ctrl+C / SIGTERMまで動作
+pub fn run(args: HeadlessArgs) -> AppResult<()> {
+    let state = AppState::new();
+    state
+        .init_player()
+        .map_err(|e| AppError::GStreamer(e.to_string()))?;
+    info!("[Headless] GStreamer initialized");
+
+    // コマンドラインの指定はこのPCの設定より優先
+    let mut local = local_config::load_or_default();
+    args.apply_local(&mut local);
+    *state.local_config.lock() = local.clone();
+
+    let mut project = project::read(&args.project)?;
+    if args.control_port.is_some() {
+        project.settings.osc.enabled = true;
+    }
+    info!(
+        "[Headless] Loaded project '{}' ({} cues) from {}",
+        project.name,
+        project.cues.len(),
+        args.project.display()
+    );
+
+    let sync_config = local_config::apply_sync(&project.sync, &local);
+    project::activate(&state, project);
+
+    sync::restart(&state, &sync_config)?;
+    if sync_config.mode != SyncMode::Off {
+        info!("[Headless] Sync started as {:?}", sync_config.mode);
+    }
+
+    if let Some(cue) = args.cue {
+        if args.play {
+            playback::go_cue(&state, cue)?;
+        } else {
+            playback::load_cue(&state, cue)?;
+        }
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(wait_for_shutdown(&state));
+
+    shutdown(&state);
+    Ok(())
+}
+
+/// 終了シグナルを待ちながら再生状態の変化をログに出す
+async fn wait_for_shutdown(state: &AppState) {
+    let signal = shutdown_signal();
+    tokio::pin!(signal);
+
+    let mut interval = tokio::time::interval(STATUS_INTERVAL);
+    let mut last: Option<PlayerState> = None;
+    loop {
+        tokio::select! {
+            _ = &mut signal => {
+                info!("[Headless] Shutting down");
+                return;
+            }
+            _ = interval.tick() => {
+                let current = playback::player_state(state);
+                let changed = last.as_ref().is_none_or(|last| {
+                    last.status != current.status
+                        || last.current_cue_index != current.current_cue_index
+                        || last.error != current.error
+                });
+                if changed {
+                    info!(
+                        "[Headless] {:?} cue={} {:.2}/{:.2}s{}",
+                        current.status,
+                        current.current_cue_index + 1,
+                        current.current_time,
+                        current.duration,
+                        current
+                            .error
+                            .as_ref()
+                            .map(|e| format!(" error={}", e))
+                            .unwrap_or_default()
+                    );
+                }
+                last = Some(current);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn shutdown_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+        }
+        Err(e) => {
+            warn!("[Headless] Failed to listen for SIGTERM: {}", e);
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// バックグラウンドサービスと再生を停止
+///
+/// サービスのスレッドはAppStateのクローンを保持しているため、
+/// ドロップに任せず明示的に停止する。
+fn shutdown(state: &AppState) {
+    if let Some(mut sync) = state.sync.lock().take() {
+        sync.stop();
+    }
+    if let Some(mut server) = state.osc.lock().take() {
+        server.stop();
+    }
+    if let Some(mut server) = state.http.lock().take() {
+        server.stop();
+    }
+    if let Some(mut input) = state.midi.lock().take() {
+        input.stop();
+    }
+    if let Some(mut input) = state.art_net.lock().take() {
+        input.stop();
+    }
+    if let Some(mut input) = state.sacn.lock().take() {
+        input.stop();
+    }
+    if let Some(mut output) = state.art_timecode_output.lock().take() {
+        output.stop();
+    }
+    if let Err(e) = playback::stop(state) {
+        warn!("[Headless] Failed to stop playback: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<HeadlessArgs>, String> {
+        HeadlessArgs::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_flags() {
+        let args = parse(&[
+            "--project",
+            "show.json",
+            "--cue=3",
+            "--play",
+            "-r",
+            "Slave",
+            "--control-port",
+            "9000",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            args,
+            HeadlessArgs {
+                project: PathBuf::from("show.json"),
+                cue: Some(2),
+                play: true,
+                role: Some(SyncMode::Slave),
+                control_port: Some(9000),
+            }
+        );
+
+        let positional = parse(&["show.json"]).unwrap().unwrap();
+        assert_eq!(positional.project, PathBuf::from("show.json"));
+        assert_eq!(positional.role, None);
+
+        assert_eq!(parse(&["--help"]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_apply_local() {
+        let mut local = LocalConfig {
+            sync_role: Some(SyncMode::Slave),
+            ..Default::default()
+        };
+        local.ports.osc = Some(8000);
+        local.ports.http = Some(8080);
+
+        parse(&["show.json", "--role", "off"])
+            .unwrap()
+            .unwrap()
+            .apply_local(&mut local);
+        assert_eq!(local.sync_role, Some(SyncMode::Off));
+        assert_eq!(local.ports.osc, Some(8000));
+
+        parse(&["show.json", "--control-port", "9000"])
+            .unwrap()
+            .unwrap()
+            .apply_local(&mut local);
+        assert_eq!(local.sync_role, Some(SyncMode::Off));
+        assert_eq!(local.ports.osc, Some(9000));
+        assert_eq!(local.ports.http, Some(8080));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["show.json", "--cue", "0"]).is_err());
+        assert!(parse(&["show.json", "--play"]).is_err());
+        assert!(parse(&["show.json", "--role", "backup"]).is_err());
+        assert!(parse(&["show.json", "--control-port"]).is_err());
+        assert!(parse(&["show.json", "other.json"]).is_err());
+    }
+}
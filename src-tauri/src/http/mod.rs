@@ -0,0 +1,137 @@
+//! HTTP / WebSocket リモート制御API
+//!
+//! LAN上のタブレット等からCueを操作できるよう、プロジェクト・Cue一覧・
+//! プレイヤー状態をJSONで公開する。`/api/ws` では状態とアプリケーション
+//! イベントを配信する。
+//!
+//! トークンを設定した場合は全てのリクエストにトークンを要求する
+//! （LANに公開するときは設定するか、bind_addressを絞る）。
+
+mod routes;
+mod ws;
+
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+use crate::error::{AppError, AppResult};
+use crate::state::AppState;
+use crate::types::HttpConfig;
+#[cfg(feature = "gui")]
+use crate::types::HttpStatus;
+
+/// リクエスト処理と共有する状態
+#[derive(Default)]
+struct HttpShared {
+    running: AtomicBool,
+    ws_clients: AtomicUsize,
+    requests: AtomicU64,
+}
+
+/// HTTP / WebSocket APIサーバー
+pub struct HttpServer {
+    shared: Arc<HttpShared>,
+    #[cfg(feature = "gui")]
+    address: String,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HttpServer {
+    pub fn start(config: &HttpConfig, state: AppState) -> AppResult<Self> {
+        let listener = TcpListener::bind((config.bind_address.as_str(), config.port))?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?.to_string();
+
+        let shared = Arc::new(HttpShared {
+            running: AtomicBool::new(true),
+            ..Default::default()
+        });
+        let router = routes::router(routes::ApiState {
+            app: state,
+            shared: shared.clone(),
+            token: config.token.clone().filter(|t| !t.is_empty()),
+        });
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+
+        let thread = std::thread::Builder::new()
+            .name("http-server".to_string())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(2)
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        error!("[HTTP] Failed to create runtime: {}", e);
+                        return;
+                    }
+                };
+
+                let result = runtime.block_on(async move {
+                    let listener = tokio::net::TcpListener::from_std(listener)?;
+                    axum::serve(listener, router)
+                        .with_graceful_shutdown(async {
+                            let _ = shutdown_rx.await;
+                        })
+                        .await
+                });
+                if let Err(e) = result {
+                    error!("[HTTP] Server error: {}", e);
+                }
+            })
+            .map_err(AppError::Io)?;
+
+        info!("[HTTP] Listening on {}", address);
+
+        Ok(Self {
+            shared,
+            #[cfg(feature = "gui")]
+            address,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> HttpStatus {
+        HttpStatus {
+            running: self.shared.running.load(Ordering::Relaxed),
+            address: Some(self.address.clone()),
+            ws_clients: self.shared.ws_clients.load(Ordering::Relaxed),
+            requests: self.shared.requests.load(Ordering::Relaxed),
+        }
+    }
+
+    /// サーバーを停止（WebSocket接続は次の状態確認で閉じる）
+    pub fn stop(&mut self) {
+        self.shared.running.store(false, Ordering::Relaxed);
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[HTTP] Stopped");
+        }
+    }
+}
+
+impl Drop for HttpServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 実行中のサーバーを停止し、指定の設定で起動し直す
+pub fn restart(state: &AppState, config: &HttpConfig) -> AppResult<()> {
+    let mut http_guard = state.http.lock();
+    if let Some(mut existing) = http_guard.take() {
+        existing.stop();
+    }
+    *http_guard = Some(HttpServer::start(config, state.clone())?);
+    Ok(())
+}
@@ -0,0 +1,354 @@
+//! RESTエンドポイント
+//!
+//! | メソッド | パス                      | 内容                              |
+//! |----------|---------------------------|-----------------------------------|
+//! | GET      | /api/project              | プロジェクト                       |
+//! | GET      | /api/cues                 | Cue一覧                           |
+//! | GET      | /api/cues/{index}         | Cue                               |
+//! | POST     | /api/cues/{index}/load    | Cueを読み込み                      |
+//! | POST     | /api/cues/{index}/go      | Cueを読み込んで再生                 |
+//! | GET      | /api/state                | プレイヤー状態                     |
+//! | POST     | /api/go                   | GO                                |
+//! | POST     | /api/play, /pause, /stop  | 再生制御                           |
+//! | POST     | /api/seek                 | `{ "position": 秒 }`              |
+//! | POST     | /api/master/brightness    | `{ "value": 0-100 }`              |
+//! | POST     | /api/master/volume        | `{ "value": 0-100 }`              |
+//! | POST     | /api/trigger/{name}       | HTTPトリガー（`{ "value": n }` は任意） |
+//! | POST     | /api/undo, /api/redo      | 編集を元に戻す・やり直す             |
+//! | GET      | /api/ws                   | WebSocket（状態・イベントの配信）    |
+//!
+//! Cueのインデックスは Tauri コマンドと同じく0始まり。
+//! トークンを設定した場合、トークンのないリクエストは401を返す。
+
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use super::{ws, HttpShared};
+use crate::control::{self, ControlInput};
+use crate::error::{AppError, AppResult};
+use crate::playback;
+use crate::project;
+use crate::state::AppState;
+use crate::types::{Cue, PlayerState, Project};
+
+/// ハンドラーと共有する状態
+#[derive(Clone)]
+pub(super) struct ApiState {
+    pub app: AppState,
+    pub(super) shared: Arc<HttpShared>,
+    /// 要求するトークン（Noneなら認証なし）
+    pub(super) token: Option<String>,
+}
+
+/// AppErrorをHTTPステータスとJSONのエラーに変換
+pub(super) struct ApiError(AppError);
+
+impl From<AppError> for ApiError {
+    fn from(error: AppError) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::InvalidState(_) => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let body = Json(serde_json::json!({ "error": self.0.to_string() }));
+        (status, body).into_response()
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
+#[derive(Deserialize)]
+struct SeekRequest {
+    position: f64,
+}
+
+#[derive(Deserialize)]
+struct LevelRequest {
+    value: f64,
+}
+
+#[derive(Deserialize)]
+struct TriggerRequest {
+    value: Option<f64>,
+}
+
+pub(super) fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/api/project", get(get_project))
+        .route("/api/cues", get(get_cues))
+        .route("/api/cues/{index}", get(get_cue))
+        .route("/api/cues/{index}/load", post(load_cue))
+        .route("/api/cues/{index}/go", post(go_cue))
+        .route("/api/state", get(get_state))
+        .route("/api/go", post(go))
+        .route("/api/play", post(play))
+        .route("/api/pause", post(pause))
+        .route("/api/stop", post(stop))
+        .route("/api/seek", post(seek))
+        .route("/api/master/brightness", post(set_master_brightness))
+        .route("/api/master/volume", post(set_master_volume))
+        .route("/api/trigger/{name}", post(trigger))
+        .route("/api/undo", post(undo))
+        .route("/api/redo", post(redo))
+        .route("/api/ws", get(ws::handler))
+        .layer(middleware::from_fn_with_state(state.clone(), check_token))
+        .layer(middleware::from_fn_with_state(state.clone(), count_request))
+        .with_state(state)
+}
+
+async fn count_request(State(api): State<ApiState>, request: Request, next: Next) -> Response {
+    api.shared.requests.fetch_add(1, Ordering::Relaxed);
+    next.run(request).await
+}
+
+/// リクエストのトークン（Authorizationヘッダーか、WebSocket用の `token` クエリ）
+fn request_token(request: &Request) -> Option<&str> {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    bearer.or_else(|| {
+        request
+            .uri()
+            .query()?
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+    })
+}
+
+async fn check_token(State(api): State<ApiState>, request: Request, next: Next) -> Response {
+    match &api.token {
+        Some(token) if request_token(&request) != Some(token.as_str()) => {
+            let body = Json(serde_json::json!({ "error": "Invalid or missing token" }));
+            (StatusCode::UNAUTHORIZED, body).into_response()
+        }
+        _ => next.run(request).await,
+    }
+}
+
+/// プレイヤー操作はパイプライン構築等で時間がかかるため、別スレッドで実行
+async fn run(
+    api: &ApiState,
+    f: impl FnOnce(&AppState) -> AppResult<()> + Send + 'static,
+) -> ApiResult<StatusCode> {
+    let app = api.app.clone();
+    tokio::task::spawn_blocking(move || f(&app))
+        .await
+        .map_err(|e| AppError::InvalidState(e.to_string()))??;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn project(api: &ApiState) -> AppResult<Project> {
+    api.app
+        .project
+        .lock()
+        .clone()
+        .ok_or_else(|| AppError::NotFound("No project loaded".to_string()))
+}
+
+async fn get_project(State(api): State<ApiState>) -> ApiResult<Json<Project>> {
+    Ok(Json(project(&api)?))
+}
+
+async fn get_cues(State(api): State<ApiState>) -> ApiResult<Json<Vec<Cue>>> {
+    Ok(Json(project(&api)?.cues))
+}
+
+async fn get_cue(State(api): State<ApiState>, Path(index): Path<usize>) -> ApiResult<Json<Cue>> {
+    project(&api)?
+        .cues
+        .into_iter()
+        .nth(index)
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound("Cue not found".to_string()).into())
+}
+
+async fn load_cue(State(api): State<ApiState>, Path(index): Path<usize>) -> ApiResult<StatusCode> {
+    run(&api, move |app| playback::load_cue(app, index)).await
+}
+
+async fn go_cue(State(api): State<ApiState>, Path(index): Path<usize>) -> ApiResult<StatusCode> {
+    run(&api, move |app| playback::go_cue(app, index)).await
+}
+
+async fn get_state(State(api): State<ApiState>) -> Json<PlayerState> {
+    Json(playback::player_state(&api.app))
+}
+
+async fn go(State(api): State<ApiState>) -> ApiResult<StatusCode> {
+    run(&api, playback::go).await
+}
+
+async fn play(State(api): State<ApiState>) -> ApiResult<StatusCode> {
+    run(&api, playback::play).await
+}
+
+async fn pause(State(api): State<ApiState>) -> ApiResult<StatusCode> {
+    run(&api, playback::pause).await
+}
+
+async fn stop(State(api): State<ApiState>) -> ApiResult<StatusCode> {
+    run(&api, playback::stop).await
+}
+
+async fn seek(
+    State(api): State<ApiState>,
+    Json(request): Json<SeekRequest>,
+) -> ApiResult<StatusCode> {
+    let position = request.position.max(0.0);
+    run(&api, move |app| playback::seek(app, position)).await
+}
+
+async fn set_master_brightness(
+    State(api): State<ApiState>,
+    Json(request): Json<LevelRequest>,
+) -> ApiResult<StatusCode> {
+    let value = request.value.clamp(0.0, 100.0);
+    run(&api, move |app| playback::set_master_brightness(app, value)).await
+}
+
+async fn set_master_volume(
+    State(api): State<ApiState>,
+    Json(request): Json<LevelRequest>,
+) -> ApiResult<StatusCode> {
+    let value = request.value.clamp(0.0, 100.0);
+    run(&api, move |app| playback::set_master_volume(app, value)).await
+}
+
+/// 元に戻す編集が無ければ409
+async fn undo(State(api): State<ApiState>) -> ApiResult<StatusCode> {
+    run(&api, |app| project::undo(app).map(|_| ())).await
+}
+
+async fn redo(State(api): State<ApiState>) -> ApiResult<StatusCode> {
+    run(&api, |app| project::redo(app).map(|_| ())).await
+}
+
+/// 一致するバインディングが無ければ404
+async fn trigger(
+    State(api): State<ApiState>,
+    Path(name): Path<String>,
+    body: Option<Json<TriggerRequest>>,
+) -> ApiResult<StatusCode> {
+    let value = body.and_then(|Json(body)| body.value);
+    run(&api, move |app| {
+        let input = ControlInput::Http { name: &name, value };
+        match control::dispatch(app, &input)? {
+            0 => Err(AppError::NotFound(format!(
+                "No binding for trigger: {}",
+                name
+            ))),
+            _ => Ok(()),
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use tower::ServiceExt;
+
+    fn api(token: Option<&str>) -> ApiState {
+        let app = AppState::new();
+        *app.project.lock() = Some(Project {
+            cues: vec![Cue {
+                id: "c1".to_string(),
+                name: "Opening".to_string(),
+                duration: 10.0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        ApiState {
+            app,
+            shared: Arc::new(HttpShared::default()),
+            token: token.map(str::to_string),
+        }
+    }
+
+    async fn send(api: ApiState, method: &str, uri: &str, token: Option<&str>) -> Response {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        router(api)
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn error_message(response: Response) -> String {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        json["error"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_api_error_status() {
+        let cases = [
+            (AppError::NotFound("x".to_string()), StatusCode::NOT_FOUND),
+            (
+                AppError::InvalidState("x".to_string()),
+                StatusCode::CONFLICT,
+            ),
+            (
+                AppError::Project("x".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+        for (error, status) in cases {
+            let message = error.to_string();
+            let response = ApiError::from(error).into_response();
+            assert_eq!(response.status(), status);
+            assert_eq!(error_message(response).await, message);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes() {
+        let response = send(api(None), "GET", "/api/cues/0", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(api(None), "GET", "/api/cues/1", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = send(api(None), "POST", "/api/trigger/house-lights", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(error_message(response).await.contains("house-lights"));
+
+        // 編集履歴が空
+        let response = send(api(None), "POST", "/api/undo", None).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_token() {
+        let response = send(api(Some("secret")), "GET", "/api/cues", None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = send(api(Some("secret")), "GET", "/api/cues", Some("wrong")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = send(api(Some("secret")), "GET", "/api/cues", Some("secret")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(api(Some("secret")), "GET", "/api/cues?token=secret", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
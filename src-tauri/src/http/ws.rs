@@ -0,0 +1,88 @@
+//! WebSocketによる状態配信
+//!
+//! 接続時と状態（ステータス・Cue）の変化時、再生中の経過時間は一定間隔で
+//! `{"type": "state", "data": PlayerState}` を送り、アプリケーションイベントは
+//! `{"type": "event", "data": AppEvent}` として転送する。
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::debug;
+
+use super::routes::ApiState;
+use crate::playback;
+use crate::types::{AppEvent, PlayerState};
+
+/// 状態の変化を確認する間隔
+const STATE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 変化がなくても状態を送る間隔
+const STATE_SEND_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+enum WsMessage {
+    State(PlayerState),
+    Event(AppEvent),
+}
+
+pub(super) async fn handler(ws: WebSocketUpgrade, State(api): State<ApiState>) -> Response {
+    ws.on_upgrade(move |socket| stream(socket, api))
+}
+
+async fn stream(mut socket: WebSocket, api: ApiState) {
+    api.shared.ws_clients.fetch_add(1, Ordering::Relaxed);
+    debug!("[HTTP] WebSocket client connected");
+
+    let mut events = api.app.events.subscribe();
+    let mut ticker = tokio::time::interval(STATE_POLL_INTERVAL);
+    let mut last_state: Option<PlayerState> = None;
+    let mut last_sent = Instant::now();
+
+    while api.shared.running.load(Ordering::Relaxed) {
+        let message = tokio::select! {
+            _ = ticker.tick() => {
+                let state = playback::player_state(&api.app);
+                let changed = last_state.as_ref().is_none_or(|last| {
+                    last.status != state.status
+                        || last.current_cue_index != state.current_cue_index
+                        || last.error != state.error
+                });
+                if changed || last_sent.elapsed() >= STATE_SEND_INTERVAL {
+                    last_state = Some(state.clone());
+                    last_sent = Instant::now();
+                    Some(WsMessage::State(state))
+                } else {
+                    None
+                }
+            }
+            event = events.recv() => match event {
+                Ok(event) => Some(WsMessage::Event(event)),
+                Err(RecvError::Lagged(_)) => None,
+                Err(RecvError::Closed) => break,
+            },
+            // クライアントからのメッセージは使わない（pingはaxumが応答する）
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => None,
+            },
+        };
+
+        let Some(message) = message else {
+            continue;
+        };
+        let Ok(text) = serde_json::to_string(&message) else {
+            continue;
+        };
+        if socket.send(Message::Text(text.into())).await.is_err() {
+            break;
+        }
+    }
+
+    api.shared.ws_clients.fetch_sub(1, Ordering::Relaxed);
+    debug!("[HTTP] WebSocket client disconnected");
+}
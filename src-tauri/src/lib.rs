@@ -1,14 +1,28 @@
 mod audio;
+#[cfg(feature = "gui")]
 mod commands;
+mod control;
+mod dmx;
 mod error;
+mod events;
+mod headless;
+mod http;
+mod local_config;
+mod midi;
+mod osc;
 mod output;
 mod pipeline;
+mod playback;
+mod project;
 mod state;
+mod sync;
+mod timecode;
 mod types;
 
+#[cfg(feature = "gui")]
 use state::AppState;
-use tauri::Manager;
-use tracing::info;
+#[cfg(feature = "gui")]
+use tauri::{Emitter, Manager};
 use tracing_subscriber::{fmt, EnvFilter};
 
 /// Initialize tracing/logging
@@ -38,7 +52,7 @@ fn setup_ndi_library_path() {
     // Set NDI_RUNTIME_DIR_V6 for GStreamer NDI plugin
     if env::var("NDI_RUNTIME_DIR_V6").is_err() {
         env::set_var("NDI_RUNTIME_DIR_V6", ndi_lib_path);
-        info!(path = %ndi_lib_path, "Set NDI_RUNTIME_DIR_V6 for GStreamer NDI plugin");
+        tracing::info!(path = %ndi_lib_path, "Set NDI_RUNTIME_DIR_V6 for GStreamer NDI plugin");
     }
 }
 
@@ -47,6 +61,33 @@ fn setup_ndi_library_path() {
     // On Windows/Linux, NDI SDK is typically in system path or needs different handling
 }
 
+/// Webviewなしで実行（`tauri-live-player-headless` バイナリ）
+pub fn run_headless() -> std::process::ExitCode {
+    let args = match headless::HeadlessArgs::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", headless::USAGE);
+            return std::process::ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, headless::USAGE);
+            return std::process::ExitCode::from(2);
+        }
+    };
+
+    init_logging();
+    setup_ndi_library_path();
+
+    match headless::run(args) {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(e) => {
+            tracing::error!("[Headless] {}", e);
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize logging first
@@ -63,13 +104,42 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .manage(app_state)
         .setup(|app| {
-            // GStreamer初期化
             let state = app.state::<AppState>();
+            // このPCの設定（メディアルート等）
+            *state.local_config.lock() = local_config::load_or_default();
+
+            // 前回の異常終了の確認と自動保存
+            project::recovery::begin_session(state.inner());
+            match project::recovery::AutoSave::start(state.inner().clone()) {
+                Ok(auto_save) => *state.auto_save.lock() = Some(auto_save),
+                Err(e) => tracing::error!("Failed to start auto-save: {}", e),
+            }
+
+            // GStreamer初期化
             if let Err(e) = state.init_player() {
                 tracing::error!("Failed to initialize GStreamer: {:?}", e);
             } else {
-                info!("GStreamer initialized successfully");
+                tracing::info!("GStreamer initialized successfully");
             }
+
+            // バックグラウンドサービスのイベントをフロントエンドへ転送
+            let mut receiver = state.events.subscribe();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => {
+                            if let Err(e) = app_handle.emit(events::APP_EVENT, &event) {
+                                tracing::warn!("Failed to emit app event: {:?}", e);
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            tracing::warn!("Dropped {} app events", n);
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -96,7 +166,77 @@ pub fn run() {
             commands::project::new_project,
             commands::project::get_project,
             commands::project::update_project,
+            commands::project::undo,
+            commands::project::redo,
+            commands::project::get_history,
+            commands::project::validate_project,
+            commands::project::collect_project,
+            commands::project::find_relinks,
+            commands::project::apply_relinks,
+            commands::project::get_project_path,
+            commands::project::list_backups,
+            commands::project::restore_backup,
+            commands::project::get_recovery,
+            commands::project::restore_recovery,
+            commands::project::discard_recovery,
+            // Project editing
+            commands::edit::add_cue,
+            commands::edit::update_cue,
+            commands::edit::remove_cue,
+            commands::edit::move_cue,
+            commands::edit::duplicate_cue,
+            commands::edit::add_media_item,
+            commands::edit::update_media_item,
+            commands::edit::remove_media_item,
+            commands::edit::add_output,
+            commands::edit::update_output,
+            commands::edit::remove_output,
+            // Local settings
+            commands::local::get_local_config,
+            commands::local::set_local_config,
+            commands::local::make_project_paths_relative,
+            commands::local::make_project_paths_absolute,
+            // Timecode
+            commands::timecode::start_ltc_chase,
+            commands::timecode::stop_ltc_chase,
+            commands::timecode::get_ltc_chase_status,
+            // Sync
+            commands::sync::set_sync_config,
+            commands::sync::stop_sync,
+            commands::sync::get_sync_status,
+            commands::sync::get_slaves,
+            commands::sync::send_slave_command,
+            // Control
+            commands::control::trigger_hotkey,
+            // OSC
+            commands::osc::start_osc_server,
+            commands::osc::stop_osc_server,
+            commands::osc::get_osc_status,
+            // HTTP
+            commands::http::start_http_server,
+            commands::http::stop_http_server,
+            commands::http::get_http_status,
+            // MIDI
+            commands::midi::list_midi_ports,
+            commands::midi::start_midi_input,
+            commands::midi::stop_midi_input,
+            commands::midi::get_midi_status,
+            // DMX
+            commands::dmx::start_art_net,
+            commands::dmx::stop_art_net,
+            commands::dmx::get_art_net_status,
+            commands::dmx::start_sacn,
+            commands::dmx::stop_sacn,
+            commands::dmx::get_sacn_status,
+            commands::dmx::start_art_timecode_output,
+            commands::dmx::stop_art_timecode_output,
+            commands::dmx::get_art_timecode_output_status,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                project::recovery::end_session(&app.state::<AppState>());
+            }
+        });
 }
@@ -0,0 +1,356 @@
+//! PC固有のローカル設定とメディアパスの解決
+//!
+//! プロジェクトのメディアパスは相対パスで保存し、PCごとの設定ファイルの
+//! `mediaRoot` を基準に解決する。絶対パスはそのまま使うため、変換前の
+//! プロジェクトも読み込める。
+//!
+//! オーディオデバイス・ディスプレイ番号・NDI名・同期モード・受信ポートは
+//! 実行時にプロジェクトの設定へ上書きし、同じプロジェクトファイルを
+//! メイン機とバックアップ機で編集せずに使えるようにする。
+//!
+//! 設定ファイルはOSのユーザー設定フォルダ（環境変数
+//! `TAURI_LIVE_PLAYER_LOCAL_CONFIG` で変更可）の `local.json`。
+
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::error::AppResult;
+use crate::pipeline::DEFAULT_NDI_NAME;
+#[cfg(feature = "gui")]
+use crate::types::Project;
+use crate::types::{Cue, LocalConfig, OutputTarget, OutputType, ProjectSettings, SyncConfig};
+
+/// 設定ファイルのパスを上書きする環境変数
+pub const PATH_ENV: &str = "TAURI_LIVE_PLAYER_LOCAL_CONFIG";
+
+/// 設定フォルダ名（tauri.conf.json の identifier）
+const APP_IDENTIFIER: &str = "com.tauriliveplayer.app";
+
+const FILE_NAME: &str = "local.json";
+
+/// 設定ファイルのパス（ユーザーフォルダが分からない場合はNone）
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(PATH_ENV) {
+        return Some(PathBuf::from(path));
+    }
+
+    let env_dir = |name: &str| std::env::var_os(name).map(PathBuf::from);
+    let base = if cfg!(windows) {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+    }?;
+
+    Some(base.join(APP_IDENTIFIER).join(FILE_NAME))
+}
+
+/// このPCの設定を置くフォルダ（リカバリーファイル等も置く）
+#[cfg(feature = "gui")]
+pub fn config_dir() -> Option<PathBuf> {
+    config_path()?.parent().map(Path::to_path_buf)
+}
+
+/// 設定ファイルを読み込み（ファイルがなければ既定値）
+pub fn load() -> AppResult<LocalConfig> {
+    let Some(path) = config_path() else {
+        return Ok(LocalConfig::default());
+    };
+    if !path.exists() {
+        return Ok(LocalConfig::default());
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    let config = serde_json::from_str(&content)?;
+    info!("[LocalConfig] Loaded {}", path.display());
+    Ok(config)
+}
+
+/// 設定ファイルを読み込み、失敗した場合は警告を出して既定値を使う
+pub fn load_or_default() -> LocalConfig {
+    load().unwrap_or_else(|e| {
+        warn!("[LocalConfig] Failed to load local settings: {}", e);
+        LocalConfig::default()
+    })
+}
+
+/// 設定ファイルに保存
+pub fn save(config: &LocalConfig) -> AppResult<()> {
+    let path = config_path().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Cannot determine the local settings folder",
+        )
+    })?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    crate::project::write_atomic(&path, serde_json::to_string_pretty(config)?.as_bytes())?;
+    info!("[LocalConfig] Saved {}", path.display());
+    Ok(())
+}
+
+/// 絶対パスか（別OSで作られたWindowsのドライブ・UNCパスも含む）
+pub fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let windows_drive = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    Path::new(path).is_absolute()
+        || windows_drive
+        || path.starts_with('/')
+        || path.starts_with("\\\\")
+}
+
+/// メディアのパスを解決
+///
+/// 絶対パスはそのまま、相対パスはメディアルートを基準にする。
+/// 区切り文字は `/` と `\` のどちらも受け付ける。
+pub fn resolve_media_path(path: &str, media_root: Option<&str>) -> PathBuf {
+    match media_root {
+        Some(root) if !is_absolute(path) => path
+            .split(['/', '\\'])
+            .filter(|part| !part.is_empty() && *part != ".")
+            .fold(PathBuf::from(root), |resolved, part| resolved.join(part)),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// アイテムのパスを解決したCue
+pub fn resolve_cue(cue: &Cue, media_root: Option<&str>) -> Cue {
+    let mut cue = cue.clone();
+    for item in &mut cue.items {
+        item.path = resolve_media_path(&item.path, media_root)
+            .to_string_lossy()
+            .into_owned();
+    }
+    cue
+}
+
+/// メディアルートからの相対パス（`/` 区切り）。ルートの外ならNone
+#[cfg(feature = "gui")]
+pub fn to_relative(path: &str, media_root: &str) -> Option<String> {
+    let relative = Path::new(path).strip_prefix(media_root).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// プロジェクト内の絶対パスをメディアルートからの相対パスに変換
+///
+/// 変換したアイテム数と、ルートの外にあり変換できなかったパスを返す。
+#[cfg(feature = "gui")]
+pub fn make_relative(project: &mut Project, media_root: &str) -> (usize, Vec<String>) {
+    let mut converted = 0;
+    let mut skipped = Vec::new();
+    for item in project.cues.iter_mut().flat_map(|cue| cue.items.iter_mut()) {
+        if !is_absolute(&item.path) {
+            continue;
+        }
+        match to_relative(&item.path, media_root) {
+            Some(relative) => {
+                item.path = relative;
+                converted += 1;
+            }
+            None if !skipped.contains(&item.path) => skipped.push(item.path.clone()),
+            None => {}
+        }
+    }
+    (converted, skipped)
+}
+
+/// プロジェクト内の相対パスをメディアルート基準の絶対パスに変換
+#[cfg(feature = "gui")]
+pub fn make_absolute(project: &mut Project, media_root: &str) -> usize {
+    let mut converted = 0;
+    for item in project.cues.iter_mut().flat_map(|cue| cue.items.iter_mut()) {
+        if is_absolute(&item.path) {
+            continue;
+        }
+        item.path = resolve_media_path(&item.path, Some(media_root))
+            .to_string_lossy()
+            .into_owned();
+        converted += 1;
+    }
+    converted
+}
+
+/// 出力先にこのPCの上書きを適用
+pub fn apply_output(output: &OutputTarget, config: &LocalConfig) -> OutputTarget {
+    let mut output = output.clone();
+    let overrides = config.outputs.iter().find(|o| o.output_id == output.id);
+
+    if let Some(index) = output.display_index {
+        output.display_index = Some(config.display_map.get(&index).copied().unwrap_or(index));
+    }
+    if output.output_type == OutputType::Audio {
+        if let Some(driver) = &config.audio_driver {
+            output.audio_driver = Some(driver.clone());
+        }
+    }
+    if let Some(prefix) = &config.ndi_name_prefix {
+        if output.output_type == OutputType::Ndi {
+            let name = output.ndi_name.as_deref().unwrap_or(DEFAULT_NDI_NAME);
+            output.ndi_name = Some(format!("{}{}", prefix, name));
+        }
+    }
+
+    if let Some(overrides) = overrides {
+        if overrides.display_index.is_some() {
+            output.display_index = overrides.display_index;
+        }
+        if overrides.audio_driver.is_some() {
+            output.audio_driver = overrides.audio_driver.clone();
+        }
+        if overrides.audio_device.is_some() {
+            output.audio_device = overrides.audio_device.clone();
+        }
+        if overrides.audio_channels.is_some() {
+            output.audio_channels = overrides.audio_channels.clone();
+        }
+    }
+
+    output
+}
+
+/// 全出力先にこのPCの上書きを適用
+pub fn apply_outputs(outputs: &[OutputTarget], config: &LocalConfig) -> Vec<OutputTarget> {
+    outputs.iter().map(|o| apply_output(o, config)).collect()
+}
+
+/// 外部制御の設定に受信ポートの上書きを適用
+pub fn apply_settings(settings: &ProjectSettings, config: &LocalConfig) -> ProjectSettings {
+    let mut settings = settings.clone();
+    let ports = &config.ports;
+    if let Some(port) = ports.osc {
+        settings.osc.port = port;
+    }
+    if let Some(port) = ports.http {
+        settings.http.port = port;
+    }
+    if let Some(port) = ports.art_net {
+        settings.art_net.port = port;
+    }
+    if let Some(name) = &ports.midi {
+        settings.midi.port_name = Some(name.clone());
+    }
+    settings
+}
+
+/// 同期設定に同期モードの上書きを適用
+pub fn apply_sync(sync: &SyncConfig, config: &LocalConfig) -> SyncConfig {
+    let mut sync = sync.clone();
+    if let Some(role) = config.sync_role {
+        sync.mode = role;
+    }
+    sync
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AudioDriver, OutputOverride};
+
+    #[test]
+    fn test_resolve_media_path() {
+        let root = if cfg!(windows) {
+            "D:\\Videos"
+        } else {
+            "/videos"
+        };
+        assert_eq!(
+            resolve_media_path("M01/intro.mp4", Some(root)),
+            Path::new(root).join("M01").join("intro.mp4")
+        );
+        assert_eq!(
+            resolve_media_path("M01\\intro.mp4", Some(root)),
+            Path::new(root).join("M01").join("intro.mp4")
+        );
+        // 絶対パスはそのまま
+        assert_eq!(
+            resolve_media_path("/other/intro.mp4", Some(root)),
+            PathBuf::from("/other/intro.mp4")
+        );
+        assert_eq!(
+            resolve_media_path("C:\\show\\intro.mp4", Some(root)),
+            PathBuf::from("C:\\show\\intro.mp4")
+        );
+        // ルート未設定なら相対パスのまま
+        assert_eq!(
+            resolve_media_path("M01/intro.mp4", None),
+            PathBuf::from("M01/intro.mp4")
+        );
+    }
+
+    #[test]
+    fn test_relative_round_trip() {
+        let root = if cfg!(windows) {
+            "D:\\Videos"
+        } else {
+            "/videos"
+        };
+        let inside = Path::new(root).join("M01").join("intro.mp4");
+        let inside = inside.to_str().unwrap();
+
+        assert_eq!(to_relative(inside, root).as_deref(), Some("M01/intro.mp4"));
+        assert_eq!(to_relative(root, root), None);
+        assert_eq!(to_relative("/elsewhere/intro.mp4", root), None);
+        assert_eq!(
+            resolve_media_path(&to_relative(inside, root).unwrap(), Some(root)),
+            PathBuf::from(inside)
+        );
+    }
+
+    fn output(id: &str, output_type: OutputType) -> OutputTarget {
+        OutputTarget {
+            id: id.to_string(),
+            name: id.to_string(),
+            output_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_output_overrides() {
+        let config = LocalConfig {
+            audio_driver: Some(AudioDriver::Wasapi),
+            outputs: vec![OutputOverride {
+                output_id: "audio".to_string(),
+                audio_driver: Some(AudioDriver::Asio),
+                audio_device: Some("RME".to_string()),
+                ..Default::default()
+            }],
+            display_map: [(1, 2)].into_iter().collect(),
+            ndi_name_prefix: Some("BACKUP ".to_string()),
+            ..Default::default()
+        };
+
+        let mut display = output("display", OutputType::Display);
+        display.display_index = Some(1);
+        assert_eq!(apply_output(&display, &config).display_index, Some(2));
+        display.display_index = Some(0);
+        assert_eq!(apply_output(&display, &config).display_index, Some(0));
+
+        let audio = apply_output(&output("audio", OutputType::Audio), &config);
+        assert_eq!(audio.audio_driver, Some(AudioDriver::Asio));
+        assert_eq!(audio.audio_device.as_deref(), Some("RME"));
+        let other = apply_output(&output("other", OutputType::Audio), &config);
+        assert_eq!(other.audio_driver, Some(AudioDriver::Wasapi));
+        assert_eq!(other.audio_device, None);
+
+        let mut ndi = output("ndi", OutputType::Ndi);
+        assert_eq!(
+            apply_output(&ndi, &config).ndi_name.as_deref(),
+            Some("BACKUP TauriLivePlayer")
+        );
+        ndi.ndi_name = Some("Stage".to_string());
+        assert_eq!(
+            apply_output(&ndi, &config).ndi_name.as_deref(),
+            Some("BACKUP Stage")
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! MIDI入力サービス
+//!
+//! midirのコールバックでは解釈だけ行い、Cueの読み込み等の重い処理は
+//! 専用スレッドで実行する。MTCのタイムコードはChaseRunnerと
+//! タイムコードトリガーへ渡す。
+
+use midir::{Ignore, MidiInput, MidiInputConnection};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::control::{self, ControlInput, TimecodeTriggers};
+use crate::error::{AppError, AppResult};
+use crate::midi::mapping::{self, MidiMessage};
+use crate::midi::msc::{self, MscMessage};
+use crate::state::AppState;
+use crate::timecode::chase_runner::{ChaseFeed, ChaseRunner};
+use crate::timecode::mtc::{self, MtcDecoder};
+#[cfg(feature = "gui")]
+use crate::types::MidiStatus;
+use crate::types::{FrameRate, MidiConfig, Timecode};
+
+/// midirのクライアント名
+const CLIENT_NAME: &str = "tauri-live-player";
+
+/// 停止確認のためのタイムアウト
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 入力ポート名の一覧
+#[cfg(feature = "gui")]
+pub fn list_ports() -> AppResult<Vec<String>> {
+    let input = MidiInput::new(CLIENT_NAME).map_err(|e| AppError::Midi(e.to_string()))?;
+    Ok(input
+        .ports()
+        .iter()
+        .filter_map(|port| input.port_name(port).ok())
+        .collect())
+}
+
+/// 受信したメッセージの分類
+#[derive(Debug)]
+enum Incoming {
+    Channel(MidiMessage),
+    ShowControl(MscMessage),
+    QuarterFrame(u8),
+    FullFrame(Timecode, FrameRate),
+}
+
+impl Incoming {
+    fn parse(data: &[u8]) -> Option<Self> {
+        match *data.first()? {
+            0xF1 => Some(Incoming::QuarterFrame(*data.get(1)?)),
+            0xF0 => MscMessage::parse(data)
+                .map(Incoming::ShowControl)
+                .or_else(|| {
+                    mtc::parse_full_frame(data).map(|(tc, rate)| Incoming::FullFrame(tc, rate))
+                }),
+            _ => MidiMessage::parse(data).map(Incoming::Channel),
+        }
+    }
+}
+
+/// 受信スレッドと共有する状態
+#[derive(Default)]
+struct MidiShared {
+    messages_received: u64,
+    errors: u64,
+    last_message: Option<String>,
+    last_error: Option<String>,
+}
+
+/// MIDI入力サービス
+pub struct MidiInputService {
+    connection: Option<MidiInputConnection<()>>,
+    #[cfg(feature = "gui")]
+    port: String,
+    mtc_chase: Option<ChaseRunner>,
+    #[cfg(feature = "gui")]
+    shared: Arc<Mutex<MidiShared>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MidiInputService {
+    pub fn start(config: &MidiConfig, state: AppState) -> AppResult<Self> {
+        let mut input = MidiInput::new(CLIENT_NAME).map_err(|e| AppError::Midi(e.to_string()))?;
+        // SysEx (MSC・MTCフルフレーム) とクォーターフレームを受信する
+        input.ignore(Ignore::ActiveSense);
+
+        let (sender, receiver) = mpsc::channel::<Incoming>();
+        let callback = move |_timestamp: u64, data: &[u8], _: &mut ()| {
+            if let Some(message) = Incoming::parse(data) {
+                let _ = sender.send(message);
+            }
+        };
+
+        let (connection, port) = if config.virtual_port {
+            Self::connect_virtual(input, &config.virtual_port_name, callback)?
+        } else {
+            let ports = input.ports();
+            let port = ports
+                .iter()
+                .find(|port| match &config.port_name {
+                    Some(name) => input
+                        .port_name(port)
+                        .map(|n| n.contains(name.as_str()))
+                        .unwrap_or(false),
+                    None => true,
+                })
+                .ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "MIDI input port not found: {}",
+                        config.port_name.as_deref().unwrap_or("(any)")
+                    ))
+                })?
+                .clone();
+            let port_name = input
+                .port_name(&port)
+                .map_err(|e| AppError::Midi(e.to_string()))?;
+            let connection = input
+                .connect(&port, CLIENT_NAME, callback, ())
+                .map_err(|e| AppError::Midi(e.to_string()))?;
+            (connection, port_name)
+        };
+
+        // チェイスの開始位置・しきい値はLTCチェイスの設定を共用
+        let (mtc_feed, mtc_chase) = if config.mtc_chase {
+            let chase_config = state
+                .project
+                .lock()
+                .as_ref()
+                .map(|p| p.settings.ltc_chase.clone())
+                .unwrap_or_default();
+            let feed = ChaseFeed::new("MtcChase", &chase_config);
+            let runner = ChaseRunner::start("mtc-chase", feed.clone(), state.player.clone(), None)?;
+            (Some(feed), Some(runner))
+        } else {
+            (None, None)
+        };
+
+        let shared = Arc::new(Mutex::new(MidiShared::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let config = config.clone();
+            let shared = shared.clone();
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name("midi-input".to_string())
+                .spawn(move || Self::run(state, config, mtc_feed, receiver, shared, running))
+                .map_err(AppError::Io)?
+        };
+
+        info!("[MIDI] Listening on {}", port);
+
+        Ok(Self {
+            connection: Some(connection),
+            #[cfg(feature = "gui")]
+            port,
+            mtc_chase,
+            #[cfg(feature = "gui")]
+            shared,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    #[cfg(unix)]
+    fn connect_virtual(
+        input: MidiInput,
+        name: &str,
+        callback: impl FnMut(u64, &[u8], &mut ()) + Send + 'static,
+    ) -> AppResult<(MidiInputConnection<()>, String)> {
+        use midir::os::unix::VirtualInput;
+
+        let connection = input
+            .create_virtual(name, callback, ())
+            .map_err(|e| AppError::Midi(e.to_string()))?;
+        Ok((connection, format!("{} (virtual)", name)))
+    }
+
+    #[cfg(not(unix))]
+    fn connect_virtual(
+        _input: MidiInput,
+        _name: &str,
+        _callback: impl FnMut(u64, &[u8], &mut ()) + Send + 'static,
+    ) -> AppResult<(MidiInputConnection<()>, String)> {
+        Err(AppError::Midi(
+            "Virtual MIDI ports are not supported on this platform".to_string(),
+        ))
+    }
+
+    fn run(
+        state: AppState,
+        config: MidiConfig,
+        mtc_feed: Option<ChaseFeed>,
+        receiver: mpsc::Receiver<Incoming>,
+        shared: Arc<Mutex<MidiShared>>,
+        running: Arc<AtomicBool>,
+    ) {
+        let mut mtc = MtcDecoder::new();
+        let timecode_triggers = TimecodeTriggers::new();
+
+        let record_error = |error: String| {
+            warn!("[MIDI] {}", error);
+            let mut shared = shared.lock();
+            shared.errors += 1;
+            shared.last_error = Some(error);
+        };
+
+        while running.load(Ordering::Relaxed) {
+            let message = match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(message) => message,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+
+            // クォーターフレームは1秒に100個以上届くため記録しない
+            if let Incoming::QuarterFrame(data) = message {
+                if let Some((timecode, rate)) = mtc.on_quarter_frame(data) {
+                    if let Some(feed) = &mtc_feed {
+                        feed.on_timecode(timecode, Some(rate));
+                    }
+                    if let Err(e) = timecode_triggers.on_timecode(&state, timecode, rate) {
+                        record_error(format!("Timecode trigger at {} failed: {}", timecode, e));
+                    }
+                }
+                continue;
+            }
+
+            debug!("[MIDI] {:?}", message);
+            {
+                let mut shared = shared.lock();
+                shared.messages_received += 1;
+                shared.last_message = Some(format!("{:?}", message));
+            }
+
+            match message {
+                Incoming::Channel(message) => {
+                    // プロジェクトのバインディングが一致すればMIDIバインディングは使わない
+                    let result =
+                        control::dispatch_or(&state, &ControlInput::Midi(&message), || {
+                            Ok(mapping::resolve(&config.bindings, &message))
+                        });
+                    if let Err(e) = result {
+                        record_error(format!("{:?} failed: {}", message, e));
+                    }
+                }
+                Incoming::ShowControl(message) => {
+                    if !config.msc || !message.is_for(config.msc_device_id) {
+                        continue;
+                    }
+                    if let Err(e) = msc::command(&message.command)
+                        .and_then(|command| control::apply(&state, command))
+                    {
+                        record_error(format!("MSC {:?} failed: {}", message.command, e));
+                    }
+                }
+                Incoming::FullFrame(timecode, rate) => {
+                    // ロケート: クォーターフレームとタイムコードトリガーは揃え直す
+                    mtc.reset();
+                    timecode_triggers.reset();
+                    if let Some(feed) = &mtc_feed {
+                        feed.on_timecode(timecode, Some(rate));
+                    }
+                }
+                Incoming::QuarterFrame(_) => {}
+            }
+        }
+        debug!("[MIDI] Input thread finished");
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> MidiStatus {
+        let shared = self.shared.lock();
+        MidiStatus {
+            running: self.running.load(Ordering::Relaxed),
+            port: Some(self.port.clone()),
+            messages_received: shared.messages_received,
+            errors: shared.errors,
+            last_message: shared.last_message.clone(),
+            last_error: shared.last_error.clone(),
+            mtc_chase: self.mtc_chase.as_ref().map(|chase| chase.status()),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(connection) = self.connection.take() {
+            connection.close();
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[MIDI] Stopped");
+        }
+        if let Some(mut chase) = self.mtc_chase.take() {
+            chase.stop();
+        }
+    }
+}
+
+impl Drop for MidiInputService {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,184 @@
+//! MIDIメッセージの解釈とバインディングの照合
+
+use crate::control::trigger::{self, InputValue};
+use crate::control::ControlCommand;
+use crate::types::{MidiBinding, MidiTrigger};
+
+/// 受信したMIDIメッセージ（チャンネルは1〜16）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MidiMessage {
+    NoteOn {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    ProgramChange {
+        channel: u8,
+        program: u8,
+    },
+}
+
+impl MidiMessage {
+    /// 生のバイト列を解釈（対象外のメッセージはNone）
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let status = *data.first()?;
+        let channel = (status & 0x0F) + 1;
+        match status & 0xF0 {
+            // ベロシティ0のノートオンはノートオフ扱い
+            0x90 if *data.get(2)? > 0 => Some(MidiMessage::NoteOn {
+                channel,
+                note: *data.get(1)? & 0x7F,
+                velocity: data[2] & 0x7F,
+            }),
+            0xB0 => Some(MidiMessage::ControlChange {
+                channel,
+                controller: *data.get(1)? & 0x7F,
+                value: *data.get(2)? & 0x7F,
+            }),
+            0xC0 => Some(MidiMessage::ProgramChange {
+                channel,
+                program: *data.get(1)? & 0x7F,
+            }),
+            _ => None,
+        }
+    }
+
+    fn channel(&self) -> u8 {
+        match self {
+            MidiMessage::NoteOn { channel, .. }
+            | MidiMessage::ControlChange { channel, .. }
+            | MidiMessage::ProgramChange { channel, .. } => *channel,
+        }
+    }
+
+    /// 値（ベロシティ・CC値・プログラム番号）
+    fn value(&self) -> u8 {
+        match self {
+            MidiMessage::NoteOn { velocity, .. } => *velocity,
+            MidiMessage::ControlChange { value, .. } => *value,
+            MidiMessage::ProgramChange { program, .. } => *program,
+        }
+    }
+
+    /// 値を0〜100にスケール
+    pub fn level(&self) -> f64 {
+        self.value() as f64 * 100.0 / 127.0
+    }
+
+    /// Cueインデックスとして使う値（ノート番号・CC値・プログラム番号）
+    pub fn index(&self) -> usize {
+        match self {
+            MidiMessage::NoteOn { note, .. } => *note as usize,
+            _ => self.value() as usize,
+        }
+    }
+}
+
+/// トリガーがメッセージに一致するか
+pub fn matches(trigger: &MidiTrigger, message: &MidiMessage) -> bool {
+    let channel_matches = |channel: &Option<u8>| channel.is_none_or(|c| c == message.channel());
+
+    match (trigger, message) {
+        (MidiTrigger::Note { channel, note }, MidiMessage::NoteOn { note: n, .. }) => {
+            channel_matches(channel) && note.is_none_or(|note| note == *n)
+        }
+        (
+            MidiTrigger::ControlChange {
+                channel,
+                controller,
+            },
+            MidiMessage::ControlChange { controller: c, .. },
+        ) => channel_matches(channel) && controller == c,
+        (
+            MidiTrigger::ProgramChange { channel, program },
+            MidiMessage::ProgramChange { program: p, .. },
+        ) => channel_matches(channel) && program.is_none_or(|program| program == *p),
+        _ => false,
+    }
+}
+
+/// メッセージに一致するバインディングの操作を列挙
+pub fn resolve(bindings: &[MidiBinding], message: &MidiMessage) -> Vec<ControlCommand> {
+    let value = InputValue {
+        level: Some(message.level()),
+        cue: Some(message.index()),
+        release: false,
+    };
+
+    bindings
+        .iter()
+        .filter(|binding| matches(&binding.trigger, message))
+        .filter_map(|binding| trigger::resolve_action(&binding.action, value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ControlAction, MidiConfig};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            MidiMessage::parse(&[0x91, 60, 100]),
+            Some(MidiMessage::NoteOn {
+                channel: 2,
+                note: 60,
+                velocity: 100
+            })
+        );
+        assert_eq!(MidiMessage::parse(&[0x90, 60, 0]), None);
+        assert_eq!(MidiMessage::parse(&[0x80, 60, 64]), None);
+        assert_eq!(
+            MidiMessage::parse(&[0xC0, 4]),
+            Some(MidiMessage::ProgramChange {
+                channel: 1,
+                program: 4
+            })
+        );
+        assert_eq!(MidiMessage::parse(&[0xB0, 7]), None);
+    }
+
+    #[test]
+    fn test_default_bindings() {
+        let bindings = MidiConfig::default().bindings;
+
+        let program = MidiMessage::parse(&[0xC0, 4]).unwrap();
+        assert_eq!(
+            resolve(&bindings, &program),
+            vec![ControlCommand::LoadCue(4)]
+        );
+
+        let note = MidiMessage::parse(&[0x90, 36, 127]).unwrap();
+        assert_eq!(resolve(&bindings, &note), vec![ControlCommand::Go]);
+
+        let volume = MidiMessage::parse(&[0xB3, 7, 127]).unwrap();
+        assert_eq!(
+            resolve(&bindings, &volume),
+            vec![ControlCommand::MasterVolume(100.0)]
+        );
+
+        let other_cc = MidiMessage::parse(&[0xB0, 1, 64]).unwrap();
+        assert!(resolve(&bindings, &other_cc).is_empty());
+    }
+
+    #[test]
+    fn test_channel_filter() {
+        let bindings = vec![MidiBinding {
+            trigger: MidiTrigger::Note {
+                channel: Some(10),
+                note: Some(36),
+            },
+            action: ControlAction::Stop,
+        }];
+        let on_channel = MidiMessage::parse(&[0x99, 36, 100]).unwrap();
+        let off_channel = MidiMessage::parse(&[0x90, 36, 100]).unwrap();
+        assert_eq!(resolve(&bindings, &on_channel), vec![ControlCommand::Stop]);
+        assert!(resolve(&bindings, &off_channel).is_empty());
+    }
+}
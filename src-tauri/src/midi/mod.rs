@@ -0,0 +1,26 @@
+//! MIDI入力
+//!
+//! MIDI入力ポート（Linuxでは ALSA の仮想ポートも可）からノート・CC・
+//! プログラムチェンジを受信し、プロジェクトのバインディング表でプレイヤー操作に
+//! 割り当てる。MIDIショーコントロールでCueを操作し、MIDIタイムコードで
+//! 再生位置をチェイスすることもできる。
+
+pub mod input;
+pub mod mapping;
+pub mod msc;
+
+pub use input::MidiInputService;
+
+use crate::error::AppResult;
+use crate::state::AppState;
+use crate::types::MidiConfig;
+
+/// 実行中の入力を停止し、指定の設定で起動し直す
+pub fn restart(state: &AppState, config: &MidiConfig) -> AppResult<()> {
+    let mut midi_guard = state.midi.lock();
+    if let Some(mut existing) = midi_guard.take() {
+        existing.stop();
+    }
+    *midi_guard = Some(MidiInputService::start(config, state.clone())?);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! MIDIショーコントロール (MSC)
+//!
+//! `F0 7F <device_id> 02 <command_format> <command> <data> F7` のうち
+//! GO / STOP / RESUME / LOAD を扱う。Cue番号は1始まりの整数として
+//! プロジェクトのCue順に対応させる（"5" と "5.0" は5番目のCue）。
+
+use crate::control::ControlCommand;
+use crate::error::{AppError, AppResult};
+
+/// 全デバイス宛てのデバイスID
+const ALL_CALL: u8 = 0x7F;
+
+/// MSCコマンド
+#[derive(Debug, Clone, PartialEq)]
+pub enum MscCommand {
+    Go { cue: Option<String> },
+    Stop { cue: Option<String> },
+    Resume { cue: Option<String> },
+    Load { cue: String },
+}
+
+/// MSCメッセージ
+#[derive(Debug, Clone, PartialEq)]
+pub struct MscMessage {
+    pub device_id: u8,
+    pub command_format: u8,
+    pub command: MscCommand,
+}
+
+impl MscMessage {
+    /// SysExを解釈（MSC以外・未対応コマンドはNone）
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let [0xF0, 0x7F, device_id, 0x02, command_format, command, rest @ ..] = data else {
+            return None;
+        };
+        let body = rest.strip_suffix(&[0xF7]).unwrap_or(rest);
+
+        // Q_number 00 Q_list 00 Q_path のうちCue番号のみ使う
+        let cue = body
+            .split(|b| *b == 0x00)
+            .next()
+            .filter(|cue| !cue.is_empty())
+            .map(|cue| String::from_utf8_lossy(cue).into_owned());
+
+        let command = match command {
+            0x01 => MscCommand::Go { cue },
+            0x02 => MscCommand::Stop { cue },
+            0x03 => MscCommand::Resume { cue },
+            0x05 => MscCommand::Load { cue: cue? },
+            _ => return None,
+        };
+
+        Some(Self {
+            device_id: *device_id,
+            command_format: *command_format,
+            command,
+        })
+    }
+
+    /// 自分宛てのメッセージか（Noneなら全て受け付ける）
+    pub fn is_for(&self, device_id: Option<u8>) -> bool {
+        device_id.is_none_or(|id| self.device_id == id || self.device_id == ALL_CALL)
+    }
+}
+
+/// Cue番号をインデックスに変換
+fn cue_index(cue: &str) -> AppResult<usize> {
+    match cue.trim().parse::<f64>() {
+        Ok(n) if n >= 1.0 && n.fract() == 0.0 => Ok(n as usize - 1),
+        _ => Err(AppError::NotFound(format!(
+            "Unsupported MSC cue number: {}",
+            cue
+        ))),
+    }
+}
+
+/// プレイヤー操作に変換
+pub fn command(command: &MscCommand) -> AppResult<ControlCommand> {
+    Ok(match command {
+        MscCommand::Go { cue: None } => ControlCommand::Go,
+        MscCommand::Go { cue: Some(cue) } => ControlCommand::GoCue(cue_index(cue)?),
+        MscCommand::Stop { .. } => ControlCommand::Pause,
+        MscCommand::Resume { .. } => ControlCommand::Play,
+        MscCommand::Load { cue } => ControlCommand::LoadCue(cue_index(cue)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_go_with_cue() {
+        let data = [
+            0xF0, 0x7F, 0x01, 0x02, 0x30, 0x01, b'1', b'2', b'.', b'0', 0x00, b'1', 0xF7,
+        ];
+        let message = MscMessage::parse(&data).unwrap();
+        assert_eq!(
+            message.command,
+            MscCommand::Go {
+                cue: Some("12.0".to_string())
+            }
+        );
+        assert!(message.is_for(Some(1)));
+        assert!(!message.is_for(Some(2)));
+        assert_eq!(cue_index("12.0").unwrap(), 11);
+        assert!(cue_index("12.5").is_err());
+    }
+
+    #[test]
+    fn test_parse_without_cue() {
+        let stop = [0xF0, 0x7F, 0x7F, 0x02, 0x7F, 0x02, 0xF7];
+        let message = MscMessage::parse(&stop).unwrap();
+        assert_eq!(message.command, MscCommand::Stop { cue: None });
+        assert!(message.is_for(Some(5)));
+
+        // LOADはCue番号が必須
+        let load = [0xF0, 0x7F, 0x7F, 0x02, 0x7F, 0x05, 0xF7];
+        assert_eq!(MscMessage::parse(&load), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! OSCアドレスとプレイヤー操作の対応
+//!
+//! | アドレス                    | 引数        | 操作                         |
+//! |-----------------------------|-------------|------------------------------|
+//! | /go                         |             | GO（読み込み済みを再生 / 次のCue） |
+//! | /play, /pause, /stop        |             | 再生制御                      |
+//! | /seek                       | 秒          | seek                         |
+//! | /cue/{n}/load               |             | Cue番号 n (1始まり) を読み込み |
+//! | /cue/{n}/go                 |             | Cue番号 n を読み込んで再生     |
+//! | /master/brightness          | 0-100       | マスター明るさ                 |
+//! | /master/volume              | 0-100       | マスター音量                   |
+//! | /output/{id}/brightness     | 0-100       | 出力ごとの明るさ（負の値で解除） |
+//!
+//! ボタン型のコントローラーは押下で1、離すと0を送るため、トリガー系の
+//! アドレスは最初の引数が0のメッセージを無視する。
+//!
+//! プロジェクトのバインディングに一致したアドレスは、この表より優先される。
+
+use rosc::OscType;
+
+use crate::control::ControlCommand;
+use crate::error::{AppError, AppResult};
+
+/// 数値引数を取り出す
+pub(crate) fn number(arg: &OscType) -> Option<f64> {
+    match arg {
+        OscType::Float(v) => Some(*v as f64),
+        OscType::Double(v) => Some(*v),
+        OscType::Int(v) => Some(*v as f64),
+        OscType::Long(v) => Some(*v as f64),
+        OscType::Bool(v) => Some(if *v { 1.0 } else { 0.0 }),
+        OscType::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn required_number(addr: &str, args: &[OscType]) -> AppResult<f64> {
+    args.first()
+        .and_then(number)
+        .ok_or_else(|| AppError::Osc(format!("{} requires a numeric argument", addr)))
+}
+
+/// ボタンを離した時のメッセージか
+fn is_release(args: &[OscType]) -> bool {
+    args.first().and_then(number) == Some(0.0)
+}
+
+/// Cue番号 (1始まり) をインデックスに変換
+fn cue_index(addr: &str, number: &str) -> AppResult<usize> {
+    match number.parse::<usize>() {
+        Ok(n) if n >= 1 => Ok(n - 1),
+        _ => Err(AppError::Osc(format!("Invalid cue number in {}", addr))),
+    }
+}
+
+/// アドレスと引数を操作に変換（無視するメッセージはNone）
+pub fn parse(addr: &str, args: &[OscType]) -> AppResult<Option<ControlCommand>> {
+    let parts: Vec<&str> = addr.trim_matches('/').split('/').collect();
+
+    let trigger = |command: ControlCommand| Ok((!is_release(args)).then_some(command));
+
+    match parts.as_slice() {
+        ["go"] => trigger(ControlCommand::Go),
+        ["play"] => trigger(ControlCommand::Play),
+        ["pause"] => trigger(ControlCommand::Pause),
+        ["stop"] => trigger(ControlCommand::Stop),
+        ["seek"] => Ok(Some(ControlCommand::Seek(
+            required_number(addr, args)?.max(0.0),
+        ))),
+        ["cue", n, "load"] => trigger(ControlCommand::LoadCue(cue_index(addr, n)?)),
+        ["cue", n, "go"] => trigger(ControlCommand::GoCue(cue_index(addr, n)?)),
+        ["master", "brightness"] => Ok(Some(ControlCommand::MasterBrightness(
+            required_number(addr, args)?.clamp(0.0, 100.0),
+        ))),
+        ["master", "volume"] => Ok(Some(ControlCommand::MasterVolume(
+            required_number(addr, args)?.clamp(0.0, 100.0),
+        ))),
+        ["output", id, "brightness"] => {
+            let value = required_number(addr, args)?;
+            Ok(Some(ControlCommand::OutputBrightness {
+                output_id: id.to_string(),
+                value: (value >= 0.0).then(|| value.min(100.0)),
+            }))
+        }
+        _ => Err(AppError::Osc(format!("Unknown address: {}", addr))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_addresses() {
+        assert_eq!(parse("/go", &[]).unwrap(), Some(ControlCommand::Go));
+        assert_eq!(
+            parse("/cue/3/load", &[]).unwrap(),
+            Some(ControlCommand::LoadCue(2))
+        );
+        assert_eq!(
+            parse("/master/brightness", &[OscType::Float(50.0)]).unwrap(),
+            Some(ControlCommand::MasterBrightness(50.0))
+        );
+        assert_eq!(
+            parse("/output/main/brightness", &[OscType::Int(-1)]).unwrap(),
+            Some(ControlCommand::OutputBrightness {
+                output_id: "main".to_string(),
+                value: None
+            })
+        );
+        assert!(parse("/cue/0/load", &[]).is_err());
+        assert!(parse("/master/brightness", &[]).is_err());
+        assert!(parse("/unknown", &[]).is_err());
+    }
+
+    #[test]
+    fn test_ignore_button_release() {
+        assert_eq!(
+            parse("/go", &[OscType::Float(1.0)]).unwrap(),
+            Some(ControlCommand::Go)
+        );
+        assert_eq!(parse("/go", &[OscType::Float(0.0)]).unwrap(), None);
+        assert_eq!(parse("/stop", &[OscType::Int(0)]).unwrap(), None);
+    }
+}
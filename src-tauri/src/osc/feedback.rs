@@ -0,0 +1,183 @@
+//! OSCフィードバック
+//!
+//! 登録されたクライアントへ現在の状態を送信する。Cue・再生状態・レベル・
+//! エラーが変わった時は即座に、経過時間を含む全体は設定した周期で送る。
+//!
+//! | アドレス                  | 引数 | 内容                      |
+//! |---------------------------|------|---------------------------|
+//! | /status/cue/index         | i    | Cue番号（1始まり、0=なし） |
+//! | /status/cue/name          | s    | Cue名                     |
+//! | /status/state             | s    | idle / ready / playing 等 |
+//! | /status/time/elapsed      | f    | 経過時間（秒）             |
+//! | /status/time/remaining    | f    | 残り時間（秒）             |
+//! | /status/master/brightness | f    | マスター明るさ             |
+//! | /status/master/volume     | f    | マスター音量               |
+//! | /status/error             | s    | 最後のエラー（なければ空）  |
+
+use rosc::{OscBundle, OscMessage, OscPacket, OscTime, OscType};
+
+use crate::error::{AppError, AppResult};
+use crate::playback;
+use crate::state::AppState;
+use crate::types::PlayerStatus;
+
+/// フィードバックで送る状態
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackSnapshot {
+    pub cue_index: i32,
+    pub cue_name: String,
+    pub status: PlayerStatus,
+    pub elapsed: f64,
+    pub remaining: f64,
+    pub master_brightness: f64,
+    pub master_volume: f64,
+    pub last_error: Option<String>,
+}
+
+impl FeedbackSnapshot {
+    /// 現在のプレイヤー・プロジェクトの状態から作成
+    pub fn capture(state: &AppState, last_error: Option<String>) -> Self {
+        let player_state = playback::player_state(state);
+        let project_guard = state.project.lock();
+        let project = project_guard.as_ref();
+
+        let cue_name = project
+            .and_then(|p| {
+                usize::try_from(player_state.current_cue_index)
+                    .ok()
+                    .and_then(|i| p.cues.get(i))
+            })
+            .map(|cue| cue.name.clone())
+            .unwrap_or_default();
+
+        Self {
+            cue_index: player_state.current_cue_index,
+            cue_name,
+            status: player_state.status,
+            elapsed: player_state.current_time,
+            remaining: (player_state.duration - player_state.current_time).max(0.0),
+            master_brightness: project.map(|p| p.master_brightness).unwrap_or(100.0),
+            master_volume: project.map(|p| p.master_volume).unwrap_or(100.0),
+            last_error: last_error.or(player_state.error),
+        }
+    }
+
+    /// 時間以外の項目が変わったか
+    pub fn differs_from(&self, other: &FeedbackSnapshot) -> bool {
+        self.cue_index != other.cue_index
+            || self.cue_name != other.cue_name
+            || self.status != other.status
+            || self.master_brightness != other.master_brightness
+            || self.master_volume != other.master_volume
+            || self.last_error != other.last_error
+    }
+
+    pub fn messages(&self) -> Vec<OscMessage> {
+        let status = match self.status {
+            PlayerStatus::Idle => "idle",
+            PlayerStatus::Loading => "loading",
+            PlayerStatus::Ready => "ready",
+            PlayerStatus::Playing => "playing",
+            PlayerStatus::Paused => "paused",
+            PlayerStatus::Error => "error",
+        };
+
+        let message = |addr: &str, arg: OscType| OscMessage {
+            addr: addr.to_string(),
+            args: vec![arg],
+        };
+
+        vec![
+            message(
+                "/status/cue/index",
+                OscType::Int(self.cue_index.max(-1) + 1),
+            ),
+            message("/status/cue/name", OscType::String(self.cue_name.clone())),
+            message("/status/state", OscType::String(status.to_string())),
+            message("/status/time/elapsed", OscType::Float(self.elapsed as f32)),
+            message(
+                "/status/time/remaining",
+                OscType::Float(self.remaining as f32),
+            ),
+            message(
+                "/status/master/brightness",
+                OscType::Float(self.master_brightness as f32),
+            ),
+            message(
+                "/status/master/volume",
+                OscType::Float(self.master_volume as f32),
+            ),
+            message(
+                "/status/error",
+                OscType::String(self.last_error.clone().unwrap_or_default()),
+            ),
+        ]
+    }
+
+    /// 即時実行のバンドルとしてエンコード
+    pub fn encode(&self) -> AppResult<Vec<u8>> {
+        let bundle = OscPacket::Bundle(OscBundle {
+            timetag: OscTime::from((0, 1)),
+            content: self
+                .messages()
+                .into_iter()
+                .map(OscPacket::Message)
+                .collect(),
+        });
+        rosc::encoder::encode(&bundle).map_err(|e| AppError::Osc(format!("{:?}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> FeedbackSnapshot {
+        FeedbackSnapshot {
+            cue_index: 1,
+            cue_name: "Opening".to_string(),
+            status: PlayerStatus::Playing,
+            elapsed: 10.0,
+            remaining: 50.0,
+            master_brightness: 100.0,
+            master_volume: 80.0,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_time_only_change_is_not_a_change() {
+        let a = snapshot();
+        let mut b = a.clone();
+        b.elapsed = 10.5;
+        b.remaining = 49.5;
+        assert!(!b.differs_from(&a));
+
+        b.status = PlayerStatus::Paused;
+        assert!(b.differs_from(&a));
+    }
+
+    #[test]
+    fn test_cleared_error() {
+        let mut failed = snapshot();
+        failed.last_error = Some("Cue not found".to_string());
+        let cleared = snapshot();
+        assert!(cleared.differs_from(&failed));
+
+        let messages = cleared.messages();
+        let error = messages.iter().find(|m| m.addr == "/status/error").unwrap();
+        assert_eq!(error.args, vec![OscType::String(String::new())]);
+    }
+
+    #[test]
+    fn test_messages() {
+        let messages = snapshot().messages();
+        assert_eq!(messages[0].addr, "/status/cue/index");
+        assert_eq!(messages[0].args, vec![OscType::Int(2)]);
+        assert_eq!(
+            messages[2].args,
+            vec![OscType::String("playing".to_string())]
+        );
+        assert!(snapshot().encode().is_ok());
+    }
+}
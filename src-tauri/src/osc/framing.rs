@@ -0,0 +1,121 @@
+//! OSC over TCP のフレーミング
+//!
+//! TCPはストリームなので、OSC 1.0 の長さプレフィックスまたは OSC 1.1 の
+//! SLIP でパケットの区切りを表す。
+
+use crate::error::{AppError, AppResult};
+use crate::types::OscTcpFraming;
+
+/// 1パケットの最大サイズ（これを超える接続は切断する）
+const MAX_FRAME_SIZE: usize = 1 << 20;
+
+const SLIP_END: u8 = 0xC0;
+const SLIP_ESC: u8 = 0xDB;
+const SLIP_ESC_END: u8 = 0xDC;
+const SLIP_ESC_ESC: u8 = 0xDD;
+
+/// 受信データからOSCパケットを切り出す
+pub struct FrameReader {
+    framing: OscTcpFraming,
+    buffer: Vec<u8>,
+    escape: bool,
+}
+
+impl FrameReader {
+    pub fn new(framing: OscTcpFraming) -> Self {
+        Self {
+            framing,
+            buffer: Vec::new(),
+            escape: false,
+        }
+    }
+
+    /// 受信データを追加し、完成したパケットを返す
+    pub fn push(&mut self, data: &[u8]) -> AppResult<Vec<Vec<u8>>> {
+        let mut frames = Vec::new();
+
+        match self.framing {
+            OscTcpFraming::Length => {
+                self.buffer.extend_from_slice(data);
+                while self.buffer.len() >= 4 {
+                    let size = u32::from_be_bytes([
+                        self.buffer[0],
+                        self.buffer[1],
+                        self.buffer[2],
+                        self.buffer[3],
+                    ]) as usize;
+                    if size > MAX_FRAME_SIZE {
+                        return Err(AppError::Osc(format!("OSC frame too large: {}", size)));
+                    }
+                    if self.buffer.len() < 4 + size {
+                        break;
+                    }
+                    frames.push(self.buffer[4..4 + size].to_vec());
+                    self.buffer.drain(..4 + size);
+                }
+            }
+            OscTcpFraming::Slip => {
+                for &byte in data {
+                    if self.escape {
+                        self.escape = false;
+                        self.buffer.push(match byte {
+                            SLIP_ESC_END => SLIP_END,
+                            SLIP_ESC_ESC => SLIP_ESC,
+                            other => other,
+                        });
+                        continue;
+                    }
+                    match byte {
+                        SLIP_END => {
+                            if !self.buffer.is_empty() {
+                                frames.push(std::mem::take(&mut self.buffer));
+                            }
+                        }
+                        SLIP_ESC => self.escape = true,
+                        _ => self.buffer.push(byte),
+                    }
+                }
+                if self.buffer.len() > MAX_FRAME_SIZE {
+                    return Err(AppError::Osc("OSC frame too large".to_string()));
+                }
+            }
+        }
+
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_prefixed_split_across_reads() {
+        let mut reader = FrameReader::new(OscTcpFraming::Length);
+        let data = [0, 0, 0, 3, b'a', b'b', b'c', 0, 0, 0, 1, b'd'];
+        assert!(reader.push(&data[..5]).unwrap().is_empty());
+        assert_eq!(
+            reader.push(&data[5..]).unwrap(),
+            vec![b"abc".to_vec(), b"d".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_slip_unescape() {
+        let mut reader = FrameReader::new(OscTcpFraming::Slip);
+        let data = [
+            SLIP_END,
+            1,
+            SLIP_ESC,
+            SLIP_ESC_END,
+            2,
+            SLIP_ESC,
+            SLIP_ESC_ESC,
+            SLIP_END,
+        ];
+        assert_eq!(
+            reader.push(&data).unwrap(),
+            vec![vec![1, SLIP_END, 2, SLIP_ESC]]
+        );
+    }
+}
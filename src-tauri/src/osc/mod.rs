@@ -0,0 +1,25 @@
+//! OSC制御
+//!
+//! UDP・TCPでOSCメッセージを受信し、プレイヤー操作に割り当てる。
+//! 登録されたクライアントへは状態をフィードバックする。
+
+pub mod dispatch;
+pub mod feedback;
+pub mod framing;
+pub mod server;
+
+pub use server::OscServer;
+
+use crate::error::AppResult;
+use crate::state::AppState;
+use crate::types::OscConfig;
+
+/// 実行中のサーバーを停止し、指定の設定で起動し直す
+pub fn restart(state: &AppState, config: &OscConfig) -> AppResult<()> {
+    let mut osc_guard = state.osc.lock();
+    if let Some(mut existing) = osc_guard.take() {
+        existing.stop();
+    }
+    *osc_guard = Some(OscServer::start(config, state.clone())?);
+    Ok(())
+}
@@ -0,0 +1,474 @@
+//! OSCサーバー
+//!
+//! UDPは1データグラム = 1パケット、TCPは接続ごとのスレッドでフレームを切り出す。
+//! `/feedback/subscribe [port]` を受けると送信元（TCPの場合は指定ポート必須）を
+//! フィードバック送信先に登録し、`/feedback/unsubscribe [port]` で解除する。
+//!
+//! 最後のエラーは次のメッセージを正常に処理した時点で消し、
+//! フィードバックの `/status/error` にも空文字列を送る。
+
+use parking_lot::Mutex;
+use rosc::{OscMessage, OscPacket};
+use std::io::Read;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::control::{self, ControlInput};
+use crate::error::{AppError, AppResult};
+use crate::osc::dispatch;
+use crate::osc::feedback::FeedbackSnapshot;
+use crate::osc::framing::FrameReader;
+use crate::state::AppState;
+#[cfg(feature = "gui")]
+use crate::types::OscStatus;
+use crate::types::{OscConfig, OscTcpFraming};
+
+/// 停止確認のためのタイムアウト
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// UDPの最大データグラムサイズ
+const UDP_BUFFER_SIZE: usize = 65536;
+
+/// フィードバックの変化確認間隔
+const FEEDBACK_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+const SUBSCRIBE_ADDRESS: &str = "/feedback/subscribe";
+const UNSUBSCRIBE_ADDRESS: &str = "/feedback/unsubscribe";
+
+/// 受信スレッドと共有する状態
+#[derive(Default)]
+struct ServerShared {
+    messages_received: u64,
+    errors: u64,
+    tcp_clients: usize,
+    last_address: Option<String>,
+    last_error: Option<String>,
+    /// /feedback/subscribe で登録された送信先
+    subscribers: Vec<SocketAddr>,
+    /// 次の確認で変化がなくても全体を送る
+    feedback_pending: bool,
+}
+
+impl ServerShared {
+    fn record_error(&mut self, error: String) {
+        self.errors += 1;
+        self.last_error = Some(error);
+    }
+
+    /// メッセージを正常に処理した。残っているエラーを消して通知する
+    fn record_handled(&mut self) {
+        if self.last_error.take().is_some() {
+            self.feedback_pending = true;
+        }
+    }
+}
+
+/// 受信したパケットを処理する
+#[derive(Clone)]
+struct Handler {
+    state: AppState,
+    shared: Arc<Mutex<ServerShared>>,
+}
+
+impl Handler {
+    fn handle_data(&self, data: &[u8], source: SocketAddr) {
+        match rosc::decoder::decode_udp(data) {
+            Ok((_, packet)) => self.handle_packet(packet, source),
+            Err(e) => self.record_error(format!("Invalid OSC packet: {:?}", e)),
+        }
+    }
+
+    fn handle_packet(&self, packet: OscPacket, source: SocketAddr) {
+        match packet {
+            OscPacket::Message(message) => self.handle_message(&message, source),
+            // タイムタグは無視して即時実行
+            OscPacket::Bundle(bundle) => {
+                for packet in bundle.content {
+                    self.handle_packet(packet, source);
+                }
+            }
+        }
+    }
+
+    fn handle_message(&self, message: &OscMessage, source: SocketAddr) {
+        debug!("[OSC] {} {:?}", message.addr, message.args);
+        {
+            let mut shared = self.shared.lock();
+            shared.messages_received += 1;
+            shared.last_address = Some(message.addr.clone());
+        }
+
+        if message.addr == SUBSCRIBE_ADDRESS || message.addr == UNSUBSCRIBE_ADDRESS {
+            self.handle_subscription(message, source);
+            self.shared.lock().record_handled();
+            return;
+        }
+
+        let input = ControlInput::Osc {
+            address: &message.addr,
+            value: message.args.first().and_then(dispatch::number),
+        };
+        let result = control::dispatch_or(&self.state, &input, || {
+            Ok(dispatch::parse(&message.addr, &message.args)?
+                .into_iter()
+                .collect())
+        });
+
+        match result {
+            Ok(_) => self.shared.lock().record_handled(),
+            Err(e) => self.record_error(format!("{}: {}", message.addr, e)),
+        }
+    }
+
+    fn handle_subscription(&self, message: &OscMessage, source: SocketAddr) {
+        let port = message
+            .args
+            .first()
+            .and_then(dispatch::number)
+            .and_then(|port| u16::try_from(port as i64).ok())
+            .unwrap_or(source.port());
+        let target = SocketAddr::new(source.ip(), port);
+
+        let mut shared = self.shared.lock();
+        shared.subscribers.retain(|s| *s != target);
+        if message.addr == SUBSCRIBE_ADDRESS {
+            info!("[OSC] Feedback subscribed: {}", target);
+            shared.subscribers.push(target);
+            shared.feedback_pending = true;
+        } else {
+            info!("[OSC] Feedback unsubscribed: {}", target);
+        }
+    }
+
+    fn record_error(&self, error: String) {
+        warn!("[OSC] {}", error);
+        self.shared.lock().record_error(error);
+    }
+}
+
+/// OSCサーバー
+pub struct OscServer {
+    #[cfg(feature = "gui")]
+    shared: Arc<Mutex<ServerShared>>,
+    running: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+    #[cfg(feature = "gui")]
+    udp_address: Option<String>,
+    #[cfg(feature = "gui")]
+    tcp_address: Option<String>,
+    /// 設定で指定したフィードバック送信先の数
+    #[cfg(feature = "gui")]
+    feedback_targets: usize,
+}
+
+impl OscServer {
+    pub fn start(config: &OscConfig, state: AppState) -> AppResult<Self> {
+        if !config.udp && !config.tcp {
+            return Err(AppError::Osc("Both UDP and TCP are disabled".to_string()));
+        }
+
+        let address = format!("{}:{}", config.bind_address, config.port);
+        let shared = Arc::new(Mutex::new(ServerShared::default()));
+        let running = Arc::new(AtomicBool::new(true));
+        let handler = Handler {
+            state,
+            shared: shared.clone(),
+        };
+
+        let feedback_targets = config
+            .feedback_targets
+            .iter()
+            .map(|target| {
+                target
+                    .to_socket_addrs()
+                    .ok()
+                    .and_then(|mut addrs| addrs.next())
+                    .ok_or_else(|| AppError::Osc(format!("Invalid feedback target: {}", target)))
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        let mut threads = Vec::new();
+        let mut udp_address = None;
+        let mut tcp_address = None;
+        let mut feedback_socket = None;
+
+        if config.udp {
+            let socket = UdpSocket::bind(&address)?;
+            socket.set_read_timeout(Some(POLL_INTERVAL))?;
+            udp_address = Some(socket.local_addr()?.to_string());
+            // 受信ポートから返信すると、送信元ポートで待つコントローラーにも届く
+            feedback_socket = Some(socket.try_clone()?);
+
+            let handler = handler.clone();
+            let running = running.clone();
+            threads.push(
+                std::thread::Builder::new()
+                    .name("osc-udp".to_string())
+                    .spawn(move || Self::run_udp(socket, handler, running))
+                    .map_err(AppError::Io)?,
+            );
+        }
+
+        if config.tcp {
+            let listener = TcpListener::bind(&address)?;
+            listener.set_nonblocking(true)?;
+            tcp_address = Some(listener.local_addr()?.to_string());
+
+            let framing = config.tcp_framing;
+            let handler = handler.clone();
+            let running = running.clone();
+            threads.push(
+                std::thread::Builder::new()
+                    .name("osc-tcp".to_string())
+                    .spawn(move || Self::run_tcp(listener, framing, handler, running))
+                    .map_err(AppError::Io)?,
+            );
+        }
+
+        {
+            let socket = match feedback_socket {
+                Some(socket) => socket,
+                None => UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?,
+            };
+            let rate_hz = config.feedback_rate_hz;
+            let running = running.clone();
+            threads.push(
+                std::thread::Builder::new()
+                    .name("osc-feedback".to_string())
+                    .spawn(move || {
+                        Self::run_feedback(socket, feedback_targets, rate_hz, handler, running)
+                    })
+                    .map_err(AppError::Io)?,
+            );
+        }
+
+        info!(
+            "[OSC] Listening (udp={:?}, tcp={:?})",
+            udp_address, tcp_address
+        );
+
+        Ok(Self {
+            #[cfg(feature = "gui")]
+            shared,
+            running,
+            threads,
+            #[cfg(feature = "gui")]
+            udp_address,
+            #[cfg(feature = "gui")]
+            tcp_address,
+            #[cfg(feature = "gui")]
+            feedback_targets: config.feedback_targets.len(),
+        })
+    }
+
+    fn run_udp(socket: UdpSocket, handler: Handler, running: Arc<AtomicBool>) {
+        let mut buf = vec![0u8; UDP_BUFFER_SIZE];
+        while running.load(Ordering::Relaxed) {
+            match socket.recv_from(&mut buf) {
+                Ok((len, source)) => handler.handle_data(&buf[..len], source),
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => {
+                    warn!("[OSC] UDP receive error: {}", e);
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+        }
+        debug!("[OSC] UDP thread finished");
+    }
+
+    fn run_tcp(
+        listener: TcpListener,
+        framing: OscTcpFraming,
+        handler: Handler,
+        running: Arc<AtomicBool>,
+    ) {
+        while running.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, peer)) => {
+                    info!("[OSC] TCP client connected: {}", peer);
+                    let handler = handler.clone();
+                    let running = running.clone();
+                    let spawned = std::thread::Builder::new()
+                        .name("osc-tcp-client".to_string())
+                        .spawn(move || {
+                            handler.shared.lock().tcp_clients += 1;
+                            Self::serve_client(stream, peer, framing, &handler, &running);
+                            handler.shared.lock().tcp_clients -= 1;
+                            info!("[OSC] TCP client disconnected: {}", peer);
+                        });
+                    if let Err(e) = spawned {
+                        warn!("[OSC] Failed to spawn client thread: {}", e);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => {
+                    warn!("[OSC] TCP accept error: {}", e);
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+        }
+        debug!("[OSC] TCP thread finished");
+    }
+
+    fn serve_client(
+        mut stream: TcpStream,
+        peer: SocketAddr,
+        framing: OscTcpFraming,
+        handler: &Handler,
+        running: &AtomicBool,
+    ) {
+        if stream.set_nonblocking(false).is_err()
+            || stream.set_read_timeout(Some(POLL_INTERVAL)).is_err()
+        {
+            return;
+        }
+
+        let mut reader = FrameReader::new(framing);
+        let mut buf = [0u8; 4096];
+
+        while running.load(Ordering::Relaxed) {
+            match stream.read(&mut buf) {
+                Ok(0) => break,
+                Ok(len) => match reader.push(&buf[..len]) {
+                    Ok(frames) => {
+                        for frame in frames {
+                            handler.handle_data(&frame, peer);
+                        }
+                    }
+                    Err(e) => {
+                        handler.record_error(e.to_string());
+                        break;
+                    }
+                },
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => {
+                    debug!("[OSC] TCP read error: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// 変化時と一定周期で状態を送信
+    fn run_feedback(
+        socket: UdpSocket,
+        targets: Vec<SocketAddr>,
+        rate_hz: u32,
+        handler: Handler,
+        running: Arc<AtomicBool>,
+    ) {
+        let interval = (rate_hz > 0).then(|| Duration::from_secs_f64(1.0 / rate_hz as f64));
+        let mut last_sent: Option<FeedbackSnapshot> = None;
+        let mut next_periodic = Instant::now();
+
+        while running.load(Ordering::Relaxed) {
+            std::thread::sleep(FEEDBACK_POLL_INTERVAL);
+
+            let (recipients, pending, last_error) = {
+                let mut shared = handler.shared.lock();
+                let recipients: Vec<SocketAddr> = targets
+                    .iter()
+                    .chain(shared.subscribers.iter())
+                    .copied()
+                    .collect();
+                let pending = std::mem::take(&mut shared.feedback_pending);
+                (recipients, pending, shared.last_error.clone())
+            };
+            if recipients.is_empty() {
+                continue;
+            }
+
+            let snapshot = FeedbackSnapshot::capture(&handler.state, last_error);
+            let now = Instant::now();
+            let changed = last_sent
+                .as_ref()
+                .is_none_or(|last| snapshot.differs_from(last));
+            let periodic = interval.is_some() && now >= next_periodic;
+            if !(pending || changed || periodic) {
+                continue;
+            }
+
+            let data = match snapshot.encode() {
+                Ok(data) => data,
+                Err(e) => {
+                    warn!("[OSC] Failed to encode feedback: {}", e);
+                    continue;
+                }
+            };
+            for target in &recipients {
+                if let Err(e) = socket.send_to(&data, target) {
+                    debug!("[OSC] Failed to send feedback to {}: {}", target, e);
+                }
+            }
+
+            last_sent = Some(snapshot);
+            if let Some(interval) = interval {
+                next_periodic = now + interval;
+            }
+        }
+        debug!("[OSC] Feedback thread finished");
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> OscStatus {
+        let shared = self.shared.lock();
+        OscStatus {
+            running: self.running.load(Ordering::Relaxed),
+            udp_address: self.udp_address.clone(),
+            tcp_address: self.tcp_address.clone(),
+            tcp_clients: shared.tcp_clients,
+            feedback_clients: self.feedback_targets + shared.subscribers.len(),
+            messages_received: shared.messages_received,
+            errors: shared.errors,
+            last_address: shared.last_address.clone(),
+            last_error: shared.last_error.clone(),
+        }
+    }
+
+    /// 受信スレッドを停止（TCPクライアントのスレッドも次のタイムアウトで終了する）
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+        info!("[OSC] Stopped");
+    }
+}
+
+impl Drop for OscServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_error_clears_after_handled_message() {
+        let mut shared = ServerShared::default();
+        shared.record_handled();
+        assert!(!shared.feedback_pending);
+
+        shared.record_error("/cue/99/go: Cue not found".to_string());
+        assert_eq!(shared.errors, 1);
+        assert!(shared.last_error.is_some());
+
+        shared.record_handled();
+        assert_eq!(shared.last_error, None);
+        assert_eq!(shared.errors, 1);
+        // 空の /status/error を送る
+        assert!(shared.feedback_pending);
+    }
+}
@@ -1,11 +1,17 @@
 use std::collections::HashMap;
+#[cfg(feature = "gui")]
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+#[cfg(feature = "gui")]
 use tracing::{debug, warn};
 
+#[cfg(feature = "gui")]
 use crate::error::{AppError, AppResult};
+#[cfg(feature = "gui")]
 use crate::types::*;
 
-use super::native_handle::{get_native_handle, NativeHandle};
+#[cfg(feature = "gui")]
+use super::native_handle::get_native_handle;
+use super::native_handle::NativeHandle;
 
 pub struct OutputManager {
     outputs: HashMap<String, OutputWindowState>,
@@ -13,9 +19,12 @@ pub struct OutputManager {
 
 /// State for an output window including native handle for GStreamer
 pub struct OutputWindowState {
+    #[cfg(feature = "gui")]
     pub id: String,
+    #[cfg(feature = "gui")]
     pub output_type: OutputType,
     pub native_handle: Option<NativeHandle>,
+    #[cfg(feature = "gui")]
     pub monitor_index: Option<usize>,
 }
 
@@ -26,6 +35,26 @@ impl OutputManager {
         }
     }
 
+    /// Get the native handle for an output (for GStreamer sink creation)
+    pub fn get_native_handle(&self, output_id: &str) -> Option<NativeHandle> {
+        self.outputs.get(output_id)?.native_handle.clone()
+    }
+
+    /// Check if an output window exists
+    #[cfg(feature = "gui")]
+    pub fn has_output(&self, output_id: &str) -> bool {
+        self.outputs.contains_key(output_id)
+    }
+
+    /// Get all open output IDs
+    pub fn get_open_output_ids(&self) -> Vec<String> {
+        self.outputs.keys().cloned().collect()
+    }
+}
+
+/// 出力ウィンドウの作成・破棄（Tauriが必要）
+#[cfg(feature = "gui")]
+impl OutputManager {
     /// Create an output window and extract native handle for GStreamer
     ///
     /// # Arguments
@@ -174,21 +203,6 @@ impl OutputManager {
         }
     }
 
-    /// Get the native handle for an output (for GStreamer sink creation)
-    pub fn get_native_handle(&self, output_id: &str) -> Option<NativeHandle> {
-        self.outputs.get(output_id)?.native_handle.clone()
-    }
-
-    /// Check if an output window exists
-    pub fn has_output(&self, output_id: &str) -> bool {
-        self.outputs.contains_key(output_id)
-    }
-
-    /// Get all open output IDs
-    pub fn get_open_output_ids(&self) -> Vec<String> {
-        self.outputs.keys().cloned().collect()
-    }
-
     pub fn close_output(&mut self, app: &AppHandle, id: &str) {
         if let Some(output) = self.outputs.remove(id) {
             if output.output_type == OutputType::Display {
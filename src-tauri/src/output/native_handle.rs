@@ -4,7 +4,9 @@
 //! and create GStreamer video sinks that render directly to those windows.
 
 use gstreamer as gst;
+#[cfg(feature = "gui")]
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+#[cfg(feature = "gui")]
 use tauri::WebviewWindow;
 use tracing::{debug, warn};
 
@@ -28,6 +30,7 @@ unsafe impl Send for NativeHandle {}
 unsafe impl Sync for NativeHandle {}
 
 /// Extract native window handle from a Tauri WebviewWindow
+#[cfg(feature = "gui")]
 pub fn get_native_handle(window: &WebviewWindow) -> Option<NativeHandle> {
     let handle = window.window_handle().ok()?;
 
@@ -9,14 +9,19 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error, warn};
 
+use crate::audio::bus as audio_bus;
 use crate::error::{AppError, AppResult};
 use crate::output::native_handle::NativeHandle;
 use crate::pipeline::media_handler;
-use crate::pipeline::NdiSender;
 #[cfg(target_os = "macos")]
 use crate::pipeline::SyphonSender;
+use crate::pipeline::{NdiSender, DEFAULT_NDI_NAME};
+use crate::timecode::ltc_output;
 use crate::types::*;
 
+/// play_at_epochで開始位置を決めるときの猶予 (ms)
+const ALIGN_LEAD_MS: u64 = 200;
+
 /// 出力先とモニター情報、ネイティブハンドルを組み合わせた構造体
 #[derive(Debug, Clone)]
 pub struct OutputWithMonitor {
@@ -45,6 +50,12 @@ pub struct CuePlayer {
     master_brightness: f64,
     master_volume: f64,
     output_brightness: HashMap<String, Option<f64>>,
+    /// LTC出力設定（load_cue時に反映）
+    ltc_output: LtcOutputConfig,
+    /// 外部から指定したパイプラインクロック（ネットワーククロック共有用）
+    clock: Option<gst::Clock>,
+    /// 暗転中（明るさ設定は保持したまま全映像出力を0にする）
+    blackout: bool,
 }
 
 impl CuePlayer {
@@ -64,6 +75,9 @@ impl CuePlayer {
             master_brightness: 100.0,
             master_volume: 100.0,
             output_brightness: HashMap::new(),
+            ltc_output: LtcOutputConfig::default(),
+            clock: None,
+            blackout: false,
         })
     }
 
@@ -95,6 +109,25 @@ impl CuePlayer {
             }
         }
 
+        // Audio出力ごとにシンクを1つ開き、アイテムとLTCを合成する
+        let ltc_output_id = self
+            .ltc_output
+            .enabled
+            .then_some(self.ltc_output.output_id.as_str());
+        for owm in &outputs_with_monitors {
+            let output = &owm.output;
+            let ltc = ltc_output_id == Some(output.id.as_str());
+            let used = cue
+                .items
+                .iter()
+                .any(|i| i.media_type == MediaType::Audio && i.output_id == output.id);
+            if output.output_type == OutputType::Audio && (used || ltc) {
+                let channels =
+                    audio_bus::channel_count(output, ltc.then_some(self.ltc_output.channel));
+                audio_bus::add_output_bus(&self.pipeline, output, channels)?;
+            }
+        }
+
         // 各メディアアイテムを追加
         for item in &cue.items {
             let owm = outputs_with_monitors
@@ -118,6 +151,28 @@ impl CuePlayer {
             media_handler::add_media_item(&self.pipeline, item, owm, brightness, appsink_weak)?;
         }
 
+        // LTC出力（Cueごとに開始タイムコードから生成）
+        if self.ltc_output.enabled {
+            let owm = outputs_with_monitors
+                .iter()
+                .find(|o| o.output.id == self.ltc_output.output_id)
+                .ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "LTC output not found: {}",
+                        self.ltc_output.output_id
+                    ))
+                })?;
+            let duration = (cue.duration > 0.0).then_some(cue.duration);
+            let channels = audio_bus::channel_count(&owm.output, Some(self.ltc_output.channel));
+            ltc_output::add_ltc_output(
+                &self.pipeline,
+                &self.ltc_output,
+                &owm.output,
+                channels,
+                duration,
+            )?;
+        }
+
         self.configure_live_mode(&outputs_with_monitors);
         self.preroll_pipeline()?;
         self.apply_master_volume();
@@ -197,7 +252,7 @@ impl CuePlayer {
     /// NDI出力用のNdiSenderとappsinkを作成
     fn setup_ndi_sender(&mut self, owm: &OutputWithMonitor) -> AppResult<()> {
         let output_id = &owm.output.id;
-        let ndi_name = owm.output.ndi_name.as_deref().unwrap_or("TauriLivePlayer");
+        let ndi_name = owm.output.ndi_name.as_deref().unwrap_or(DEFAULT_NDI_NAME);
 
         debug!(
             "[CuePlayer] Setting up NDI sender for '{}' (ndi-name='{}')",
@@ -399,6 +454,9 @@ impl CuePlayer {
     }
 
     fn get_effective_brightness(&self, output_id: &str) -> f64 {
+        if self.blackout {
+            return 0.0;
+        }
         self.output_brightness
             .get(output_id)
             .and_then(|b| *b)
@@ -510,12 +568,104 @@ impl CuePlayer {
         Ok(())
     }
 
+    /// 再生速度を変更
+    ///
+    /// フラッシュせずに速度だけを切り替えるため、同期の微調整に使える
+    pub fn set_rate(&self, rate: f64) -> AppResult<()> {
+        self.pipeline
+            .seek(
+                rate,
+                gst::SeekFlags::INSTANT_RATE_CHANGE,
+                gst::SeekType::None,
+                gst::ClockTime::NONE,
+                gst::SeekType::None,
+                gst::ClockTime::NONE,
+            )
+            .map_err(|e| AppError::Pipeline(format!("Failed to set rate: {:?}", e)))?;
+        Ok(())
+    }
+
+    // ========================================
+    // クロック共有
+    // ========================================
+
+    /// パイプラインのクロックを固定（Noneで自動選択に戻す）
+    ///
+    /// パイプラインはCue間で再利用されるため、次のCue以降も同じクロックを使う
+    pub fn set_clock(&mut self, clock: Option<gst::Clock>) {
+        match &clock {
+            Some(clock) => self.pipeline.use_clock(Some(clock)),
+            None => {
+                self.pipeline.auto_clock();
+                // play_at_epochで無効化したbase_timeの自動選択を戻す
+                self.pipeline.set_start_time(gst::ClockTime::ZERO);
+            }
+        }
+        self.clock = clock;
+    }
+
+    /// 共有クロック上の時刻と再生位置を同時に取得
+    ///
+    /// 再生中のみ。`時刻 - 位置` が再生位置0に対応するクロック時刻（エポック）になる
+    pub fn clock_position(&self) -> Option<(gst::ClockTime, gst::ClockTime)> {
+        if self.pipeline.current_state() != gst::State::Playing {
+            return None;
+        }
+        let clock = self.pipeline.clock()?;
+        let now = clock.time()?;
+        let position = self.pipeline.query_position::<gst::ClockTime>()?;
+        Some((now, position))
+    }
+
+    /// 共有クロック上のエポックに合わせて再生
+    ///
+    /// 現在時刻から少し先の位置へフラッシュseekし、base_timeを
+    /// `エポック + 位置 - レイテンシ` に固定する。同じクロックを使う他のマシンと
+    /// 位置の追従ではなくクロック基準でフレーム単位に揃う。
+    pub fn play_at_epoch(&self, epoch: gst::ClockTime) -> AppResult<()> {
+        let clock = self
+            .clock
+            .as_ref()
+            .ok_or_else(|| AppError::InvalidState("No shared clock set".to_string()))?;
+        let now = clock
+            .time()
+            .ok_or_else(|| AppError::InvalidState("Shared clock has no time".to_string()))?;
+
+        // seek・プリロールに必要な猶予を見込んだ開始位置
+        let start_at = now + gst::ClockTime::from_mseconds(ALIGN_LEAD_MS);
+        let target = start_at.saturating_sub(epoch);
+        let latency = self.pipeline.latency().unwrap_or(gst::ClockTime::ZERO);
+
+        // 子エレメントへbase_timeを配布し直すため一旦PAUSEDに戻す
+        if self.pipeline.current_state() == gst::State::Playing {
+            self.pause()?;
+        }
+
+        self.pipeline.set_start_time(gst::ClockTime::NONE);
+        self.seek(target.seconds_f64())?;
+        self.pipeline
+            .set_base_time((epoch + target).saturating_sub(latency));
+
+        debug!(
+            "[CuePlayer] play_at_epoch: epoch={}, target={}, latency={}",
+            epoch, target, latency
+        );
+
+        self.pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|e| AppError::Pipeline(format!("Failed to play: {:?}", e)))?;
+        Ok(())
+    }
+
     // ========================================
     // 明るさ調整
     // ========================================
 
     pub fn set_master_brightness(&mut self, value: f64) {
         self.master_brightness = value;
+        if self.blackout {
+            return;
+        }
 
         for (output_id, balance) in &self.video_balances {
             if self
@@ -532,6 +682,9 @@ impl CuePlayer {
 
     pub fn set_output_brightness(&mut self, output_id: &str, value: Option<f64>) {
         self.output_brightness.insert(output_id.to_string(), value);
+        if self.blackout {
+            return;
+        }
 
         if let Some(balance) = self.video_balances.get(output_id) {
             let effective = value.unwrap_or(self.master_brightness);
@@ -540,6 +693,29 @@ impl CuePlayer {
         }
     }
 
+    /// 暗転中か
+    pub fn is_blackout(&self) -> bool {
+        self.blackout
+    }
+
+    /// 全映像出力を暗転（解除で現在の明るさに戻す）
+    pub fn set_blackout(&mut self, enabled: bool) {
+        self.blackout = enabled;
+        for (output_id, balance) in &self.video_balances {
+            let gst_brightness = (self.get_effective_brightness(output_id) / 100.0) - 1.0;
+            balance.set_property("brightness", gst_brightness);
+        }
+    }
+
+    // ========================================
+    // LTC出力
+    // ========================================
+
+    /// LTC出力設定を変更（次のload_cueから反映）
+    pub fn set_ltc_output(&mut self, config: LtcOutputConfig) {
+        self.ltc_output = config;
+    }
+
     // ========================================
     // 音量調整
     // ========================================
@@ -586,6 +762,21 @@ impl CuePlayer {
             .map(|d| d.seconds_f64())
     }
 
+    /// 全シンクのドロップフレーム数の合計（basesinkのstatsプロパティ）
+    pub fn dropped_frames(&self) -> u64 {
+        self.pipeline
+            .iterate_sinks()
+            .into_iter()
+            .flatten()
+            .filter(|sink| sink.find_property("stats").is_some())
+            .filter_map(|sink| {
+                sink.property::<gst::Structure>("stats")
+                    .get::<u64>("dropped")
+                    .ok()
+            })
+            .sum()
+    }
+
     pub fn state(&self) -> gst::State {
         self.pipeline.current_state()
     }
@@ -8,10 +8,10 @@ use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
 use tracing::{debug, error};
 
-use crate::audio::sink::create_audio_sink;
+use crate::audio::bus as audio_bus;
 use crate::error::{AppError, AppResult};
 use crate::output::native_handle::{create_fallback_sink, create_video_sink_with_handle};
-use crate::pipeline::OutputWithMonitor;
+use crate::pipeline::{OutputWithMonitor, DEFAULT_NDI_NAME};
 use crate::types::*;
 
 /// メディアアイテムをパイプラインに追加
@@ -76,7 +76,7 @@ pub fn add_media_item(
             // ビデオアイテムからのオーディオパッドは fakesink に捨てる
             handle_audio_pad_from_video(&pipeline, src_pad, &item_clone);
         } else if name.starts_with("audio/") && item_clone.media_type == MediaType::Audio {
-            handle_audio_pad(&pipeline, src_pad, &owm_clone);
+            handle_audio_pad(&pipeline, src_pad, &item_clone, &owm_clone);
         }
     });
 
@@ -439,8 +439,13 @@ fn handle_audio_pad_from_video(pipeline: &gst::Pipeline, src_pad: &gst::Pad, ite
     let _ = fakesink.sync_state_with_parent();
 }
 
-/// オーディオアイテムのオーディオパッドを処理
-fn handle_audio_pad(pipeline: &gst::Pipeline, src_pad: &gst::Pad, owm: &OutputWithMonitor) {
+/// オーディオアイテムのオーディオパッドを処理（出力のオーディオバスへ合成）
+fn handle_audio_pad(
+    pipeline: &gst::Pipeline,
+    src_pad: &gst::Pad,
+    item: &MediaItem,
+    owm: &OutputWithMonitor,
+) {
     let convert = match gst::ElementFactory::make("audioconvert").build() {
         Ok(e) => e,
         Err(e) => {
@@ -458,7 +463,8 @@ fn handle_audio_pad(pipeline: &gst::Pipeline, src_pad: &gst::Pad, owm: &OutputWi
     };
 
     // Volume element with unique name for later access
-    let volume_name = format!("volume_{}", owm.output.id);
+    // （同じ出力に複数のアイテムがあるためアイテムIDも含める。LTCはバスで合成するので対象外）
+    let volume_name = format!("volume_{}_{}", owm.output.id, item.id);
     let volume = match gst::ElementFactory::make("volume")
         .name(&volume_name)
         .property("volume", 1.0_f64)
@@ -471,24 +477,37 @@ fn handle_audio_pad(pipeline: &gst::Pipeline, src_pad: &gst::Pad, owm: &OutputWi
         }
     };
 
-    let sink = match create_audio_sink(&owm.output) {
-        Ok(s) => s,
+    let mixer_pad = match audio_bus::request_pad(pipeline, &owm.output) {
+        Ok(p) => p,
         Err(e) => {
-            error!("Failed to create audio sink: {:?}", e);
+            error!("Failed to get audio bus input: {}", e);
             return;
         }
     };
 
-    if let Err(e) = pipeline.add_many([&convert, &resample, &volume, &sink]) {
+    if let Err(e) = pipeline.add_many([&convert, &resample, &volume]) {
         error!("Failed to add audio elements to pipeline: {:?}", e);
         return;
     }
 
-    if let Err(e) = gst::Element::link_many([&convert, &resample, &volume, &sink]) {
+    if let Err(e) = gst::Element::link_many([&convert, &resample, &volume]) {
         error!("Failed to link audio elements: {:?}", e);
         return;
     }
 
+    let volume_pad = match volume.static_pad("src") {
+        Some(p) => p,
+        None => {
+            error!("Failed to get src pad from volume");
+            return;
+        }
+    };
+
+    if let Err(e) = volume_pad.link(&mixer_pad) {
+        error!("Failed to link volume to audio bus: {:?}", e);
+        return;
+    }
+
     let sink_pad = match convert.static_pad("sink") {
         Some(p) => p,
         None => {
@@ -505,7 +524,6 @@ fn handle_audio_pad(pipeline: &gst::Pipeline, src_pad: &gst::Pad, owm: &OutputWi
     let _ = convert.sync_state_with_parent();
     let _ = resample.sync_state_with_parent();
     let _ = volume.sync_state_with_parent();
-    let _ = sink.sync_state_with_parent();
 }
 
 /// ビデオシンクの作成
@@ -565,7 +583,7 @@ pub fn create_video_sink(owm: &OutputWithMonitor) -> Result<gst::Element, gst::g
         }
         OutputType::Ndi => {
             // NDI送信
-            let ndi_name = owm.output.ndi_name.as_deref().unwrap_or("TauriLivePlayer");
+            let ndi_name = owm.output.ndi_name.as_deref().unwrap_or(DEFAULT_NDI_NAME);
             debug!(
                 "Creating NDI sink for '{}' with ndi-name='{}'",
                 owm.output.name, ndi_name
@@ -1,12 +1,13 @@
 pub mod cue_player;
 pub mod media_handler;
 pub mod ndi_sender;
+pub mod probe;
 
 #[cfg(target_os = "macos")]
 pub mod syphon_sender;
 
 pub use cue_player::OutputWithMonitor;
-pub use ndi_sender::NdiSender;
+pub use ndi_sender::{NdiSender, DEFAULT_NDI_NAME};
 
 #[cfg(target_os = "macos")]
 pub use syphon_sender::SyphonSender;
@@ -21,6 +21,9 @@ use tracing::{debug, error, info, trace};
 
 use crate::error::{AppError, AppResult};
 
+/// 出力にNDI送信名が無い場合の名前
+pub const DEFAULT_NDI_NAME: &str = "TauriLivePlayer";
+
 /// NDI送信を管理する構造体
 /// appsink からフレームを受け取り、NDI SDK で送信
 pub struct NdiSender {
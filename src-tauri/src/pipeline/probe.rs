@@ -0,0 +1,60 @@
+//! メディアファイルの解析
+//!
+//! GStreamer Discoverer でファイルをデコードできるか（プラグインが揃っているか）と
+//! 含まれるストリームを調べる。プロジェクトの検証で使う。
+
+use gstreamer as gst;
+use gstreamer_pbutils as gst_pbutils;
+use std::path::Path;
+
+use crate::error::{AppError, AppResult};
+
+/// メディアファイルに含まれるストリーム
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaStreams {
+    pub video: bool,
+    pub audio: bool,
+}
+
+/// 1ファイルあたりの解析のタイムアウト（秒）
+const PROBE_TIMEOUT_SECS: u64 = 10;
+
+pub struct MediaProber {
+    discoverer: gst_pbutils::Discoverer,
+}
+
+impl MediaProber {
+    pub fn new() -> AppResult<Self> {
+        let discoverer =
+            gst_pbutils::Discoverer::new(gst::ClockTime::from_seconds(PROBE_TIMEOUT_SECS))
+                .map_err(|e| AppError::GStreamer(format!("Failed to create discoverer: {}", e)))?;
+        Ok(Self { discoverer })
+    }
+
+    /// ファイルを解析し、デコードできなければエラー内容を返す
+    pub fn probe(&self, path: &Path) -> Result<MediaStreams, String> {
+        let uri = gst::glib::filename_to_uri(path, None).map_err(|e| e.to_string())?;
+        let info = self
+            .discoverer
+            .discover_uri(&uri)
+            .map_err(|e| e.to_string())?;
+
+        match info.result() {
+            gst_pbutils::DiscovererResult::Ok => {}
+            gst_pbutils::DiscovererResult::MissingPlugins => {
+                let missing: Vec<String> = info
+                    .missing_elements_installer_details()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect();
+                return Err(format!("Missing GStreamer plugins: {}", missing.join(", ")));
+            }
+            other => return Err(format!("Discoverer result {:?}", other)),
+        }
+
+        Ok(MediaStreams {
+            video: !info.video_streams().is_empty(),
+            audio: !info.audio_streams().is_empty(),
+        })
+    }
+}
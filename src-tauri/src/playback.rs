@@ -0,0 +1,270 @@
+//! 再生操作
+//!
+//! Tauriコマンドとマルチ PC 同期などのバックグラウンドサービスから共通で使う
+//! プレイヤー操作。ウィンドウやモニター情報は AppState のキャッシュを参照するため、
+//! AppHandle なしで呼び出せる。
+
+use std::collections::HashMap;
+
+use crate::error::{AppError, AppResult};
+use crate::local_config;
+use crate::output::native_handle::NativeHandle;
+use crate::pipeline::cue_player::CuePlayer;
+use crate::state::AppState;
+use crate::types::*;
+
+/// プレイヤーを取得して操作を実行
+fn with_player<T>(
+    state: &AppState,
+    f: impl FnOnce(&mut CuePlayer) -> AppResult<T>,
+) -> AppResult<T> {
+    let mut player_guard = state.player.lock();
+    let player = player_guard
+        .as_mut()
+        .ok_or_else(|| AppError::InvalidState("Player not initialized".to_string()))?;
+    f(player)
+}
+
+/// OutputManagerからネイティブハンドルを収集
+fn collect_native_handles(state: &AppState) -> HashMap<String, NativeHandle> {
+    let output_manager = state.output_manager.lock();
+    let open_ids = output_manager.get_open_output_ids();
+    tracing::debug!(
+        "[load_cue] Open output IDs in OutputManager: {:?}",
+        open_ids
+    );
+
+    let handles: HashMap<String, NativeHandle> = open_ids
+        .iter()
+        .filter_map(|id| {
+            output_manager
+                .get_native_handle(id)
+                .map(|handle| (id.clone(), handle))
+        })
+        .collect();
+
+    tracing::debug!(
+        "[load_cue] Native handles collected: {:?}",
+        handles.keys().collect::<Vec<_>>()
+    );
+    handles
+}
+
+/// プロジェクトのCueを読み込む
+pub fn load_cue(state: &AppState, cue_index: usize) -> AppResult<()> {
+    let monitors = state.monitors.lock().clone();
+    let native_handles = collect_native_handles(state);
+
+    let mut player_guard = state.player.lock();
+    let project_guard = state.project.lock();
+
+    let project = project_guard
+        .as_ref()
+        .ok_or_else(|| AppError::InvalidState("No project loaded".to_string()))?;
+
+    let cue = project
+        .cues
+        .get(cue_index)
+        .ok_or_else(|| AppError::NotFound("Cue not found".to_string()))?;
+
+    let player = player_guard
+        .as_mut()
+        .ok_or_else(|| AppError::InvalidState("Player not initialized".to_string()))?;
+
+    // 相対パスの解決と出力先の上書きはこのPCの設定で行う
+    let (cue, outputs) = {
+        let local = state.local_config.lock();
+        (
+            local_config::resolve_cue(cue, local.media_root.as_deref()),
+            local_config::apply_outputs(&project.outputs, &local),
+        )
+    };
+
+    player.set_ltc_output(project.settings.ltc_output.clone());
+    player.load_cue(&cue, &outputs, &monitors, &native_handles)?;
+
+    *state.current_cue_index.lock() = cue_index as i32;
+
+    Ok(())
+}
+
+/// Cueを読み込んで再生
+pub fn go_cue(state: &AppState, cue_index: usize) -> AppResult<()> {
+    load_cue(state, cue_index)?;
+    play(state)
+}
+
+/// GO
+///
+/// 読み込み済み（一時停止中）のCueがあれば再生し、再生中・停止後は
+/// 次のCueを読み込んで再生する
+pub fn go(state: &AppState) -> AppResult<()> {
+    let current = player_state(state);
+    if matches!(current.status, PlayerStatus::Ready | PlayerStatus::Paused)
+        && current.current_cue_index >= 0
+    {
+        return play(state);
+    }
+
+    let next = (current.current_cue_index + 1).max(0) as usize;
+    let cue_count = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|p| p.cues.len())
+        .ok_or_else(|| AppError::InvalidState("No project loaded".to_string()))?;
+    if next >= cue_count {
+        return Err(AppError::NotFound("No next cue".to_string()));
+    }
+
+    go_cue(state, next)
+}
+
+pub fn play(state: &AppState) -> AppResult<()> {
+    with_player(state, |player| player.play())
+}
+
+pub fn pause(state: &AppState) -> AppResult<()> {
+    with_player(state, |player| player.pause())
+}
+
+pub fn stop(state: &AppState) -> AppResult<()> {
+    with_player(state, |player| player.stop())
+}
+
+pub fn seek(state: &AppState, position: f64) -> AppResult<()> {
+    with_player(state, |player| player.seek(position))
+}
+
+/// 再生速度を変更（同期補正用）
+pub fn set_rate(state: &AppState, rate: f64) -> AppResult<()> {
+    with_player(state, |player| player.set_rate(rate))
+}
+
+/// パイプラインのクロックを設定（ネットワーククロック共有用）
+pub fn set_clock(state: &AppState, clock: Option<gstreamer::Clock>) -> AppResult<()> {
+    with_player(state, |player| {
+        player.set_clock(clock);
+        Ok(())
+    })
+}
+
+/// 共有クロック上のエポックに合わせて再生
+pub fn play_at_epoch(state: &AppState, epoch: gstreamer::ClockTime) -> AppResult<()> {
+    with_player(state, |player| player.play_at_epoch(epoch))
+}
+
+/// 共有クロック上の時刻と再生位置（再生中のみ）
+pub fn clock_position(state: &AppState) -> Option<(gstreamer::ClockTime, gstreamer::ClockTime)> {
+    state
+        .player
+        .lock()
+        .as_ref()
+        .and_then(|player| player.clock_position())
+}
+
+pub fn set_master_brightness(state: &AppState, value: f64) -> AppResult<()> {
+    with_player(state, |player| {
+        player.set_master_brightness(value);
+        Ok(())
+    })?;
+
+    // プロジェクトの値も更新
+    if let Some(project) = state.project.lock().as_mut() {
+        project.master_brightness = value;
+    }
+
+    Ok(())
+}
+
+pub fn set_output_brightness(
+    state: &AppState,
+    output_id: &str,
+    value: Option<f64>,
+) -> AppResult<()> {
+    with_player(state, |player| {
+        player.set_output_brightness(output_id, value);
+        Ok(())
+    })?;
+
+    // プロジェクトの値も更新
+    if let Some(project) = state.project.lock().as_mut() {
+        if let Some(output) = project.outputs.iter_mut().find(|o| o.id == output_id) {
+            output.brightness = value;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn set_master_volume(state: &AppState, value: f64) -> AppResult<()> {
+    with_player(state, |player| {
+        player.set_master_volume(value);
+        Ok(())
+    })?;
+
+    // プロジェクトの値も更新
+    if let Some(project) = state.project.lock().as_mut() {
+        project.master_volume = value;
+    }
+
+    Ok(())
+}
+
+/// 全映像出力を暗転（プロジェクトの明るさは変更しない）
+pub fn set_blackout(state: &AppState, enabled: bool) -> AppResult<()> {
+    with_player(state, |player| {
+        player.set_blackout(enabled);
+        Ok(())
+    })
+}
+
+/// 暗転中か
+pub fn blackout(state: &AppState) -> bool {
+    state
+        .player
+        .lock()
+        .as_ref()
+        .is_some_and(|player| player.is_blackout())
+}
+
+/// ドロップフレーム数
+pub fn dropped_frames(state: &AppState) -> u64 {
+    state
+        .player
+        .lock()
+        .as_ref()
+        .map(|player| player.dropped_frames())
+        .unwrap_or(0)
+}
+
+/// 現在のプレイヤー状態
+pub fn player_state(state: &AppState) -> PlayerState {
+    let player_guard = state.player.lock();
+
+    let (status, current_time, duration) = match player_guard.as_ref() {
+        Some(player) => {
+            let status = match player.state() {
+                gstreamer::State::Null => PlayerStatus::Idle,
+                gstreamer::State::Ready => PlayerStatus::Ready,
+                gstreamer::State::Paused => PlayerStatus::Paused,
+                gstreamer::State::Playing => PlayerStatus::Playing,
+                _ => PlayerStatus::Idle,
+            };
+            let pos = player.position().unwrap_or(0.0);
+            let dur = player.duration().unwrap_or(0.0);
+            (status, pos, dur)
+        }
+        None => (PlayerStatus::Idle, 0.0, 0.0),
+    };
+
+    let current_cue_index = *state.current_cue_index.lock();
+
+    PlayerState {
+        status,
+        current_cue_index,
+        current_time,
+        duration,
+        error: None,
+    }
+}
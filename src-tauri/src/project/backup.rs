@@ -0,0 +1,176 @@
+//! プロジェクトファイルのバックアップ
+//!
+//! 保存で上書きする前のファイルを `<名前>.backups/<名前>-<UTC日時>.<拡張子>` に
+//! コピーし、古いものから削除して指定数だけ残す。
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+use crate::error::{AppError, AppResult};
+use crate::types::BackupInfo;
+
+/// バックアップを置くフォルダ
+pub fn backup_dir(project_path: &Path) -> Option<PathBuf> {
+    let stem = project_path.file_stem()?.to_string_lossy();
+    Some(project_path.with_file_name(format!("{}.backups", stem)))
+}
+
+/// バックアップのファイル名の先頭と拡張子
+fn name_parts(project_path: &Path) -> (String, String) {
+    let stem = project_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = project_path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (format!("{}-", stem), extension)
+}
+
+/// UNIXエポックからのmsを `YYYYMMDD-HHMMSS-mmm`（UTC）に
+fn timestamp(ms: u64) -> String {
+    let secs = ms / 1000;
+    let days = (secs / 86_400) as i64;
+    let time = secs % 86_400;
+
+    // 1970-01-01からの日数を年月日に変換
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}-{:03}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        ms % 1000
+    )
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// プロジェクトのバックアップ一覧（新しい順）
+pub fn list(project_path: &Path) -> AppResult<Vec<BackupInfo>> {
+    let Some(dir) = backup_dir(project_path) else {
+        return Ok(Vec::new());
+    };
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let (prefix, extension) = name_parts(project_path);
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if !file_name.starts_with(&prefix) || !file_name.ends_with(&extension) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified_at_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        backups.push(BackupInfo {
+            path: entry.path().to_string_lossy().into_owned(),
+            file_name,
+            modified_at_ms,
+            size: metadata.len(),
+        });
+    }
+
+    // 日時入りのファイル名なので名前順が作成順
+    backups.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+    Ok(backups)
+}
+
+/// 上書きする前のファイルをバックアップし、古いものを削除して `keep` 個だけ残す
+///
+/// 元のファイルがない場合（新規保存）と `keep` が0の場合は何もしない。
+pub fn create(project_path: &Path, keep: usize) -> AppResult<Option<PathBuf>> {
+    if keep == 0 || !project_path.is_file() {
+        return Ok(None);
+    }
+    let dir = backup_dir(project_path).ok_or_else(|| {
+        AppError::Project(format!("Invalid project path: {}", project_path.display()))
+    })?;
+    std::fs::create_dir_all(&dir)?;
+
+    let (prefix, extension) = name_parts(project_path);
+    let backup = dir.join(format!("{}{}{}", prefix, timestamp(now_ms()), extension));
+    std::fs::copy(project_path, &backup)?;
+    debug!(
+        "[Backup] {} -> {}",
+        project_path.display(),
+        backup.display()
+    );
+
+    for old in list(project_path)?.into_iter().skip(keep) {
+        if let Err(e) = std::fs::remove_file(&old.path) {
+            warn!("[Backup] Failed to remove {}: {}", old.path, e);
+        }
+    }
+
+    Ok(Some(backup))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0), "19700101-000000-000");
+        // 2024-02-29 23:59:58.123 UTC
+        assert_eq!(timestamp(1_709_251_198_123), "20240229-235958-123");
+    }
+
+    #[test]
+    fn test_rotation() {
+        let dir = std::env::temp_dir().join(format!("backup-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let project = dir.join("show.json");
+
+        // 新規保存ではバックアップしない
+        assert_eq!(create(&project, 3).unwrap(), None);
+
+        for i in 0..5 {
+            std::fs::write(&project, format!("{}", i)).unwrap();
+            assert!(create(&project, 3).unwrap().is_some());
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        // 別プロジェクトのファイルは対象外
+        std::fs::write(dir.join("show.backups").join("other-1.json"), "").unwrap();
+
+        let backups = list(&project).unwrap();
+        let contents: Vec<String> = backups
+            .iter()
+            .map(|b| std::fs::read_to_string(&b.path).unwrap())
+            .collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(contents, vec!["4", "3", "2"]);
+        assert!(backups[0].file_name.starts_with("show-"));
+    }
+}
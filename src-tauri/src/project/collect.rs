@@ -0,0 +1,584 @@
+//! ショーの収集
+//!
+//! プロジェクトが参照するメディアを収集先フォルダの `media/` にコピーし、
+//! パスを収集先フォルダからの相対パスに書き換えたプロジェクトと
+//! SHA-256のマニフェスト（`sha256sum -c` で検証できる形式）を一緒に書き出す。
+//! 収集先フォルダを別のPCのメディアルート（local_config）にすればそのまま再生できる。
+//!
+//! メディアルートからの相対パスのメディアはフォルダ構成を保ち、
+//! 絶対パスのメディアはファイル名だけにする（同名の別ファイルは番号を付ける）。
+//! 収集先に同じ内容のファイルがあればコピーしない。
+
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+use zip::write::SimpleFileOptions;
+
+use crate::error::{AppError, AppResult};
+use crate::local_config;
+use crate::types::{CollectMode, CollectProgress, CollectResult, Project};
+
+/// メディアを置くフォルダ
+const MEDIA_DIR: &str = "media";
+
+/// マニフェストのファイル名
+pub const MANIFEST_FILE: &str = "manifest.sha256";
+
+const BUFFER_SIZE: usize = 1 << 20;
+
+/// ファイルの途中でも進捗を通知する間隔
+const PROGRESS_BYTES: u64 = 64 << 20;
+
+/// 進捗の通知先
+pub type Progress<'a> = &'a mut dyn FnMut(CollectProgress);
+
+/// コピーするメディア
+struct Source {
+    path: PathBuf,
+    /// 収集先での相対パス（`/` 区切り）
+    target: String,
+    size: u64,
+}
+
+/// プロジェクト名をファイル名に使える形に
+pub fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim().trim_end_matches('.');
+    if stem.is_empty() {
+        "project".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// 既に使われている名前と重ならないパス（大文字小文字は区別しない）
+fn unique_target(file_name: &str, taken: &mut HashSet<String>) -> String {
+    let path = Path::new(file_name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut target = format!("{}/{}", MEDIA_DIR, file_name);
+    let mut n = 2;
+    while !taken.insert(target.to_lowercase()) {
+        target = format!("{}/{}-{}{}", MEDIA_DIR, stem, n, extension);
+        n += 1;
+    }
+    target
+}
+
+/// 収集するメディアと、アイテムのパス → 収集先での相対パスの対応を決める
+///
+/// 見つからないメディアがあればエラーにする。
+fn plan(
+    project: &Project,
+    media_root: Option<&str>,
+) -> AppResult<(Vec<Source>, HashMap<String, String>)> {
+    let mut paths: Vec<&str> = Vec::new();
+    for item in project.cues.iter().flat_map(|c| &c.items) {
+        if !paths.contains(&item.path.as_str()) {
+            paths.push(&item.path);
+        }
+    }
+
+    let mut missing = Vec::new();
+    let mut resolved = Vec::new();
+    for path in paths {
+        let source = local_config::resolve_media_path(path, media_root);
+        match std::fs::metadata(&source) {
+            Ok(metadata) if metadata.is_file() => resolved.push((path, source, metadata.len())),
+            _ => missing.push(source.to_string_lossy().into_owned()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "{} media file(s) not found: {}",
+            missing.len(),
+            missing.join(", ")
+        )));
+    }
+
+    // 相対パスのメディアを先に割り当て、ファイル名だけのメディアは重ならない名前にする
+    let keep_structure = |path: &str| {
+        !local_config::is_absolute(path) && !path.split(['/', '\\']).any(|part| part == "..")
+    };
+    resolved.sort_by_key(|(path, _, _)| !keep_structure(path));
+
+    let mut sources: Vec<Source> = Vec::new();
+    let mut targets = HashMap::new();
+    let mut taken = HashSet::new();
+    for (path, source, size) in resolved {
+        // 別の書き方で同じファイルを参照している
+        if let Some(existing) = sources.iter().find(|s| s.path == source) {
+            targets.insert(path.to_string(), existing.target.clone());
+            continue;
+        }
+
+        let target = if keep_structure(path) {
+            let parts: Vec<&str> = path
+                .split(['/', '\\'])
+                .filter(|part| !part.is_empty() && *part != ".")
+                .collect();
+            let target = format!("{}/{}", MEDIA_DIR, parts.join("/"));
+            taken.insert(target.to_lowercase());
+            target
+        } else {
+            let file_name = source
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "media".to_string());
+            unique_target(&file_name, &mut taken)
+        };
+
+        targets.insert(path.to_string(), target.clone());
+        sources.push(Source {
+            path: source,
+            target,
+            size,
+        });
+    }
+
+    Ok((sources, targets))
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `reader` を `writer` にコピーしながらSHA-256を計算
+fn copy_hashed(
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+    on_bytes: &mut dyn FnMut(u64),
+) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        writer.write_all(&buffer[..n])?;
+        on_bytes(n as u64);
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+fn hash_file(path: &Path, on_bytes: &mut dyn FnMut(u64)) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    copy_hashed(&mut file, &mut std::io::sink(), on_bytes)
+}
+
+/// ファイルのSHA-256（16進小文字）
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    hash_file(path, &mut |_| {})
+}
+
+/// 収集先にメディアをコピー。同じ内容のファイルがあればコピーせず、ハッシュとコピーしたかを返す
+fn copy_to_folder(
+    source: &Source,
+    destination: &Path,
+    on_bytes: &mut dyn FnMut(u64),
+) -> AppResult<(String, bool)> {
+    let target = source
+        .target
+        .split('/')
+        .fold(destination.to_path_buf(), |path, part| path.join(part));
+
+    // 照合のための読み込みは進捗に数えず、コピーしなかった場合にまとめて進める
+    if std::fs::metadata(&target).is_ok_and(|m| m.is_file() && m.len() == source.size) {
+        let hash = sha256_file(&source.path)?;
+        if sha256_file(&target)? == hash {
+            debug!("[Collect] Unchanged: {}", source.target);
+            on_bytes(source.size);
+            return Ok((hash, false));
+        }
+    }
+
+    let dir = target.parent().unwrap_or(destination);
+    std::fs::create_dir_all(dir)?;
+    let temp = dir.join(format!(".collect.{}.tmp", uuid::Uuid::new_v4().simple()));
+    let result = (|| -> std::io::Result<String> {
+        let mut reader = std::fs::File::open(&source.path)?;
+        let mut writer = std::fs::File::create(&temp)?;
+        let hash = copy_hashed(&mut reader, &mut writer, on_bytes)?;
+        writer.sync_all()?;
+        drop(writer);
+        std::fs::rename(&temp, &target)?;
+        Ok(hash)
+    })();
+    match result {
+        Ok(hash) => Ok((hash, true)),
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp);
+            Err(e.into())
+        }
+    }
+}
+
+/// マニフェストの内容（ファイル名順）
+fn manifest(hashes: &mut [(String, String)]) -> String {
+    hashes.sort();
+    hashes
+        .iter()
+        .map(|(name, hash)| format!("{}  {}\n", hash, name))
+        .collect()
+}
+
+/// 書き出し先（アーカイブ）
+enum Sink {
+    Folder,
+    Archive(zip::ZipWriter<std::fs::File>),
+}
+
+/// プロジェクトのメディアを収集先フォルダに集め、プロジェクトとマニフェストを書き出す
+///
+/// `stem` は書き出すプロジェクトファイル（またはアーカイブ）の拡張子を除いた名前。
+/// 現在のプロジェクトは変更しない。
+pub fn collect(
+    project: &Project,
+    media_root: Option<&str>,
+    destination: &Path,
+    stem: &str,
+    mode: CollectMode,
+    progress: Progress,
+) -> AppResult<CollectResult> {
+    let (sources, targets) = plan(project, media_root)?;
+    std::fs::create_dir_all(destination)?;
+
+    let files_total = sources.len();
+    let bytes_total: u64 = sources.iter().map(|s| s.size).sum();
+    let mut bytes_done = 0;
+    info!(
+        "[Collect] Collecting {} media file(s) ({} bytes) to {}",
+        files_total,
+        bytes_total,
+        destination.display()
+    );
+
+    let project_name = format!("{}.json", stem);
+    let (path, temp_archive) = match mode {
+        CollectMode::Folder => (destination.join(&project_name), None),
+        CollectMode::Archive => {
+            let path = destination.join(format!("{}.zip", stem));
+            let temp = destination.join(format!(
+                ".{}.zip.{}.tmp",
+                stem,
+                uuid::Uuid::new_v4().simple()
+            ));
+            (path, Some(temp))
+        }
+    };
+    let mut sink = match &temp_archive {
+        Some(temp) => Sink::Archive(zip::ZipWriter::new(std::fs::File::create(temp)?)),
+        None => Sink::Folder,
+    };
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+
+    let result = (|| -> AppResult<CollectResult> {
+        let mut hashes = Vec::new();
+        let mut copied = 0;
+        let mut skipped = 0;
+
+        for (index, source) in sources.iter().enumerate() {
+            let mut report = |bytes_done: u64, files_done: usize| {
+                progress(CollectProgress {
+                    file: source.target.clone(),
+                    files_done,
+                    files_total,
+                    bytes_done,
+                    bytes_total,
+                })
+            };
+            report(bytes_done, index);
+
+            let mut reported = bytes_done;
+            let mut on_bytes = |n: u64| {
+                bytes_done += n;
+                if bytes_done - reported >= PROGRESS_BYTES {
+                    reported = bytes_done;
+                    report(bytes_done, index);
+                }
+            };
+
+            let hash = match &mut sink {
+                Sink::Folder => {
+                    let (hash, was_copied) = copy_to_folder(source, destination, &mut on_bytes)?;
+                    if was_copied {
+                        copied += 1;
+                    } else {
+                        skipped += 1;
+                    }
+                    hash
+                }
+                Sink::Archive(writer) => {
+                    writer
+                        .start_file(source.target.as_str(), options)
+                        .map_err(|e| AppError::File(e.to_string()))?;
+                    let mut reader = std::fs::File::open(&source.path)?;
+                    copied += 1;
+                    copy_hashed(&mut reader, writer, &mut on_bytes)?
+                }
+            };
+            hashes.push((source.target.clone(), hash));
+        }
+
+        // パスを収集先からの相対パスに書き換え、再リンク用にサイズとハッシュを記録
+        let mut collected = project.clone();
+        for item in collected.cues.iter_mut().flat_map(|c| &mut c.items) {
+            let Some(target) = targets.get(&item.path) else {
+                continue;
+            };
+            if let Some((source, (_, hash))) = sources
+                .iter()
+                .zip(&hashes)
+                .find(|(source, _)| &source.target == target)
+            {
+                item.size = Some(source.size);
+                item.sha256 = Some(hash.clone());
+            }
+            item.path = target.clone();
+        }
+        let content = serde_json::to_string_pretty(&collected)?;
+        hashes.push((
+            project_name.clone(),
+            hex(&Sha256::digest(content.as_bytes())),
+        ));
+        let manifest = manifest(&mut hashes);
+
+        match &mut sink {
+            Sink::Folder => {
+                super::write_atomic(&path, content.as_bytes())?;
+                super::write_atomic(&destination.join(MANIFEST_FILE), manifest.as_bytes())?;
+            }
+            Sink::Archive(writer) => {
+                let files = [
+                    (project_name.as_str(), content.as_str()),
+                    (MANIFEST_FILE, manifest.as_str()),
+                ];
+                for (name, data) in files {
+                    writer
+                        .start_file(name, options)
+                        .map_err(|e| AppError::File(e.to_string()))?;
+                    writer.write_all(data.as_bytes())?;
+                }
+            }
+        }
+
+        Ok(CollectResult {
+            path: path.to_string_lossy().into_owned(),
+            copied,
+            skipped,
+            bytes: bytes_total,
+        })
+    })();
+
+    // アーカイブは書き終えてから置き換える
+    let result = match (result, sink, &temp_archive) {
+        (Ok(result), Sink::Archive(writer), Some(temp)) => writer
+            .finish()
+            .map_err(|e| AppError::File(e.to_string()))
+            .and_then(|file| Ok(file.sync_all()?))
+            .and_then(|_| Ok(std::fs::rename(temp, &path)?))
+            .map(|_| result),
+        (result, _, _) => result,
+    };
+    if result.is_err() {
+        if let Some(temp) = &temp_archive {
+            let _ = std::fs::remove_file(temp);
+        }
+    }
+
+    if let Ok(result) = &result {
+        progress(CollectProgress {
+            file: String::new(),
+            files_done: files_total,
+            files_total,
+            bytes_done: bytes_total,
+            bytes_total,
+        });
+        info!(
+            "[Collect] Collected '{}' to {} ({} copied, {} unchanged)",
+            project.name, result.path, result.copied, result.skipped
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Cue, MediaItem, MediaType};
+
+    fn item(id: &str, path: &Path) -> MediaItem {
+        MediaItem {
+            id: id.to_string(),
+            media_type: MediaType::Video,
+            name: id.to_string(),
+            path: path.to_string_lossy().into_owned(),
+            output_id: "screen".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn project(items: Vec<MediaItem>) -> Project {
+        Project {
+            cues: vec![Cue {
+                id: "cue".to_string(),
+                name: "cue".to_string(),
+                items,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("Show: Night 1/2"), "Show_ Night 1_2");
+        assert_eq!(file_stem(" .. "), "project");
+    }
+
+    #[test]
+    fn test_copy_progress() {
+        let dir = std::env::temp_dir().join(format!("collect-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("show")).unwrap();
+        std::fs::write(dir.join("a.mp4"), b"new!").unwrap();
+        let source = Source {
+            path: dir.join("a.mp4"),
+            target: "a.mp4".to_string(),
+            size: 4,
+        };
+        let copy = || {
+            let mut bytes = 0;
+            let (_, copied) =
+                copy_to_folder(&source, &dir.join("show"), &mut |n| bytes += n).unwrap();
+            (copied, bytes)
+        };
+
+        assert_eq!(copy(), (true, 4));
+        assert_eq!(copy(), (false, 4));
+        // サイズが同じで内容が違う場合も照合の読み込みは数えない
+        std::fs::write(dir.join("show").join("a.mp4"), b"old!").unwrap();
+        assert_eq!(copy(), (true, 4));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_collect_folder() {
+        let dir = std::env::temp_dir().join(format!("collect-{}", uuid::Uuid::new_v4()));
+        let root = dir.join("root");
+        std::fs::create_dir_all(root.join("M01")).unwrap();
+        std::fs::write(root.join("M01").join("intro.mp4"), b"intro").unwrap();
+        std::fs::write(root.join("loop.mp4"), b"root loop").unwrap();
+        std::fs::write(dir.join("loop.mp4"), b"loop").unwrap();
+
+        let mut intro = item("b", Path::new(""));
+        intro.path = "M01\\intro.mp4".to_string();
+        let mut project = project(vec![
+            item("a", &dir.join("loop.mp4")),
+            intro,
+            item("c", Path::new("loop.mp4")),
+            // 同じファイルの別の書き方
+            item("d", &dir.join(".").join("loop.mp4")),
+            item("e", &dir.join("gone.mp4")),
+        ]);
+        let root = root.to_string_lossy().into_owned();
+        let show = dir.join("show");
+        let run = |project: &Project, mode| {
+            let mut events = Vec::new();
+            let result = collect(project, Some(&root), &show, "Show", mode, &mut |p| {
+                events.push(p)
+            });
+            result.map(|result| (result, events))
+        };
+
+        // 見つからないメディアがあれば何もコピーしない
+        assert!(matches!(
+            run(&project, CollectMode::Folder),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(!show.exists());
+
+        project.cues[0].items.pop();
+        let (result, events) = run(&project, CollectMode::Folder).unwrap();
+        assert_eq!((result.copied, result.skipped, result.bytes), (3, 0, 18));
+        assert_eq!(events.last().unwrap().files_done, 3);
+
+        let collected = crate::project::read(&show.join("Show.json")).unwrap();
+        let paths: Vec<&str> = collected.cues[0]
+            .items
+            .iter()
+            .map(|i| i.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "media/loop-2.mp4",
+                "media/M01/intro.mp4",
+                "media/loop.mp4",
+                "media/loop-2.mp4",
+            ]
+        );
+        assert_eq!(
+            std::fs::read(show.join("media").join("loop-2.mp4")).unwrap(),
+            b"loop"
+        );
+        let recorded = &collected.cues[0].items[0];
+        assert_eq!(recorded.size, Some(4));
+        assert_eq!(
+            recorded.sha256,
+            Some(sha256_file(&show.join("media").join("loop-2.mp4")).unwrap())
+        );
+        let manifest = std::fs::read_to_string(show.join(MANIFEST_FILE)).unwrap();
+        assert_eq!(manifest.lines().count(), 4);
+        assert!(manifest.contains(&format!(
+            "{}  media/loop-2.mp4\n",
+            hex(&Sha256::digest(b"loop"))
+        )));
+
+        // 2回目は同じ内容のファイルをコピーしない
+        std::fs::write(dir.join("loop.mp4"), b"LOOP").unwrap();
+        let (result, _) = run(&project, CollectMode::Folder).unwrap();
+        assert_eq!((result.copied, result.skipped), (1, 2));
+        assert_eq!(
+            std::fs::read(show.join("media").join("loop-2.mp4")).unwrap(),
+            b"LOOP"
+        );
+
+        let (result, _) = run(&project, CollectMode::Archive).unwrap();
+        let size = std::fs::metadata(&result.path).unwrap().len();
+        let temp_files = std::fs::read_dir(&show)
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(".tmp")
+            })
+            .count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.path.ends_with("Show.zip"));
+        assert!(size > 18);
+        assert_eq!(temp_files, 0);
+    }
+}
@@ -0,0 +1,549 @@
+//! プロジェクトの部分的な編集
+//!
+//! Cue・メディアアイテム・出力をIDで指定して追加・変更・削除する。
+//! プロジェクト全体を送り直さないため、同時に行われる明るさの変更や
+//! 別の端末からの編集を上書きしない。
+//!
+//! 各操作は検証に失敗するとエラーを返し、呼び出し側（`project::edit`）は
+//! プロジェクトを変更しない。IDが空のCue・アイテム・出力には新しいIDを振る。
+
+use std::collections::HashSet;
+
+use crate::error::{AppError, AppResult};
+use crate::types::{Cue, MediaItem, MediaType, OutputTarget, OutputType, Project};
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn cue_index(project: &Project, cue_id: &str) -> AppResult<usize> {
+    project
+        .cues
+        .iter()
+        .position(|c| c.id == cue_id)
+        .ok_or_else(|| AppError::NotFound(format!("Cue not found: {}", cue_id)))
+}
+
+fn output_index(project: &Project, output_id: &str) -> AppResult<usize> {
+    project
+        .outputs
+        .iter()
+        .position(|o| o.id == output_id)
+        .ok_or_else(|| AppError::NotFound(format!("Output not found: {}", output_id)))
+}
+
+/// 挿入位置（省略時は末尾）
+fn insert_index(index: Option<usize>, len: usize) -> AppResult<usize> {
+    match index {
+        Some(index) if index > len => Err(AppError::Project(format!(
+            "Index {} is out of range (0-{})",
+            index, len
+        ))),
+        Some(index) => Ok(index),
+        None => Ok(len),
+    }
+}
+
+/// 時間の値（秒）は0以上の有限値
+fn check_seconds(what: &str, value: Option<f64>) -> AppResult<()> {
+    match value {
+        Some(value) if !value.is_finite() || value < 0.0 => {
+            Err(AppError::Project(format!("Invalid {}: {}", what, value)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// メディアと出力先の種類の不一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoutingMismatch {
+    /// 映像を音声出力に割り当てた（再生されない）
+    VideoToAudio,
+    /// 音声を映像出力に割り当てた（既定のデバイスで再生される）
+    AudioToVideo,
+}
+
+/// メディアを出力先に割り当てたときの種類の不一致（編集と検証で共通）
+pub fn routing_mismatch(
+    media_type: &MediaType,
+    output_type: &OutputType,
+) -> Option<RoutingMismatch> {
+    match (media_type, *output_type == OutputType::Audio) {
+        (MediaType::Video, true) => Some(RoutingMismatch::VideoToAudio),
+        (MediaType::Audio, false) => Some(RoutingMismatch::AudioToVideo),
+        _ => None,
+    }
+}
+
+/// アイテムの出力先・時間を検証
+///
+/// 映像を音声出力に割り当てると再生されないためエラーにする
+/// （音声を映像出力に割り当てた場合は既定のデバイスで再生されるので許可）。
+fn check_item(project: &Project, item: &MediaItem) -> AppResult<()> {
+    if item.path.trim().is_empty() {
+        return Err(AppError::Project(format!(
+            "Media item '{}' has no path",
+            item.name
+        )));
+    }
+    let output = &project.outputs[output_index(project, &item.output_id)?];
+    if routing_mismatch(&item.media_type, &output.output_type)
+        == Some(RoutingMismatch::VideoToAudio)
+    {
+        return Err(AppError::Project(format!(
+            "Video '{}' cannot be routed to audio output '{}'",
+            item.name, output.name
+        )));
+    }
+
+    check_seconds("offset", item.offset)?;
+    check_seconds("trim start", item.trim_start)?;
+    check_seconds("trim end", item.trim_end)?;
+    if let (Some(start), Some(end)) = (item.trim_start, item.trim_end) {
+        if end <= start {
+            return Err(AppError::Project(format!(
+                "Trim end ({}) must be after trim start ({})",
+                end, start
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Cueの内容を検証。`replacing` は置き換える既存のCue（ID重複の判定から除く）
+fn check_cue(project: &Project, cue: &Cue, replacing: Option<usize>) -> AppResult<()> {
+    check_seconds("duration", Some(cue.duration))?;
+
+    let others = || {
+        project
+            .cues
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| Some(*i) != replacing)
+            .map(|(_, c)| c)
+    };
+    if others().any(|c| c.id == cue.id) {
+        return Err(AppError::Project(format!("Duplicate cue id: {}", cue.id)));
+    }
+
+    let existing: HashSet<&str> = others()
+        .flat_map(|c| c.items.iter().map(|i| i.id.as_str()))
+        .collect();
+    let mut seen = HashSet::new();
+    for item in &cue.items {
+        if existing.contains(item.id.as_str()) || !seen.insert(item.id.as_str()) {
+            return Err(AppError::Project(format!(
+                "Duplicate media item id: {}",
+                item.id
+            )));
+        }
+        check_item(project, item)?;
+    }
+    Ok(())
+}
+
+fn assign_ids(cue: &mut Cue) {
+    if cue.id.is_empty() {
+        cue.id = new_id();
+    }
+    for item in &mut cue.items {
+        if item.id.is_empty() {
+            item.id = new_id();
+        }
+    }
+}
+
+/// Cueを追加（indexを省略すると末尾）し、追加したCueを返す
+pub fn add_cue(project: &mut Project, mut cue: Cue, index: Option<usize>) -> AppResult<Cue> {
+    let index = insert_index(index, project.cues.len())?;
+    assign_ids(&mut cue);
+    check_cue(project, &cue, None)?;
+
+    project.cues.insert(index, cue.clone());
+    Ok(cue)
+}
+
+/// 同じIDのCueを置き換える
+pub fn update_cue(project: &mut Project, mut cue: Cue) -> AppResult<Cue> {
+    let index = cue_index(project, &cue.id)?;
+    assign_ids(&mut cue);
+    check_cue(project, &cue, Some(index))?;
+
+    project.cues[index] = cue.clone();
+    Ok(cue)
+}
+
+/// Cueを削除し、削除したCueを返す
+pub fn remove_cue(project: &mut Project, cue_id: &str) -> AppResult<Cue> {
+    let index = cue_index(project, cue_id)?;
+    Ok(project.cues.remove(index))
+}
+
+/// Cueを指定位置（移動後の0始まりのインデックス）へ移動
+pub fn move_cue(project: &mut Project, cue_id: &str, to_index: usize) -> AppResult<()> {
+    let from = cue_index(project, cue_id)?;
+    if to_index >= project.cues.len() {
+        return Err(AppError::Project(format!(
+            "Index {} is out of range (0-{})",
+            to_index,
+            project.cues.len() - 1
+        )));
+    }
+
+    let cue = project.cues.remove(from);
+    project.cues.insert(to_index, cue);
+    Ok(())
+}
+
+/// Cueを複製して直後に挿入し、複製したCueを返す
+///
+/// Cue・アイテムには新しいIDを振る。
+pub fn duplicate_cue(project: &mut Project, cue_id: &str) -> AppResult<Cue> {
+    let index = cue_index(project, cue_id)?;
+
+    let mut cue = project.cues[index].clone();
+    cue.id = new_id();
+    cue.name = format!("{} (copy)", cue.name);
+    for item in &mut cue.items {
+        item.id = new_id();
+    }
+
+    project.cues.insert(index + 1, cue.clone());
+    Ok(cue)
+}
+
+fn check_item_id(project: &Project, item_id: &str) -> AppResult<()> {
+    let duplicate = project
+        .cues
+        .iter()
+        .flat_map(|c| &c.items)
+        .any(|i| i.id == item_id);
+    if duplicate {
+        return Err(AppError::Project(format!(
+            "Duplicate media item id: {}",
+            item_id
+        )));
+    }
+    Ok(())
+}
+
+/// Cueにメディアアイテムを追加（indexを省略すると末尾）し、追加したアイテムを返す
+pub fn add_media_item(
+    project: &mut Project,
+    cue_id: &str,
+    mut item: MediaItem,
+    index: Option<usize>,
+) -> AppResult<MediaItem> {
+    let cue = cue_index(project, cue_id)?;
+    let index = insert_index(index, project.cues[cue].items.len())?;
+    if item.id.is_empty() {
+        item.id = new_id();
+    }
+    check_item_id(project, &item.id)?;
+    check_item(project, &item)?;
+
+    project.cues[cue].items.insert(index, item.clone());
+    Ok(item)
+}
+
+/// Cue内の同じIDのメディアアイテムを置き換える
+///
+/// パスが変わった場合、記録されたSHA-256は元のファイルのものなので破棄する。
+pub fn update_media_item(
+    project: &mut Project,
+    cue_id: &str,
+    mut item: MediaItem,
+) -> AppResult<MediaItem> {
+    let cue = cue_index(project, cue_id)?;
+    let index = project.cues[cue]
+        .items
+        .iter()
+        .position(|i| i.id == item.id)
+        .ok_or_else(|| AppError::NotFound(format!("Media item not found: {}", item.id)))?;
+    check_item(project, &item)?;
+    if project.cues[cue].items[index].path != item.path {
+        item.sha256 = None;
+    }
+
+    project.cues[cue].items[index] = item.clone();
+    Ok(item)
+}
+
+/// Cueからメディアアイテムを削除し、削除したアイテムを返す
+pub fn remove_media_item(
+    project: &mut Project,
+    cue_id: &str,
+    item_id: &str,
+) -> AppResult<MediaItem> {
+    let cue = cue_index(project, cue_id)?;
+    let items = &mut project.cues[cue].items;
+    let index = items
+        .iter()
+        .position(|i| i.id == item_id)
+        .ok_or_else(|| AppError::NotFound(format!("Media item not found: {}", item_id)))?;
+    Ok(items.remove(index))
+}
+
+/// 出力先を追加し、追加した出力先を返す
+pub fn add_output(project: &mut Project, mut output: OutputTarget) -> AppResult<OutputTarget> {
+    if output.id.is_empty() {
+        output.id = new_id();
+    }
+    if project.outputs.iter().any(|o| o.id == output.id) {
+        return Err(AppError::Project(format!(
+            "Duplicate output id: {}",
+            output.id
+        )));
+    }
+
+    project.outputs.push(output.clone());
+    Ok(output)
+}
+
+/// 同じIDの出力先を置き換える
+///
+/// 明るさは `set_output_brightness` で操作する値なので現在の値を残す。
+/// 割り当て済みの映像アイテムがある出力を音声出力には変更できない。
+pub fn update_output(project: &mut Project, mut output: OutputTarget) -> AppResult<OutputTarget> {
+    let index = output_index(project, &output.id)?;
+    output.brightness = project.outputs[index].brightness;
+
+    let video = project
+        .cues
+        .iter()
+        .flat_map(|c| &c.items)
+        .filter(|i| i.output_id == output.id)
+        .find(|i| {
+            routing_mismatch(&i.media_type, &output.output_type)
+                == Some(RoutingMismatch::VideoToAudio)
+        });
+    if let Some(item) = video {
+        return Err(AppError::Project(format!(
+            "Output '{}' has video '{}' routed to it and cannot become an audio output",
+            output.name, item.name
+        )));
+    }
+
+    project.outputs[index] = output.clone();
+    Ok(output)
+}
+
+/// 出力先を削除し、削除した出力先を返す
+///
+/// メディアアイテムが割り当てられている出力は削除できない。
+pub fn remove_output(project: &mut Project, output_id: &str) -> AppResult<OutputTarget> {
+    let index = output_index(project, output_id)?;
+    let used = project
+        .cues
+        .iter()
+        .flat_map(|c| &c.items)
+        .filter(|i| i.output_id == output_id)
+        .count();
+    if used > 0 {
+        return Err(AppError::Project(format!(
+            "Output '{}' is used by {} media item(s)",
+            project.outputs[index].name, used
+        )));
+    }
+
+    Ok(project.outputs.remove(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::history::follow_cue_index;
+
+    fn output(id: &str, output_type: OutputType) -> OutputTarget {
+        OutputTarget {
+            id: id.to_string(),
+            name: id.to_string(),
+            output_type,
+            ..Default::default()
+        }
+    }
+
+    fn item(id: &str, media_type: MediaType, output_id: &str) -> MediaItem {
+        MediaItem {
+            id: id.to_string(),
+            media_type,
+            name: id.to_string(),
+            path: format!("{}.mp4", id),
+            output_id: output_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn cue(id: &str, items: Vec<MediaItem>) -> Cue {
+        Cue {
+            id: id.to_string(),
+            name: id.to_string(),
+            items,
+            ..Default::default()
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            outputs: vec![
+                output("screen", OutputType::Display),
+                output("speakers", OutputType::Audio),
+            ],
+            cues: vec![
+                cue("a", vec![item("v1", MediaType::Video, "screen")]),
+                cue("b", Vec::new()),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn cue_ids(project: &Project) -> Vec<&str> {
+        project.cues.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_routing_mismatch() {
+        use RoutingMismatch::*;
+        let cases = [
+            (MediaType::Video, OutputType::Display, None),
+            (MediaType::Video, OutputType::Ndi, None),
+            (MediaType::Video, OutputType::Audio, Some(VideoToAudio)),
+            (MediaType::Audio, OutputType::Audio, None),
+            (MediaType::Audio, OutputType::Display, Some(AudioToVideo)),
+        ];
+        for (media_type, output_type, expected) in cases {
+            assert_eq!(routing_mismatch(&media_type, &output_type), expected);
+        }
+    }
+
+    #[test]
+    fn test_cues() {
+        let mut project = project();
+
+        let added = add_cue(&mut project, cue("", Vec::new()), Some(1)).unwrap();
+        assert!(!added.id.is_empty());
+        assert_eq!(project.cues[1].id, added.id);
+        assert!(add_cue(&mut project, cue("a", Vec::new()), None).is_err());
+        assert!(add_cue(&mut project, cue("c", Vec::new()), Some(9)).is_err());
+        // 別のCueのアイテムとIDが重複
+        let copy = cue("c", vec![item("v1", MediaType::Video, "screen")]);
+        assert!(add_cue(&mut project, copy, None).is_err());
+
+        remove_cue(&mut project, &added.id).unwrap();
+        move_cue(&mut project, "a", 1).unwrap();
+        assert_eq!(cue_ids(&project), vec!["b", "a"]);
+        assert!(move_cue(&mut project, "a", 2).is_err());
+
+        let copy = duplicate_cue(&mut project, "a").unwrap();
+        assert_eq!(project.cues[2].id, copy.id);
+        assert_eq!(copy.name, "a (copy)");
+        assert_ne!(copy.id, "a");
+        assert_ne!(copy.items[0].id, "v1");
+        assert_eq!(copy.items[0].path, "v1.mp4");
+
+        let mut renamed = project.cues[0].clone();
+        renamed.name = "renamed".to_string();
+        update_cue(&mut project, renamed).unwrap();
+        assert_eq!(project.cues[0].name, "renamed");
+        assert!(matches!(
+            remove_cue(&mut project, "missing"),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_follow_cue_index() {
+        let mut project = project();
+        let before = project.clone();
+
+        // 読み込み中の "a" を末尾に移動
+        move_cue(&mut project, "a", 1).unwrap();
+        assert_eq!(follow_cue_index(0, &before, &project), 1);
+        assert_eq!(follow_cue_index(1, &before, &project), 0);
+        assert_eq!(follow_cue_index(-1, &before, &project), -1);
+
+        let before = project.clone();
+        add_cue(&mut project, cue("c", Vec::new()), Some(0)).unwrap();
+        assert_eq!(follow_cue_index(1, &before, &project), 2);
+
+        let before = project.clone();
+        remove_cue(&mut project, "a").unwrap();
+        assert_eq!(follow_cue_index(2, &before, &project), 1);
+        assert_eq!(follow_cue_index(1, &before, &project), 1);
+    }
+
+    #[test]
+    fn test_follow_removed_cue() {
+        let mut project = project();
+        add_cue(&mut project, cue("c", Vec::new()), None).unwrap();
+
+        // 読み込み中の "b" を削除しても次のGOは後ろの "c" へ進む
+        let before = project.clone();
+        remove_cue(&mut project, "b").unwrap();
+        let index = follow_cue_index(1, &before, &project);
+        assert_eq!(index, 0);
+        assert_eq!(project.cues[(index + 1) as usize].id, "c");
+
+        // 末尾のCueを削除したら次のCueはない
+        let before = project.clone();
+        remove_cue(&mut project, "c").unwrap();
+        assert_eq!(follow_cue_index(1, &before, &project), 0);
+
+        // 先頭のCueを削除したら次のGOは新しい先頭から
+        let before = project.clone();
+        remove_cue(&mut project, "a").unwrap();
+        assert_eq!(follow_cue_index(0, &before, &project), -1);
+    }
+
+    #[test]
+    fn test_items_and_outputs() {
+        let mut project = project();
+
+        let added = add_media_item(
+            &mut project,
+            "b",
+            item("", MediaType::Audio, "speakers"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(project.cues[1].items[0].id, added.id);
+        // 存在しない出力・映像を音声出力へ・ID重複
+        for bad in [
+            item("x", MediaType::Video, "missing"),
+            item("x", MediaType::Video, "speakers"),
+            item("v1", MediaType::Video, "screen"),
+        ] {
+            assert!(add_media_item(&mut project, "b", bad, None).is_err());
+        }
+
+        let mut trimmed = project.cues[0].items[0].clone();
+        trimmed.trim_start = Some(5.0);
+        trimmed.trim_end = Some(2.0);
+        assert!(update_media_item(&mut project, "a", trimmed.clone()).is_err());
+        trimmed.trim_end = Some(8.0);
+        trimmed.sha256 = Some("hash".to_string());
+        update_media_item(&mut project, "a", trimmed.clone()).unwrap();
+        assert_eq!(project.cues[0].items[0].trim_end, Some(8.0));
+        assert!(project.cues[0].items[0].sha256.is_some());
+        trimmed.path = "other.mp4".to_string();
+        update_media_item(&mut project, "a", trimmed).unwrap();
+        assert_eq!(project.cues[0].items[0].sha256, None);
+
+        // 明るさは現在の値を残す
+        project.outputs[0].brightness = Some(40.0);
+        let mut screen = output("screen", OutputType::Display);
+        screen.name = "Main".to_string();
+        update_output(&mut project, screen).unwrap();
+        assert_eq!(project.outputs[0].name, "Main");
+        assert_eq!(project.outputs[0].brightness, Some(40.0));
+        assert!(update_output(&mut project, output("screen", OutputType::Audio)).is_err());
+
+        assert!(remove_output(&mut project, "screen").is_err());
+        remove_media_item(&mut project, "a", "v1").unwrap();
+        remove_output(&mut project, "screen").unwrap();
+        assert!(add_output(&mut project, output("speakers", OutputType::Audio)).is_err());
+        let added = add_output(&mut project, output("", OutputType::Ndi)).unwrap();
+        assert_eq!(project.outputs.len(), 2);
+        assert!(!added.id.is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+//! プロジェクトの編集履歴（元に戻す・やり直し）
+//!
+//! 編集の前のプロジェクト全体をスナップショットとして積む。
+//! 値の変更（フォームの連続入力等）が短い間隔で続いた場合は1つにまとめる。
+//! Cueの追加・削除のような構造の変更は、続けて行っても1つずつ元に戻せるようにまとめない。
+
+use std::collections::VecDeque;
+#[cfg(feature = "gui")]
+use std::time::{Duration, Instant};
+
+use crate::types::{HistoryStatus, Project};
+
+/// 同じラベルの編集をまとめる間隔
+#[cfg(feature = "gui")]
+const MERGE_WINDOW: Duration = Duration::from_secs(1);
+
+struct Entry {
+    label: String,
+    project: Project,
+    /// 最後にまとめた編集の時刻（Noneはまとめない）
+    #[cfg(feature = "gui")]
+    at: Option<Instant>,
+}
+
+/// 編集履歴
+#[derive(Default)]
+pub struct History {
+    undo: VecDeque<Entry>,
+    redo: Vec<Entry>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 履歴を破棄（プロジェクトの読み込み時等）
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// 編集を記録。`before` は編集前のプロジェクト
+    ///
+    /// `coalesce` が真なら、直前の同じラベルのまとめられる編集に続けてまとめる。
+    /// やり直しの履歴は破棄し、`depth` を超えた古い履歴から削除する。
+    /// `depth` が0なら記録しない。編集はTauriコマンドからのみ行う。
+    #[cfg(feature = "gui")]
+    pub fn record(&mut self, label: &str, before: Project, depth: usize, coalesce: bool) {
+        self.redo.clear();
+        if depth == 0 {
+            self.undo.clear();
+            return;
+        }
+
+        let now = Instant::now();
+        match self.undo.back_mut() {
+            // 直前と同じ操作の続きなら、最初の編集前の状態を残す
+            Some(last)
+                if coalesce
+                    && last.label == label
+                    && last
+                        .at
+                        .is_some_and(|at| now.duration_since(at) < MERGE_WINDOW) =>
+            {
+                last.at = Some(now);
+            }
+            _ => self.undo.push_back(Entry {
+                label: label.to_string(),
+                project: before,
+                at: coalesce.then_some(now),
+            }),
+        }
+
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// 直前の編集を取り消し、戻す先のプロジェクトとラベルを返す
+    pub fn undo(&mut self, current: Project) -> Option<(String, Project)> {
+        let entry = self.undo.pop_back()?;
+        self.redo.push(Entry {
+            label: entry.label.clone(),
+            project: current,
+            #[cfg(feature = "gui")]
+            at: None,
+        });
+        Some((entry.label, entry.project))
+    }
+
+    /// 取り消した編集をやり直し、やり直した後のプロジェクトとラベルを返す
+    pub fn redo(&mut self, current: Project) -> Option<(String, Project)> {
+        let entry = self.redo.pop()?;
+        // やり直した直後の編集とはまとめない
+        self.undo.push_back(Entry {
+            label: entry.label.clone(),
+            project: current,
+            #[cfg(feature = "gui")]
+            at: None,
+        });
+        Some((entry.label, entry.project))
+    }
+
+    pub fn status(&self) -> HistoryStatus {
+        HistoryStatus {
+            undo_label: self.undo.back().map(|e| e.label.clone()),
+            redo_label: self.redo.last().map(|e| e.label.clone()),
+            undo_count: self.undo.len(),
+            redo_count: self.redo.len(),
+        }
+    }
+}
+
+/// 編集後（元に戻した後）のプロジェクトで読み込み中のCueを指すインデックス
+///
+/// 追加・削除・並べ替えで位置が変わってもIDで追いかける。
+/// Cueが削除されていれば削除した位置の直前を返し、次のGOで
+/// 削除したCueの位置に詰められたCueへ進むようにする。
+pub fn follow_cue_index(index: i32, before: &Project, after: &Project) -> i32 {
+    let Some(cue) = usize::try_from(index).ok().and_then(|i| before.cues.get(i)) else {
+        return index;
+    };
+    match after.cues.iter().position(|c| c.id == cue.id) {
+        Some(i) => i as i32,
+        None => index.min(after.cues.len() as i32) - 1,
+    }
+}
+
+/// 戻したプロジェクトに現在のマスター・出力の明るさと音量を引き継ぐ
+///
+/// これらは再生中に操作卓やフェードで変わる値なので、編集履歴では戻さない。
+pub fn keep_levels(current: &Project, restored: &mut Project) {
+    restored.master_brightness = current.master_brightness;
+    restored.master_volume = current.master_volume;
+    for output in &mut restored.outputs {
+        if let Some(live) = current.outputs.iter().find(|o| o.id == output.id) {
+            output.brightness = live.brightness;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ProjectSettings, SyncConfig};
+
+    fn project(name: &str) -> Project {
+        Project {
+            schema_version: 1,
+            id: "p".to_string(),
+            name: name.to_string(),
+            master_brightness: 100.0,
+            master_volume: 100.0,
+            outputs: Vec::new(),
+            cues: Vec::new(),
+            settings: ProjectSettings::default(),
+            sync: SyncConfig::default(),
+            bindings: Vec::new(),
+            macros: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = History::new();
+        history.record("Rename", project("a"), 10, true);
+        history.record("Add cue", project("b"), 10, false);
+        assert_eq!(history.status().undo_count, 2);
+
+        let (label, restored) = history.undo(project("c")).unwrap();
+        assert_eq!((label.as_str(), restored.name.as_str()), ("Add cue", "b"));
+        let (_, restored) = history.undo(restored).unwrap();
+        assert_eq!(restored.name, "a");
+        assert!(history.undo(restored.clone()).is_none());
+
+        let (_, redone) = history.redo(restored).unwrap();
+        assert_eq!(redone.name, "b");
+        assert_eq!(history.status().redo_label.as_deref(), Some("Add cue"));
+
+        // 新しい編集でやり直しの履歴は消える
+        history.record("Rename", redone, 10, true);
+        assert_eq!(history.status().redo_count, 0);
+    }
+
+    #[test]
+    fn test_merge_and_depth() {
+        let mut history = History::new();
+        history.record("Update project", project("a"), 10, true);
+        history.record("Update project", project("b"), 10, true);
+        assert_eq!(history.status().undo_count, 1);
+        let (_, restored) = history.undo(project("c")).unwrap();
+        assert_eq!(restored.name, "a");
+
+        // 構造の変更は続けて行っても1つずつ戻す
+        let mut history = History::new();
+        history.record("Add cue", project("a"), 10, false);
+        history.record("Add cue", project("b"), 10, false);
+        assert_eq!(history.status().undo_count, 2);
+        assert_eq!(history.undo(project("c")).unwrap().1.name, "b");
+        assert_eq!(history.undo(project("b")).unwrap().1.name, "a");
+
+        // まとめない編集の後の値の変更もまとめない
+        let mut history = History::new();
+        history.record("Update cue", project("a"), 10, false);
+        history.record("Update cue", project("b"), 10, true);
+        assert_eq!(history.status().undo_count, 2);
+
+        let mut history = History::new();
+        for (i, label) in ["1", "2", "3", "4"].iter().enumerate() {
+            history.record(label, project(&i.to_string()), 2, false);
+        }
+        assert_eq!(history.status().undo_count, 2);
+        assert_eq!(history.undo(project("x")).unwrap().1.name, "3");
+        assert_eq!(history.undo(project("x")).unwrap().1.name, "2");
+    }
+}
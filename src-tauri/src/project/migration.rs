@@ -0,0 +1,162 @@
+//! プロジェクトファイルのスキーマ移行
+//!
+//! 読み込んだJSONを `schemaVersion` から現在のバージョンまで1段ずつ変換してから
+//! `Project` にデシリアライズする。バージョンの無いファイルはバージョン1として扱う。
+//! 形式を変える時は `CURRENT_SCHEMA_VERSION` を上げ、`MIGRATIONS` に
+//! 1つ前のバージョンからの変換を追加する。
+
+use serde_json::{Map, Value};
+
+use crate::error::{AppError, AppResult};
+
+/// このアプリが読み書きするスキーマバージョン
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// JSON上のバージョンのキー
+const VERSION_KEY: &str = "schemaVersion";
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[i]` はバージョン i+1 から i+2 への変換
+const MIGRATIONS: &[Migration] = &[v1_to_v2];
+
+/// ファイルのスキーマバージョン
+fn schema_version(project: &Map<String, Value>) -> AppResult<u32> {
+    match project.get(VERSION_KEY) {
+        None => Ok(1),
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| AppError::Project(format!("Invalid {}: {}", VERSION_KEY, value))),
+    }
+}
+
+/// 現在のバージョンまで移行し、元のバージョンを返す
+pub fn migrate(value: &mut Value) -> AppResult<u32> {
+    let project = value
+        .as_object_mut()
+        .ok_or_else(|| AppError::Project("Project file is not a JSON object".to_string()))?;
+
+    let version = schema_version(project)?;
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(AppError::Project(format!(
+            "Project file uses schema version {}, but this version of the app supports up to {}. \
+             Update the app to open it.",
+            version, CURRENT_SCHEMA_VERSION
+        )));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        migration(project);
+        project.insert(VERSION_KEY.to_string(), Value::from(index as u32 + 2));
+    }
+
+    Ok(version)
+}
+
+/// キーが無ければ値を入れる
+fn insert_missing(object: &mut Map<String, Value>, key: &str, value: impl Into<Value>) {
+    object.entry(key).or_insert_with(|| value.into());
+}
+
+/// バージョン1（バージョン無し）→ 2
+///
+/// 初期のファイルや手で書いたファイルで省略されがちな必須項目を補う。
+fn v1_to_v2(project: &mut Map<String, Value>) {
+    insert_missing(project, "masterBrightness", 100.0);
+    insert_missing(project, "outputs", Value::Array(Vec::new()));
+    insert_missing(project, "cues", Value::Array(Vec::new()));
+
+    // settingsは一部の項目だけ書かれていても読めるようにする
+    if let Some(settings) = project.get_mut("settings").and_then(Value::as_object_mut) {
+        insert_missing(settings, "defaultBrightness", 100.0);
+        insert_missing(settings, "autoSave", true);
+        insert_missing(settings, "previewQuality", "medium");
+    }
+
+    let Some(cues) = project.get_mut("cues").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for (index, cue) in cues.iter_mut().enumerate() {
+        let Some(cue) = cue.as_object_mut() else {
+            continue;
+        };
+        insert_missing(cue, "name", format!("Cue {}", index + 1));
+        insert_missing(cue, "items", Value::Array(Vec::new()));
+        insert_missing(cue, "duration", 0.0);
+        insert_missing(cue, "loop", false);
+        insert_missing(cue, "autoAdvance", false);
+
+        let Some(items) = cue.get_mut("items").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for item in items.iter_mut().filter_map(Value::as_object_mut) {
+            // 名前が無ければファイル名
+            let name = item
+                .get("path")
+                .and_then(Value::as_str)
+                .and_then(|path| path.rsplit(['/', '\\']).next())
+                .unwrap_or_default()
+                .to_string();
+            insert_missing(item, "name", name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Project;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_legacy_file() {
+        let mut value = json!({
+            "id": "p1",
+            "name": "Show",
+            "outputs": [],
+            "cues": [{
+                "id": "c1",
+                "items": [{
+                    "id": "i1",
+                    "type": "video",
+                    "path": "C:\\media\\intro.mp4",
+                    "outputId": "o1"
+                }]
+            }],
+            "settings": { "autoSave": false }
+        });
+
+        assert_eq!(migrate(&mut value).unwrap(), 1);
+        assert_eq!(value[VERSION_KEY], CURRENT_SCHEMA_VERSION);
+
+        let project: Project = serde_json::from_value(value).unwrap();
+        assert_eq!(project.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(project.master_brightness, 100.0);
+        assert_eq!(project.cues[0].name, "Cue 1");
+        assert_eq!(project.cues[0].items[0].name, "intro.mp4");
+        assert!(!project.settings.auto_save);
+        assert_eq!(project.settings.default_brightness, 100.0);
+    }
+
+    #[test]
+    fn test_current_file_is_unchanged() {
+        let project = Project::default();
+        let mut value = serde_json::to_value(&project).unwrap();
+        let before = value.clone();
+
+        assert_eq!(migrate(&mut value).unwrap(), CURRENT_SCHEMA_VERSION);
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn test_newer_file_is_rejected() {
+        let mut value = json!({ VERSION_KEY: CURRENT_SCHEMA_VERSION + 1 });
+        assert!(matches!(migrate(&mut value), Err(AppError::Project(_))));
+
+        let mut value = json!({ VERSION_KEY: "two" });
+        assert!(matches!(migrate(&mut value), Err(AppError::Project(_))));
+        assert!(migrate(&mut json!([])).is_err());
+    }
+}
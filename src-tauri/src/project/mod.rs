@@ -0,0 +1,262 @@
+//! プロジェクトファイルの読み込みと適用
+//!
+//! Tauriコマンドとヘッドレス実行から共通で使う。
+
+#[cfg(feature = "gui")]
+pub mod backup;
+#[cfg(feature = "gui")]
+pub mod collect;
+#[cfg(feature = "gui")]
+pub mod editing;
+pub mod history;
+pub mod migration;
+#[cfg(feature = "gui")]
+pub mod recovery;
+#[cfg(feature = "gui")]
+pub mod relink;
+#[cfg(feature = "gui")]
+pub mod validation;
+
+use std::io::Write;
+use std::path::Path;
+#[cfg(feature = "gui")]
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::dmx;
+use crate::error::{AppError, AppResult};
+use crate::http;
+use crate::local_config;
+use crate::midi;
+use crate::osc;
+use crate::state::AppState;
+use crate::types::{AppEvent, Project, ProjectChange};
+
+/// プロジェクトファイルを読み込み、古いスキーマなら現在のバージョンへ移行
+pub fn read(path: &Path) -> AppResult<Project> {
+    let content = std::fs::read_to_string(path)?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)?;
+
+    let version = migration::migrate(&mut value)?;
+    if version < migration::CURRENT_SCHEMA_VERSION {
+        info!(
+            "[Project] Migrated {} from schema version {} to {}",
+            path.display(),
+            version,
+            migration::CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| AppError::Project(format!("Invalid project file {}: {}", path.display(), e)))
+}
+
+/// 一時ファイルに書いてfsyncしてから置き換える
+///
+/// 書き込み途中で落ちたりディスクが一杯になっても元のファイルは壊れない。
+pub fn write_atomic(path: &Path, contents: &[u8]) -> AppResult<()> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| AppError::Project(format!("Invalid file path: {}", path.display())))?;
+    let temp = dir.join(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        uuid::Uuid::new_v4().simple()
+    ));
+
+    let result = (|| -> std::io::Result<()> {
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&temp, path)
+    })();
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e.into());
+    }
+
+    // 置き換え自体もディスクに反映（Windowsはフォルダを開けないため省略）
+    #[cfg(unix)]
+    if let Ok(dir) = std::fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// プロジェクトファイルを保存
+///
+/// 既存のファイルはバックアップしてから置き換え、作成したバックアップを返す。
+#[cfg(feature = "gui")]
+pub fn write(project: &Project, path: &Path) -> AppResult<Option<PathBuf>> {
+    let content = serde_json::to_string_pretty(project)?;
+    let backup = backup::create(path, project.settings.backup_count)?;
+    write_atomic(path, content.as_bytes())?;
+    info!("[Project] Saved '{}' to {}", project.name, path.display());
+    Ok(backup)
+}
+
+/// プロジェクトを現在のプロジェクトにし、設定で有効な外部制御を起動
+///
+/// 受信ポートはこのPCの設定で上書きする。起動に失敗したサービスは
+/// 警告を出して読み込みを続ける。
+pub fn activate(state: &AppState, project: Project) {
+    *state.project.lock() = Some(project.clone());
+    replaced(state);
+
+    let settings = local_config::apply_settings(&project.settings, &state.local_config.lock());
+    if settings.osc.enabled {
+        if let Err(e) = osc::restart(state, &settings.osc) {
+            tracing::warn!("[OSC] Failed to start server: {}", e);
+        }
+    }
+    if settings.http.enabled {
+        if let Err(e) = http::restart(state, &settings.http) {
+            tracing::warn!("[HTTP] Failed to start server: {}", e);
+        }
+    }
+    if settings.midi.enabled {
+        if let Err(e) = midi::restart(state, &settings.midi) {
+            tracing::warn!("[MIDI] Failed to start input: {}", e);
+        }
+    }
+    if settings.art_net.enabled {
+        if let Err(e) = dmx::restart_art_net(state, &settings.art_net) {
+            tracing::warn!("[ArtNet] Failed to start input: {}", e);
+        }
+    }
+    if settings.sacn.enabled {
+        if let Err(e) = dmx::restart_sacn(state, &settings.sacn) {
+            tracing::warn!("[sACN] Failed to start input: {}", e);
+        }
+    }
+    if settings.art_timecode_output.enabled {
+        if let Err(e) = dmx::restart_art_timecode_output(state, &settings.art_timecode_output) {
+            tracing::warn!("[ArtTimeCode] Failed to start output: {}", e);
+        }
+    }
+}
+
+/// プロジェクトが置き換わった（読み込み・新規作成等）。編集履歴を破棄して通知
+pub fn replaced(state: &AppState) {
+    let history = {
+        let mut history = state.history.lock();
+        history.clear();
+        history.status()
+    };
+    state.events.emit(AppEvent::ProjectChanged {
+        change: ProjectChange::Load,
+        label: None,
+        history,
+    });
+}
+
+/// 現在のプロジェクトを編集し、編集前の状態を編集履歴に記録
+///
+/// `f` がエラーを返した場合はプロジェクトを変更しない。
+/// Tauriコマンド・HTTP等どこからの編集もここを通すことで元に戻せる。
+/// 編集ごとに1つずつ元に戻す（値の連続した変更は `edit_coalesced`）。
+#[cfg(feature = "gui")]
+pub fn edit<T>(
+    state: &AppState,
+    label: &str,
+    f: impl FnOnce(&mut Project) -> AppResult<T>,
+) -> AppResult<T> {
+    record_edit(state, label, false, f)
+}
+
+/// 値の変更として編集（同じラベルの編集が短い間隔で続けば1つにまとめて元に戻す）
+///
+/// フォームから連続して送られるCue・アイテム・出力・プロジェクトの更新に使う。
+#[cfg(feature = "gui")]
+pub fn edit_coalesced<T>(
+    state: &AppState,
+    label: &str,
+    f: impl FnOnce(&mut Project) -> AppResult<T>,
+) -> AppResult<T> {
+    record_edit(state, label, true, f)
+}
+
+#[cfg(feature = "gui")]
+fn record_edit<T>(
+    state: &AppState,
+    label: &str,
+    coalesce: bool,
+    f: impl FnOnce(&mut Project) -> AppResult<T>,
+) -> AppResult<T> {
+    let (result, history) = {
+        let mut project_guard = state.project.lock();
+        let project = project_guard
+            .as_mut()
+            .ok_or_else(|| AppError::InvalidState("No project loaded".to_string()))?;
+
+        let mut edited = project.clone();
+        let result = f(&mut edited)?;
+        let depth = edited.settings.history_depth;
+        let before = std::mem::replace(project, edited);
+        follow_current_cue(state, &before, project);
+
+        let mut history = state.history.lock();
+        history.record(label, before, depth, coalesce);
+        (result, history.status())
+    };
+
+    tracing::debug!("[Project] Edit: {}", label);
+    state.events.emit(AppEvent::ProjectChanged {
+        change: ProjectChange::Edit,
+        label: Some(label.to_string()),
+        history,
+    });
+    Ok(result)
+}
+
+/// 編集・元に戻す・やり直しでCueの位置が変わっても読み込み中のCueを指し続ける
+fn follow_current_cue(state: &AppState, before: &Project, after: &Project) {
+    let mut current = state.current_cue_index.lock();
+    *current = history::follow_cue_index(*current, before, after);
+}
+
+/// 直前の編集を元に戻し、戻した後のプロジェクトを返す
+pub fn undo(state: &AppState) -> AppResult<Project> {
+    step_history(state, ProjectChange::Undo)
+}
+
+/// 元に戻した編集をやり直し、やり直した後のプロジェクトを返す
+pub fn redo(state: &AppState) -> AppResult<Project> {
+    step_history(state, ProjectChange::Redo)
+}
+
+fn step_history(state: &AppState, change: ProjectChange) -> AppResult<Project> {
+    let (label, project, history) = {
+        let mut project_guard = state.project.lock();
+        let current = project_guard
+            .as_mut()
+            .ok_or_else(|| AppError::InvalidState("No project loaded".to_string()))?;
+
+        let mut history = state.history.lock();
+        let (label, mut restored) = match change {
+            ProjectChange::Redo => history
+                .redo(current.clone())
+                .ok_or_else(|| AppError::InvalidState("Nothing to redo".to_string()))?,
+            _ => history
+                .undo(current.clone())
+                .ok_or_else(|| AppError::InvalidState("Nothing to undo".to_string()))?,
+        };
+        history::keep_levels(current, &mut restored);
+        follow_current_cue(state, current, &restored);
+        *current = restored.clone();
+        (label, restored, history.status())
+    };
+
+    info!("[Project] {:?}: {}", change, label);
+    state.events.emit(AppEvent::ProjectChanged {
+        change,
+        label: Some(label),
+        history,
+    });
+    Ok(project)
+}
@@ -0,0 +1,549 @@
+//! 自動保存と異常終了からの復旧
+//!
+//! プロジェクトの `settings.autoSave` が有効な間、変更があれば少し待ってから、
+//! 変更がなくても一定間隔で、プロジェクトと現在のCue・再生位置を
+//! リカバリーファイルに書き出す。リカバリーファイルはプロジェクトファイルの
+//! 隣の `<ファイル名>.recovery`（未保存のプロジェクトは設定フォルダ）。
+//!
+//! 起動中はプロセスごとに設定フォルダへセッションファイルを置いて排他ロックし、
+//! 正常終了時に削除する。起動時にロックされていないセッションファイルが
+//! 残っていればそのプロセスは異常終了したとみなし、最後に書き出した
+//! リカバリーファイルを `<リカバリーファイル>.prev` に移して復元候補にする
+//! （このセッションの自動保存で上書きされないように）。同じPCで起動中の
+//! 別のインスタンスのセッションとリカバリーファイルには触れない。
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+use super::migration;
+use crate::error::{AppError, AppResult};
+use crate::local_config;
+use crate::playback;
+use crate::state::AppState;
+use crate::types::{Project, RecoveryFile, RecoveryInfo};
+
+/// 変更の確認間隔
+const TICK: Duration = Duration::from_millis(500);
+
+/// 最後の変更からこの時間が経ったら書き出す
+const CHANGE_DELAY: Duration = Duration::from_secs(2);
+
+/// 変更がなくても書き出す間隔（再生位置の記録を兼ねる）
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 設定フォルダに置くセッションファイル（`session-<PID>.lock`、中身は最後のリカバリーファイルのパス）
+const SESSION_PREFIX: &str = "session-";
+const SESSION_EXTENSION: &str = "lock";
+
+/// ロックを取るまでのセッションファイルの拡張子（他のプロセスに異常終了と誤認されないように）
+const PENDING_EXTENSION: &str = "pending";
+
+/// 未保存のプロジェクトのリカバリーファイル名
+const UNTITLED_RECOVERY: &str = "untitled.recovery";
+
+/// 復元候補に移したリカバリーファイルの拡張子
+const PREVIOUS_SUFFIX: &str = ".prev";
+
+/// プロジェクトのリカバリーファイルのパス
+pub fn recovery_path(project_path: Option<&Path>) -> Option<PathBuf> {
+    match project_path {
+        Some(path) => {
+            let mut name = path.file_name()?.to_os_string();
+            name.push(".recovery");
+            Some(path.with_file_name(name))
+        }
+        None => Some(local_config::config_dir()?.join(UNTITLED_RECOVERY)),
+    }
+}
+
+/// このプロセスのセッション
+///
+/// 起動中はセッションファイルを排他ロックしておく。ロックされていない
+/// セッションファイルは異常終了したプロセスのものとみなす。
+pub struct Session {
+    path: PathBuf,
+    file: File,
+}
+
+impl Session {
+    fn create(dir: &Path, name: &str) -> AppResult<Self> {
+        std::fs::create_dir_all(dir)?;
+        let pending = dir.join(name).with_extension(PENDING_EXTENSION);
+        let path = dir.join(name).with_extension(SESSION_EXTENSION);
+
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&pending)?;
+        file.try_lock().map_err(|e| {
+            AppError::Project(format!(
+                "Failed to lock session file {}: {}",
+                pending.display(),
+                e
+            ))
+        })?;
+        // ロックしてから見える名前にする（ロックはファイルについたまま）
+        std::fs::rename(&pending, &path)?;
+        Ok(Self { path, file })
+    }
+
+    /// 書き出し先のリカバリーファイルを記録（ロックしたまま書き換える）
+    fn write(&mut self, recovery: Option<&Path>) -> AppResult<()> {
+        let content = recovery
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(content.as_bytes())?;
+        Ok(())
+    }
+
+    /// 正常終了。起動中の別のセッションが使っていなければリカバリーファイルを削除し、
+    /// セッションファイルを削除する
+    fn end(mut self, recovery: Option<&Path>) {
+        if let Some(recovery) = recovery {
+            let dir = self.path.parent().unwrap_or(Path::new("."));
+            if owned_by_live_session(dir, &self.path, recovery) {
+                debug!(
+                    "[Recovery] {} is used by another session, keeping it",
+                    recovery.display()
+                );
+            } else {
+                remove(recovery);
+            }
+        }
+        if let Err(e) = self.write(None) {
+            warn!("[Recovery] Failed to clear session file: {}", e);
+        }
+        let Self { path, file } = self;
+        drop(file);
+        remove(&path);
+    }
+}
+
+fn is_session_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == SESSION_EXTENSION)
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(SESSION_PREFIX))
+}
+
+fn session_files(dir: &Path) -> Vec<PathBuf> {
+    match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| is_session_file(path))
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// 起動中の別のセッション（`except` 以外でロックされているセッションファイル）が
+/// このリカバリーファイルに書き出しているか。中身を読めなければ使っているとみなす
+fn owned_by_live_session(dir: &Path, except: &Path, recovery: &Path) -> bool {
+    session_files(dir)
+        .into_iter()
+        .filter(|session| session != except)
+        .any(|session| {
+            let Ok(mut file) = File::open(&session) else {
+                return false;
+            };
+            if file.try_lock().is_ok() {
+                return false;
+            }
+            let mut content = String::new();
+            match file.read_to_string(&mut content) {
+                Ok(_) => Path::new(content.trim()) == recovery,
+                Err(_) => true,
+            }
+        })
+}
+
+/// リカバリーファイルを読み込み
+pub fn read(path: &Path) -> AppResult<RecoveryFile> {
+    let content = std::fs::read_to_string(path)?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)?;
+    if let Some(project) = value.get_mut("project") {
+        migration::migrate(project)?;
+    }
+    serde_json::from_value(value)
+        .map_err(|e| AppError::Project(format!("Invalid recovery file {}: {}", path.display(), e)))
+}
+
+fn write(path: &Path, file: &RecoveryFile) -> AppResult<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    super::write_atomic(path, serde_json::to_string_pretty(file)?.as_bytes())
+}
+
+/// リカバリーファイルを削除（なければ何もしない）
+pub fn remove(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => debug!("[Recovery] Removed {}", path.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!("[Recovery] Failed to remove {}: {}", path.display(), e),
+    }
+}
+
+fn info(path: &Path, file: &RecoveryFile) -> RecoveryInfo {
+    RecoveryInfo {
+        path: path.to_string_lossy().into_owned(),
+        project_name: file.project.name.clone(),
+        project_path: file.project_path.clone(),
+        cue_index: file.cue_index,
+        position: file.position,
+        saved_at_ms: file.saved_at_ms,
+    }
+}
+
+/// リカバリーファイルを復元候補として `.prev` に移す
+fn set_aside(path: &Path) -> AppResult<PathBuf> {
+    let mut name = path
+        .file_name()
+        .ok_or_else(|| AppError::Project(format!("Invalid recovery path: {}", path.display())))?
+        .to_os_string();
+    name.push(PREVIOUS_SUFFIX);
+    let previous = path.with_file_name(name);
+
+    // Windowsは既存のファイルに上書きできない
+    let _ = std::fs::remove_file(&previous);
+    std::fs::rename(path, &previous)?;
+    Ok(previous)
+}
+
+/// セッションを開始し、前回異常終了していれば復元できるリカバリーファイルを記録
+pub fn begin_session(state: &AppState) {
+    let Some(dir) = local_config::config_dir() else {
+        return;
+    };
+    let name = format!("{}{}", SESSION_PREFIX, std::process::id());
+    let (session, recovery) = begin_session_in(&dir, &name);
+    *state.session.lock() = session;
+    *state.recovery.lock() = recovery;
+}
+
+fn begin_session_in(dir: &Path, name: &str) -> (Option<Session>, Option<RecoveryInfo>) {
+    // 異常終了したセッションが複数あれば最後に書き出したものを復元候補にする
+    let recovery = session_files(dir)
+        .iter()
+        .filter_map(|session| recover_session(dir, session))
+        .max_by_key(|info| info.saved_at_ms);
+
+    let session = match Session::create(dir, name) {
+        Ok(session) => Some(session),
+        Err(e) => {
+            warn!("[Recovery] Failed to create session file: {}", e);
+            None
+        }
+    };
+    (session, recovery)
+}
+
+/// セッションファイルがロックされていなければ異常終了したとみなし、
+/// そのリカバリーファイルを復元候補に移してセッションファイルを削除
+fn recover_session(dir: &Path, session: &Path) -> Option<RecoveryInfo> {
+    let mut file = File::open(session).ok()?;
+    if file.try_lock().is_err() {
+        debug!("[Recovery] Session {} is still running", session.display());
+        return None;
+    }
+    let mut content = String::new();
+    if let Err(e) = file.read_to_string(&mut content) {
+        warn!("[Recovery] Failed to read {}: {}", session.display(), e);
+        return None;
+    }
+
+    let recovery = Some(PathBuf::from(content.trim()))
+        .filter(|path| !path.as_os_str().is_empty() && path.exists())
+        // 同じプロジェクトを開いている起動中のセッションの書き出し先なら移さない
+        .filter(|path| !owned_by_live_session(dir, session, path))
+        .and_then(|path| match set_aside(&path) {
+            Ok(previous) => Some(previous),
+            Err(e) => {
+                warn!("[Recovery] Failed to move {}: {}", path.display(), e);
+                None
+            }
+        })
+        .and_then(|path| match read(&path) {
+            Ok(file) => Some(info(&path, &file)),
+            Err(e) => {
+                warn!("[Recovery] Failed to read {}: {}", path.display(), e);
+                None
+            }
+        });
+    if let Some(recovery) = &recovery {
+        warn!(
+            "[Recovery] Previous session did not shut down cleanly, recovery file: {}",
+            recovery.path
+        );
+    }
+
+    drop(file);
+    remove(session);
+    recovery
+}
+
+/// 正常終了。自動保存を止め、リカバリーファイルとセッションファイルを削除
+pub fn end_session(state: &AppState) {
+    if let Some(mut auto_save) = state.auto_save.lock().take() {
+        auto_save.stop();
+    }
+    let recovery = recovery_path(state.project_path.lock().as_deref());
+    match state.session.lock().take() {
+        Some(session) => session.end(recovery.as_deref()),
+        None => {
+            if let Some(path) = recovery {
+                remove(&path);
+            }
+        }
+    }
+}
+
+/// リカバリーファイルからプロジェクトを復元し、Cueと再生位置を戻す
+pub fn restore(state: &AppState, path: &Path) -> AppResult<Project> {
+    let file = read(path)?;
+    info!(
+        "[Recovery] Restoring '{}' from {}",
+        file.project.name,
+        path.display()
+    );
+
+    super::activate(state, file.project.clone());
+    *state.project_path.lock() = file.project_path.as_ref().map(PathBuf::from);
+
+    // メディアが見つからない等でCueを読み込めなくてもプロジェクトは復元する
+    if let Some(cue_index) = file.cue_index {
+        let result = playback::load_cue(state, cue_index).and_then(|_| {
+            if file.position > 0.0 {
+                playback::seek(state, file.position)
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(e) = result {
+            warn!("[Recovery] Failed to restore cue {}: {}", cue_index + 1, e);
+        }
+    }
+
+    Ok(file.project)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 自動保存サービス
+pub struct AutoSave {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AutoSave {
+    pub fn start(state: AppState) -> AppResult<Self> {
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name("auto-save".to_string())
+                .spawn(move || Self::run(state, running))
+                .map_err(AppError::Io)?
+        };
+
+        info!("[Recovery] Auto-save started");
+
+        Ok(Self {
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run(state: AppState, running: Arc<AtomicBool>) {
+        let mut last_content: Option<String> = None;
+        let mut changed_at: Option<Instant> = None;
+        let mut last_write: Option<Instant> = None;
+        let mut session_target: Option<PathBuf> = None;
+
+        while running.load(Ordering::Relaxed) {
+            std::thread::sleep(TICK);
+
+            let Some(project) = state.project.lock().clone() else {
+                continue;
+            };
+            if !project.settings.auto_save {
+                continue;
+            }
+
+            let now = Instant::now();
+            match serde_json::to_string(&project) {
+                Ok(content) if last_content.as_ref() != Some(&content) => {
+                    last_content = Some(content);
+                    changed_at = Some(now);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("[Recovery] Failed to serialize project: {}", e);
+                    continue;
+                }
+            }
+
+            let due = changed_at.is_some_and(|t| now.duration_since(t) >= CHANGE_DELAY)
+                || last_write.is_none_or(|t| now.duration_since(t) >= AUTOSAVE_INTERVAL);
+            if !due {
+                continue;
+            }
+
+            let project_path = state.project_path.lock().clone();
+            let Some(path) = recovery_path(project_path.as_deref()) else {
+                continue;
+            };
+            let player = playback::player_state(&state);
+            let file = RecoveryFile {
+                project,
+                project_path: project_path.map(|p| p.to_string_lossy().into_owned()),
+                cue_index: usize::try_from(player.current_cue_index).ok(),
+                position: player.current_time,
+                saved_at_ms: now_ms(),
+            };
+
+            last_write = Some(now);
+            changed_at = None;
+            if let Err(e) = write(&path, &file) {
+                warn!("[Recovery] Failed to write {}: {}", path.display(), e);
+                continue;
+            }
+            debug!("[Recovery] Auto-saved to {}", path.display());
+
+            // 異常終了時に見つけられるよう、書き出し先をセッションファイルに記録
+            if session_target.as_ref() != Some(&path) {
+                if let Some(session) = state.session.lock().as_mut() {
+                    if let Err(e) = session.write(Some(&path)) {
+                        warn!("[Recovery] Failed to write session file: {}", e);
+                    }
+                }
+                session_target = Some(path);
+            }
+        }
+
+        debug!("[Recovery] Auto-save thread finished");
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[Recovery] Auto-save stopped");
+        }
+    }
+}
+
+impl Drop for AutoSave {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, project_path: &Path) -> RecoveryFile {
+        RecoveryFile {
+            project: Project {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            project_path: Some(project_path.to_string_lossy().into_owned()),
+            cue_index: Some(1),
+            position: 12.5,
+            saved_at_ms: 1,
+        }
+    }
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("recovery-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_crash_snapshot_survives_next_session() {
+        let dir = temp_dir();
+        let project_path = dir.join("show.json");
+        let recovery = recovery_path(Some(&project_path)).unwrap();
+
+        // 前回のセッションが書き出したまま異常終了（ロックが外れる）
+        let (crashed, none) = begin_session_in(&dir, "session-1");
+        assert!(none.is_none());
+        let mut crashed = crashed.unwrap();
+        write(&recovery, &file("crashed", &project_path)).unwrap();
+        crashed.write(Some(&recovery)).unwrap();
+        drop(crashed);
+
+        let (session, info) = begin_session_in(&dir, "session-2");
+        let info = info.unwrap();
+        assert!(info.path.ends_with(".recovery.prev"));
+        assert_eq!(info.project_name, "crashed");
+        assert!(!dir.join("session-1.lock").exists());
+
+        // 復元する前に同じプロジェクトを開いて自動保存が書き出しても残る
+        write(&recovery, &file("new session", &project_path)).unwrap();
+        let restored = read(Path::new(&info.path)).unwrap();
+        assert_eq!(restored.project.name, "crashed");
+        assert_eq!(restored.cue_index, Some(1));
+
+        // 正常終了の後は復元候補なし
+        session.unwrap().end(Some(&recovery));
+        assert!(!recovery.exists());
+        assert!(!dir.join("session-2.lock").exists());
+        let (_session, info) = begin_session_in(&dir, "session-3");
+        assert!(info.is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_sessions_do_not_take_over_each_other() {
+        let dir = temp_dir();
+        let project_path = dir.join("show.json");
+        let recovery = recovery_path(Some(&project_path)).unwrap();
+
+        // 1つ目のインスタンスが自動保存中
+        let (first, _) = begin_session_in(&dir, "session-1");
+        let mut first = first.unwrap();
+        write(&recovery, &file("first", &project_path)).unwrap();
+        first.write(Some(&recovery)).unwrap();
+
+        // 2つ目のインスタンスは起動中のセッションを異常終了とみなさない
+        let (second, info) = begin_session_in(&dir, "session-2");
+        assert!(info.is_none());
+        assert!(recovery.exists());
+        assert!(dir.join("session-1.lock").exists());
+
+        // 同じプロジェクトを開いた2つ目が正常終了しても1つ目のリカバリーファイルは残る
+        let mut second = second.unwrap();
+        second.write(Some(&recovery)).unwrap();
+        second.end(Some(&recovery));
+        assert!(recovery.exists());
+        assert!(!dir.join("session-2.lock").exists());
+
+        // 1つ目が異常終了すれば次の起動で復元候補になる
+        drop(first);
+        let (_third, info) = begin_session_in(&dir, "session-3");
+        assert_eq!(info.unwrap().project_name, "first");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,443 @@
+//! 見つからないメディアの再リンク
+//!
+//! 指定したフォルダ以下から、見つからないメディアと同じファイル名
+//! （大文字小文字は区別しない）のファイルを探して候補にする。
+//! 記録されたサイズ（アイテムの追加・変更・再リンク・収集時）と
+//! SHA-256（収集時）があれば一致するものだけを残し、
+//! 元のパスと親フォルダ名がより多く一致する候補を優先する。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use super::collect;
+use crate::error::{AppError, AppResult};
+use crate::local_config;
+use crate::types::{MediaItem, Project, Relink, RelinkCandidate, RelinkMatch, RelinkProposal};
+
+/// パスの区切り（`/` と `\`）で分けた空でない部分
+fn parts(path: &str) -> Vec<&str> {
+    path.split(['/', '\\'])
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn resolves(path: &str, media_root: Option<&str>) -> bool {
+    local_config::resolve_media_path(path, media_root).is_file()
+}
+
+/// 見つからないメディアのパスと、そのパスを使うアイテム（プロジェクト内の順）
+fn missing<'a>(
+    project: &'a Project,
+    media_root: Option<&str>,
+) -> Vec<(&'a str, Vec<&'a MediaItem>)> {
+    let mut missing: Vec<(&str, Vec<&MediaItem>)> = Vec::new();
+    for item in project.cues.iter().flat_map(|c| &c.items) {
+        if let Some((_, items)) = missing.iter_mut().find(|(path, _)| *path == item.path) {
+            items.push(item);
+        } else if !resolves(&item.path, media_root) {
+            missing.push((&item.path, vec![item]));
+        }
+    }
+    missing
+}
+
+/// フォルダ以下で `names`（小文字）と同じ名前のファイルを探す
+///
+/// 隠しフォルダとシンボリックリンクのフォルダは辿らない。
+fn scan(folders: &[PathBuf], names: &HashSet<String>) -> HashMap<String, Vec<(PathBuf, u64)>> {
+    let mut found: HashMap<String, Vec<(PathBuf, u64)>> = HashMap::new();
+    let mut stack: Vec<PathBuf> = folders.to_vec();
+    let mut visited = HashSet::new();
+
+    while let Some(dir) = stack.pop() {
+        if !visited.insert(dir.clone()) {
+            continue;
+        }
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("[Relink] Cannot read {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !name.starts_with('.') {
+                    stack.push(entry.path());
+                }
+                continue;
+            }
+
+            let key = name.to_lowercase();
+            if !names.contains(&key) {
+                continue;
+            }
+            // シンボリックリンクのファイルはリンク先のサイズ
+            match std::fs::metadata(entry.path()) {
+                Ok(metadata) if metadata.is_file() => {
+                    found
+                        .entry(key)
+                        .or_default()
+                        .push((entry.path(), metadata.len()));
+                }
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+/// 元のパスと候補で、ファイルの親フォルダ名が末尾から何個一致するか
+fn matching_folders(original: &str, candidate: &Path) -> usize {
+    let original = parts(original);
+    let candidate: Vec<String> = candidate
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+        .collect();
+
+    original
+        .iter()
+        .rev()
+        .skip(1)
+        .zip(candidate.iter().rev().skip(1))
+        .take_while(|(a, b)| a.to_lowercase() == **b)
+        .count()
+}
+
+/// プロジェクトに設定するパス（メディアルート内なら相対パス）
+fn project_path(path: &Path, media_root: Option<&str>) -> String {
+    let absolute = path.to_string_lossy().into_owned();
+    media_root
+        .and_then(|root| local_config::to_relative(&absolute, root))
+        .unwrap_or(absolute)
+}
+
+/// 見つからないメディアごとに再リンクの候補を探す
+///
+/// `match_hash` が有効なら、SHA-256が記録されているメディアの候補は
+/// 内容を読んで照合する（ファイルが大きいと時間がかかる）。
+pub fn propose(
+    project: &Project,
+    media_root: Option<&str>,
+    folders: &[PathBuf],
+    match_hash: bool,
+) -> AppResult<Vec<RelinkProposal>> {
+    if let Some(folder) = folders.iter().find(|f| !f.is_dir()) {
+        return Err(AppError::NotFound(format!(
+            "Search folder not found: {}",
+            folder.display()
+        )));
+    }
+
+    let missing = missing(project, media_root);
+    if missing.is_empty() {
+        return Ok(Vec::new());
+    }
+    let names: HashSet<String> = missing
+        .iter()
+        .filter_map(|(path, _)| parts(path).last().map(|name| name.to_lowercase()))
+        .collect();
+    let found = scan(folders, &names);
+    let mut hashes: HashMap<PathBuf, Option<String>> = HashMap::new();
+
+    let proposals: Vec<RelinkProposal> = missing
+        .into_iter()
+        .map(|(path, items)| {
+            let size = items.iter().find_map(|i| i.size);
+            let sha256 = items.iter().find_map(|i| i.sha256.as_deref());
+            let name = parts(path)
+                .last()
+                .map(|n| n.to_lowercase())
+                .unwrap_or_default();
+
+            let mut candidates: Vec<RelinkCandidate> = found
+                .get(&name)
+                .into_iter()
+                .flatten()
+                .filter_map(|(candidate, candidate_size)| {
+                    let mut level = RelinkMatch::Name;
+                    if let Some(size) = size {
+                        if size != *candidate_size {
+                            return None;
+                        }
+                        level = RelinkMatch::Size;
+                    }
+                    if let (true, Some(sha256)) = (match_hash, sha256) {
+                        let hash = hashes.entry(candidate.clone()).or_insert_with(|| {
+                            collect::sha256_file(candidate)
+                                .map_err(|e| {
+                                    warn!("[Relink] Cannot read {}: {}", candidate.display(), e)
+                                })
+                                .ok()
+                        });
+                        if hash.as_deref() != Some(sha256) {
+                            return None;
+                        }
+                        level = RelinkMatch::Hash;
+                    }
+                    Some(RelinkCandidate {
+                        path: project_path(candidate, media_root),
+                        size: *candidate_size,
+                        match_level: level,
+                        matching_folders: matching_folders(path, candidate),
+                    })
+                })
+                .collect();
+            candidates.sort_by(|a, b| {
+                (b.match_level, b.matching_folders)
+                    .cmp(&(a.match_level, a.matching_folders))
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+
+            // 最も有力な候補が1つに決まる場合だけ提案する
+            let proposed = match candidates.as_slice() {
+                [only] => Some(only.path.clone()),
+                [first, second, ..]
+                    if (first.match_level, first.matching_folders)
+                        > (second.match_level, second.matching_folders) =>
+                {
+                    Some(first.path.clone())
+                }
+                _ => None,
+            };
+
+            RelinkProposal {
+                path: path.to_string(),
+                item_ids: items.iter().map(|i| i.id.clone()).collect(),
+                candidates,
+                proposed,
+            }
+        })
+        .collect();
+
+    info!(
+        "[Relink] {} missing media, {} with a proposed relink",
+        proposals.len(),
+        proposals.iter().filter(|p| p.proposed.is_some()).count()
+    );
+    Ok(proposals)
+}
+
+/// パスを置き換え、変更したアイテムの数を返す
+///
+/// 置き換え先が見つからない・置き換え元を使うアイテムがない場合は何も変更しない。
+/// ファイルサイズが記録と異なる場合は、サイズを更新してハッシュの記録を消す。
+pub fn apply(
+    project: &mut Project,
+    media_root: Option<&str>,
+    relinks: &[Relink],
+) -> AppResult<usize> {
+    let mut sizes = Vec::with_capacity(relinks.len());
+    for relink in relinks {
+        let target = local_config::resolve_media_path(&relink.to, media_root);
+        let size = std::fs::metadata(&target)
+            .ok()
+            .filter(|m| m.is_file())
+            .map(|m| m.len())
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Media file not found: {}", target.display()))
+            })?;
+        let used = project
+            .cues
+            .iter()
+            .flat_map(|c| &c.items)
+            .any(|i| i.path == relink.from);
+        if !used {
+            return Err(AppError::NotFound(format!(
+                "No media item uses path: {}",
+                relink.from
+            )));
+        }
+        sizes.push(size);
+    }
+
+    let mut relinked = 0;
+    for item in project.cues.iter_mut().flat_map(|c| &mut c.items) {
+        let Some((relink, size)) = relinks
+            .iter()
+            .zip(&sizes)
+            .find(|(r, _)| r.from == item.path)
+        else {
+            continue;
+        };
+        item.path = relink.to.clone();
+        if item.size != Some(*size) {
+            item.size = Some(*size);
+            item.sha256 = None;
+        }
+        relinked += 1;
+    }
+
+    info!("[Relink] Relinked {} media item(s)", relinked);
+    Ok(relinked)
+}
+
+/// メディアアイテムにファイルのサイズを記録（アイテムの追加・変更時）
+///
+/// ファイルが見つからなければ記録を変えない。サイズが変わった場合は
+/// 記録されたSHA-256も別のファイルのものなので破棄する。
+pub fn record_size(item: &mut MediaItem, media_root: Option<&str>) {
+    let path = local_config::resolve_media_path(&item.path, media_root);
+    let Some(size) = std::fs::metadata(path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+    else {
+        return;
+    };
+    if item.size != Some(size) {
+        item.size = Some(size);
+        item.sha256 = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Cue, MediaType};
+
+    fn item(id: &str, path: &str) -> MediaItem {
+        MediaItem {
+            id: id.to_string(),
+            media_type: MediaType::Video,
+            name: id.to_string(),
+            path: path.to_string(),
+            output_id: "screen".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_matching_folders() {
+        let candidate = Path::new("/mnt/drive/Show/M01/intro.mp4");
+        assert_eq!(matching_folders("D:\\Show\\m01\\intro.mp4", candidate), 2);
+        assert_eq!(matching_folders("/Volumes/Old/M02/intro.mp4", candidate), 0);
+        assert_eq!(matching_folders("intro.mp4", candidate), 0);
+    }
+
+    #[test]
+    fn test_record_size() {
+        let dir = std::env::temp_dir().join(format!("relink-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("intro.mp4"), b"intro").unwrap();
+        let root = dir.to_string_lossy().into_owned();
+
+        let mut added = item("a", "intro.mp4");
+        record_size(&mut added, Some(&root));
+        assert_eq!(added.size, Some(5));
+
+        // 内容が差し替わっていればハッシュは破棄
+        added.sha256 = Some("old".to_string());
+        std::fs::write(dir.join("intro.mp4"), b"new intro").unwrap();
+        record_size(&mut added, Some(&root));
+        assert_eq!((added.size, added.sha256.as_deref()), (Some(9), None));
+
+        added.sha256 = Some("hash".to_string());
+        record_size(&mut added, Some(&root));
+        assert_eq!(added.sha256.as_deref(), Some("hash"));
+
+        // このPCに無いファイルは記録を残す
+        let mut elsewhere = item("b", "gone.mp4");
+        elsewhere.size = Some(42);
+        record_size(&mut elsewhere, Some(&root));
+        assert_eq!(elsewhere.size, Some(42));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_propose_and_apply() {
+        let dir = std::env::temp_dir().join(format!("relink-{}", uuid::Uuid::new_v4()));
+        for folder in ["M01", "M02", "backup/M01", ".trash"] {
+            std::fs::create_dir_all(dir.join(folder)).unwrap();
+        }
+        std::fs::write(dir.join("M01").join("Intro.mp4"), b"intro").unwrap();
+        std::fs::write(dir.join("M02").join("intro.mp4"), b"other").unwrap();
+        std::fs::write(dir.join("backup/M01").join("intro.mp4"), b"intro").unwrap();
+        std::fs::write(dir.join("loop.mp4"), b"loop").unwrap();
+        std::fs::write(dir.join(".trash").join("loop.mp4"), b"loop").unwrap();
+        std::fs::write(dir.join("M02").join("outro.mp4"), b"outro!").unwrap();
+
+        let mut recorded = item("c", "/old/outro.mp4");
+        recorded.size = Some(5);
+        let mut hashed = item("d", "/old/M01/intro.mp4");
+        hashed.size = Some(5);
+        hashed.sha256 = Some(collect::sha256_file(&dir.join("M01").join("Intro.mp4")).unwrap());
+        let mut project = Project {
+            cues: vec![Cue {
+                id: "cue".to_string(),
+                name: "cue".to_string(),
+                items: vec![
+                    item("a", "/old/M01/intro.mp4"),
+                    item("b", "/old/loop.mp4"),
+                    recorded,
+                    item("e", "/old/loop.mp4"),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let root = dir.to_string_lossy().into_owned();
+        let folders = vec![dir.clone()];
+
+        let proposals = propose(&project, Some(&root), &folders, false).unwrap();
+        let summary: Vec<(&str, Vec<&str>, usize, Option<&str>)> = proposals
+            .iter()
+            .map(|p| {
+                (
+                    p.path.as_str(),
+                    p.item_ids.iter().map(String::as_str).collect(),
+                    p.candidates.len(),
+                    p.proposed.as_deref(),
+                )
+            })
+            .collect();
+        std::fs::write(dir.join("M02").join("intro.mp4"), b"intro").unwrap();
+        project.cues[0].items.push(hashed);
+        let hashed = propose(&project, Some(&root), &folders, true).unwrap();
+
+        let relinks = vec![Relink {
+            from: "/old/outro.mp4".to_string(),
+            to: "M02/outro.mp4".to_string(),
+        }];
+        let relinked = apply(&mut project, Some(&root), &relinks).unwrap();
+        let missing_target = apply(
+            &mut project.clone(),
+            Some(&root),
+            &[Relink {
+                from: "/old/loop.mp4".to_string(),
+                to: "gone.mp4".to_string(),
+            }],
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            summary,
+            vec![
+                // 親フォルダ名が一致する M01/Intro.mp4 と backup/M01/intro.mp4 が同点
+                ("/old/M01/intro.mp4", vec!["a"], 3, None),
+                ("/old/loop.mp4", vec!["b", "e"], 1, Some("loop.mp4")),
+                // 記録されたサイズと異なる
+                ("/old/outro.mp4", vec!["c"], 0, None),
+            ]
+        );
+        // ハッシュが記録されたアイテムは内容の一致する候補だけ（M02/intro.mp4 も同じ内容）
+        let intro = &hashed[0];
+        assert_eq!(intro.item_ids, vec!["a", "d"]);
+        assert_eq!(intro.candidates.len(), 3);
+        assert!(intro
+            .candidates
+            .iter()
+            .all(|c| c.match_level == RelinkMatch::Hash));
+        assert_eq!(intro.candidates[2].path, "M02/intro.mp4");
+
+        assert_eq!(relinked, 1);
+        assert_eq!(project.cues[0].items[2].path, "M02/outro.mp4");
+        assert_eq!(project.cues[0].items[2].size, Some(6));
+        assert!(matches!(missing_target, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,434 @@
+//! プロジェクトの検証
+//!
+//! 本番中に `load_cue` が失敗して初めて分かる問題（メディアの欠落・
+//! 存在しない出力先・ID重複・メディアと出力の種類の不一致・デコードできない
+//! コーデック・接続されていないディスプレイ）を事前に洗い出す。
+//!
+//! パス・出力先はこのPCの設定（local_config）を適用した状態で検証する。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::editing::{routing_mismatch, RoutingMismatch};
+use crate::local_config;
+use crate::pipeline::probe::MediaStreams;
+use crate::types::{
+    LocalConfig, MediaItem, MediaType, MonitorInfo, OutputTarget, OutputType, Project,
+    ValidationFinding, ValidationKind, ValidationSeverity,
+};
+
+/// メディアファイルを調べる関数（デコードできない場合はエラー内容）
+pub type Probe<'a> = &'a mut dyn FnMut(&Path) -> Result<MediaStreams, String>;
+
+fn finding(
+    severity: ValidationSeverity,
+    kind: ValidationKind,
+    message: String,
+) -> ValidationFinding {
+    ValidationFinding {
+        severity,
+        kind,
+        message,
+        cue_index: None,
+        item_id: None,
+        output_id: None,
+        path: None,
+    }
+}
+
+fn item_finding(
+    severity: ValidationSeverity,
+    kind: ValidationKind,
+    message: String,
+    cue_index: usize,
+    item: &MediaItem,
+) -> ValidationFinding {
+    ValidationFinding {
+        cue_index: Some(cue_index),
+        item_id: Some(item.id.clone()),
+        output_id: Some(item.output_id.clone()),
+        path: Some(item.path.clone()),
+        ..finding(severity, kind, message)
+    }
+}
+
+/// プロジェクトを検証（重大な問題から順に返す）
+///
+/// `monitors` が空の場合はディスプレイの検証を省略する。
+/// `probe` を渡すとメディアファイルのコーデック・ストリームも調べる。
+pub fn validate(
+    project: &Project,
+    local: &LocalConfig,
+    monitors: &[MonitorInfo],
+    probe: Option<Probe>,
+) -> Vec<ValidationFinding> {
+    let outputs = local_config::apply_outputs(&project.outputs, local);
+
+    let mut findings = check_ids(project);
+    findings.extend(check_routing(project, &outputs));
+    findings.extend(check_monitors(&outputs, monitors));
+    findings.extend(check_media(project, local.media_root.as_deref(), probe));
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    findings
+}
+
+/// IDの重複
+fn check_ids(project: &Project) -> Vec<ValidationFinding> {
+    let mut findings = Vec::new();
+    let mut duplicate = |what: &str, severity, ids: &mut dyn Iterator<Item = &String>| {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for id in ids {
+            if !seen.insert(id) && reported.insert(id) {
+                findings.push(finding(
+                    severity,
+                    ValidationKind::DuplicateId,
+                    format!("Duplicate {} id: {}", what, id),
+                ));
+            }
+        }
+    };
+
+    // Cue・出力はIDで参照されるため重複は誤動作につながる
+    duplicate(
+        "cue",
+        ValidationSeverity::Error,
+        &mut project.cues.iter().map(|c| &c.id),
+    );
+    duplicate(
+        "output",
+        ValidationSeverity::Error,
+        &mut project.outputs.iter().map(|o| &o.id),
+    );
+    duplicate(
+        "media item",
+        ValidationSeverity::Warning,
+        &mut project
+            .cues
+            .iter()
+            .flat_map(|c| c.items.iter().map(|i| &i.id)),
+    );
+    duplicate(
+        "macro",
+        ValidationSeverity::Error,
+        &mut project.macros.iter().map(|m| &m.id),
+    );
+
+    findings
+}
+
+/// アイテムの出力先と種類
+fn check_routing(project: &Project, outputs: &[OutputTarget]) -> Vec<ValidationFinding> {
+    let mut findings = Vec::new();
+    for (cue_index, cue) in project.cues.iter().enumerate() {
+        for item in &cue.items {
+            let Some(output) = outputs.iter().find(|o| o.id == item.output_id) else {
+                findings.push(item_finding(
+                    ValidationSeverity::Error,
+                    ValidationKind::UnknownOutput,
+                    format!(
+                        "'{}' in cue '{}' is routed to unknown output '{}'",
+                        item.name, cue.name, item.output_id
+                    ),
+                    cue_index,
+                    item,
+                ));
+                continue;
+            };
+
+            match routing_mismatch(&item.media_type, &output.output_type) {
+                Some(RoutingMismatch::VideoToAudio) => findings.push(item_finding(
+                    ValidationSeverity::Error,
+                    ValidationKind::MediaTypeMismatch,
+                    format!(
+                        "Video '{}' in cue '{}' is routed to audio output '{}' and will not be shown",
+                        item.name, cue.name, output.name
+                    ),
+                    cue_index,
+                    item,
+                )),
+                Some(RoutingMismatch::AudioToVideo) => findings.push(item_finding(
+                    ValidationSeverity::Warning,
+                    ValidationKind::MediaTypeMismatch,
+                    format!(
+                        "Audio '{}' in cue '{}' is routed to video output '{}' and will play on the default device",
+                        item.name, cue.name, output.name
+                    ),
+                    cue_index,
+                    item,
+                )),
+                None => {}
+            }
+        }
+    }
+    findings
+}
+
+/// Display出力のディスプレイ番号
+fn check_monitors(outputs: &[OutputTarget], monitors: &[MonitorInfo]) -> Vec<ValidationFinding> {
+    if monitors.is_empty() {
+        return Vec::new();
+    }
+
+    outputs
+        .iter()
+        .filter(|o| o.output_type == OutputType::Display)
+        .filter_map(|output| {
+            let index = output.display_index.unwrap_or(0);
+            (index >= monitors.len()).then(|| ValidationFinding {
+                output_id: Some(output.id.clone()),
+                ..finding(
+                    ValidationSeverity::Error,
+                    ValidationKind::MissingMonitor,
+                    format!(
+                        "Output '{}' uses display {} but only {} display(s) are connected",
+                        output.name,
+                        index + 1,
+                        monitors.len()
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+/// メディアファイルの存在・読み取り・デコード
+fn check_media(
+    project: &Project,
+    media_root: Option<&str>,
+    mut probe: Option<Probe>,
+) -> Vec<ValidationFinding> {
+    let mut findings = Vec::new();
+    // 同じファイルは1回だけ調べる
+    let mut probed: HashMap<PathBuf, Result<MediaStreams, String>> = HashMap::new();
+
+    for (cue_index, cue) in project.cues.iter().enumerate() {
+        for item in &cue.items {
+            let path = local_config::resolve_media_path(&item.path, media_root);
+            let error = |kind, message| {
+                item_finding(ValidationSeverity::Error, kind, message, cue_index, item)
+            };
+
+            match std::fs::metadata(&path) {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    findings.push(error(
+                        ValidationKind::MissingMedia,
+                        format!(
+                            "Media file for '{}' in cue '{}' not found: {}",
+                            item.name,
+                            cue.name,
+                            path.display()
+                        ),
+                    ));
+                    continue;
+                }
+                Ok(metadata) if metadata.is_dir() => {
+                    findings.push(error(
+                        ValidationKind::UnreadableMedia,
+                        format!(
+                            "Media path for '{}' is a folder: {}",
+                            item.name,
+                            path.display()
+                        ),
+                    ));
+                    continue;
+                }
+                _ => {}
+            }
+            if let Err(e) = std::fs::File::open(&path) {
+                findings.push(error(
+                    ValidationKind::UnreadableMedia,
+                    format!("Cannot read media file {}: {}", path.display(), e),
+                ));
+                continue;
+            }
+
+            let Some(probe) = probe.as_mut() else {
+                continue;
+            };
+            let result = probed.entry(path.clone()).or_insert_with(|| probe(&path));
+            match result {
+                Err(e) => findings.push(error(
+                    ValidationKind::UndecodableMedia,
+                    format!("Cannot decode {}: {}", path.display(), e),
+                )),
+                Ok(streams) => {
+                    let missing = match item.media_type {
+                        MediaType::Video if !streams.video => Some("video"),
+                        MediaType::Audio if !streams.audio => Some("audio"),
+                        _ => None,
+                    };
+                    if let Some(stream) = missing {
+                        findings.push(error(
+                            ValidationKind::MediaTypeMismatch,
+                            format!(
+                                "'{}' in cue '{}' has no {} stream: {}",
+                                item.name,
+                                cue.name,
+                                stream,
+                                path.display()
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Cue;
+
+    fn output(id: &str, output_type: OutputType, display_index: Option<usize>) -> OutputTarget {
+        OutputTarget {
+            id: id.to_string(),
+            name: id.to_string(),
+            output_type,
+            display_index,
+            ..Default::default()
+        }
+    }
+
+    fn item(id: &str, media_type: MediaType, path: &str, output_id: &str) -> MediaItem {
+        MediaItem {
+            id: id.to_string(),
+            media_type,
+            name: id.to_string(),
+            path: path.to_string(),
+            output_id: output_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn cue(id: &str, items: Vec<MediaItem>) -> Cue {
+        Cue {
+            id: id.to_string(),
+            name: id.to_string(),
+            items,
+            ..Default::default()
+        }
+    }
+
+    fn monitor(index: usize) -> MonitorInfo {
+        MonitorInfo {
+            index,
+            name: format!("Monitor {}", index + 1),
+            width: 1920,
+            height: 1080,
+            x: 0,
+            y: 0,
+            is_primary: index == 0,
+        }
+    }
+
+    fn kinds(findings: &[ValidationFinding]) -> Vec<(ValidationSeverity, ValidationKind)> {
+        findings.iter().map(|f| (f.severity, f.kind)).collect()
+    }
+
+    #[test]
+    fn test_structure_findings() {
+        let project = Project {
+            outputs: vec![
+                output("screen", OutputType::Display, Some(2)),
+                output("speakers", OutputType::Audio, None),
+                output("speakers", OutputType::Audio, None),
+            ],
+            cues: vec![
+                cue(
+                    "a",
+                    vec![
+                        item("v1", MediaType::Video, "v.mp4", "speakers"),
+                        item("a1", MediaType::Audio, "a.wav", "screen"),
+                    ],
+                ),
+                cue("a", vec![item("v2", MediaType::Video, "v.mp4", "missing")]),
+            ],
+            ..Default::default()
+        };
+
+        let outputs = &project.outputs;
+        let mut findings = check_ids(&project);
+        findings.extend(check_routing(&project, outputs));
+        findings.extend(check_monitors(outputs, &[monitor(0), monitor(1)]));
+
+        use ValidationKind::*;
+        use ValidationSeverity::*;
+        assert_eq!(
+            kinds(&findings),
+            vec![
+                (Error, DuplicateId),
+                (Error, DuplicateId),
+                (Error, MediaTypeMismatch),
+                (Warning, MediaTypeMismatch),
+                (Error, UnknownOutput),
+                (Error, MissingMonitor),
+            ]
+        );
+        assert_eq!(findings[4].cue_index, Some(1));
+        assert_eq!(findings[5].output_id.as_deref(), Some("screen"));
+
+        // モニター一覧がなければディスプレイは検証しない
+        assert!(check_monitors(outputs, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_media_findings() {
+        let dir = std::env::temp_dir().join(format!("validation-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("folder")).unwrap();
+        std::fs::write(dir.join("video.mp4"), b"").unwrap();
+        std::fs::write(dir.join("sound.wav"), b"").unwrap();
+
+        let project = Project {
+            outputs: vec![output("screen", OutputType::Display, None)],
+            cues: vec![cue(
+                "a",
+                vec![
+                    item("v1", MediaType::Video, "video.mp4", "screen"),
+                    item("v2", MediaType::Video, "video.mp4", "screen"),
+                    item("v3", MediaType::Video, "sound.wav", "screen"),
+                    item("v4", MediaType::Video, "gone.mp4", "screen"),
+                    item("v5", MediaType::Video, "folder", "screen"),
+                ],
+            )],
+            ..Default::default()
+        };
+        let local = LocalConfig {
+            media_root: Some(dir.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+
+        let mut probes = 0;
+        let mut probe = |path: &Path| {
+            probes += 1;
+            match path.extension().and_then(|e| e.to_str()) {
+                Some("wav") => Ok(MediaStreams {
+                    video: false,
+                    audio: true,
+                }),
+                _ => Err("missing decoder".to_string()),
+            }
+        };
+        let findings = validate(&project, &local, &[], Some(&mut probe));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        use ValidationKind::*;
+        assert_eq!(
+            findings.iter().map(|f| f.kind).collect::<Vec<_>>(),
+            vec![
+                UndecodableMedia,
+                UndecodableMedia,
+                MediaTypeMismatch,
+                MissingMedia,
+                UnreadableMedia,
+            ]
+        );
+        // 同じファイルは1回だけ調べる
+        assert_eq!(probes, 2);
+        assert_eq!(findings[3].item_id.as_deref(), Some("v4"));
+    }
+}
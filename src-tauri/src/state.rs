@@ -1,26 +1,102 @@
 use parking_lot::Mutex;
+use std::collections::HashMap;
+#[cfg(feature = "gui")]
+use std::path::PathBuf;
+use std::sync::Arc;
 
+use crate::dmx::{ArtNetInput, ArtTimecodeOutput, SacnInput};
+use crate::events::EventBus;
+use crate::http::HttpServer;
+use crate::midi::MidiInputService;
+use crate::osc::OscServer;
 use crate::output::manager::OutputManager;
+#[cfg(feature = "gui")]
 use crate::output::standby::StandbyManager;
 use crate::pipeline::cue_player::CuePlayer;
-use crate::types::Project;
+use crate::project::history::History;
+#[cfg(feature = "gui")]
+use crate::project::recovery::{AutoSave, Session};
+use crate::sync::SyncService;
+#[cfg(feature = "gui")]
+use crate::timecode::LtcChase;
+#[cfg(feature = "gui")]
+use crate::types::RecoveryInfo;
+use crate::types::{LevelTarget, LocalConfig, MonitorInfo, Project};
 
+/// アプリケーション状態
+///
+/// タイムコードチェイス等のバックグラウンドスレッドとも共有するため、
+/// 各フィールドは `Arc` で保持し、クローンは同じ状態を指す。
+#[derive(Clone)]
 pub struct AppState {
-    pub player: Mutex<Option<CuePlayer>>,
-    pub output_manager: Mutex<OutputManager>,
-    pub standby_manager: Mutex<StandbyManager>,
-    pub project: Mutex<Option<Project>>,
-    pub current_cue_index: Mutex<i32>,
+    pub player: Arc<Mutex<Option<CuePlayer>>>,
+    pub output_manager: Arc<Mutex<OutputManager>>,
+    #[cfg(feature = "gui")]
+    pub standby_manager: Arc<Mutex<StandbyManager>>,
+    pub project: Arc<Mutex<Option<Project>>>,
+    /// 現在のプロジェクトのファイル（未保存ならNone）
+    #[cfg(feature = "gui")]
+    pub project_path: Arc<Mutex<Option<PathBuf>>>,
+    /// 編集履歴（ロックはprojectの後に取る）
+    pub history: Arc<Mutex<History>>,
+    #[cfg(feature = "gui")]
+    pub auto_save: Arc<Mutex<Option<AutoSave>>>,
+    /// このプロセスのセッション（異常終了の検出用）
+    #[cfg(feature = "gui")]
+    pub session: Arc<Mutex<Option<Session>>>,
+    /// 前回異常終了した時のリカバリーファイル（復元か破棄まで保持）
+    #[cfg(feature = "gui")]
+    pub recovery: Arc<Mutex<Option<RecoveryInfo>>>,
+    /// PC固有の設定（local_config）
+    pub local_config: Arc<Mutex<LocalConfig>>,
+    pub current_cue_index: Arc<Mutex<i32>>,
+    #[cfg(feature = "gui")]
+    pub ltc_chase: Arc<Mutex<Option<LtcChase>>>,
+    pub sync: Arc<Mutex<Option<SyncService>>>,
+    pub osc: Arc<Mutex<Option<OscServer>>>,
+    pub http: Arc<Mutex<Option<HttpServer>>>,
+    pub midi: Arc<Mutex<Option<MidiInputService>>>,
+    pub art_net: Arc<Mutex<Option<ArtNetInput>>>,
+    pub sacn: Arc<Mutex<Option<SacnInput>>>,
+    pub art_timecode_output: Arc<Mutex<Option<ArtTimecodeOutput>>>,
+    /// 実行中フェードの世代番号（control::fade）
+    pub fades: Arc<Mutex<HashMap<LevelTarget, u64>>>,
+    /// 最後に取得したモニター一覧（AppHandleなしでCueを読み込むため）
+    pub monitors: Arc<Mutex<Vec<MonitorInfo>>>,
+    pub events: EventBus,
 }
 
 impl AppState {
     pub fn new() -> Self {
         Self {
-            player: Mutex::new(None),
-            output_manager: Mutex::new(OutputManager::new()),
-            standby_manager: Mutex::new(StandbyManager::new()),
-            project: Mutex::new(None),
-            current_cue_index: Mutex::new(-1),
+            player: Arc::new(Mutex::new(None)),
+            output_manager: Arc::new(Mutex::new(OutputManager::new())),
+            #[cfg(feature = "gui")]
+            standby_manager: Arc::new(Mutex::new(StandbyManager::new())),
+            project: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "gui")]
+            project_path: Arc::new(Mutex::new(None)),
+            history: Arc::new(Mutex::new(History::new())),
+            #[cfg(feature = "gui")]
+            auto_save: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "gui")]
+            session: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "gui")]
+            recovery: Arc::new(Mutex::new(None)),
+            local_config: Arc::new(Mutex::new(LocalConfig::default())),
+            current_cue_index: Arc::new(Mutex::new(-1)),
+            #[cfg(feature = "gui")]
+            ltc_chase: Arc::new(Mutex::new(None)),
+            sync: Arc::new(Mutex::new(None)),
+            osc: Arc::new(Mutex::new(None)),
+            http: Arc::new(Mutex::new(None)),
+            midi: Arc::new(Mutex::new(None)),
+            art_net: Arc::new(Mutex::new(None)),
+            sacn: Arc::new(Mutex::new(None)),
+            art_timecode_output: Arc::new(Mutex::new(None)),
+            fades: Arc::new(Mutex::new(HashMap::new())),
+            monitors: Arc::new(Mutex::new(Vec::new())),
+            events: EventBus::new(),
         }
     }
 
@@ -0,0 +1,61 @@
+//! スレーブ管理メッセージ
+//!
+//! heartbeat（スレーブ → マスター）とリモート操作（マスター → スレーブ）を
+//! 同期パケットと同じソケットでやり取りする。頻度が低く項目も可変なので、
+//! マジック "TLPC" の後にJSONを続ける。
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{SlaveCommand, SlaveHeartbeat};
+
+pub const CONTROL_MAGIC: [u8; 4] = *b"TLPC";
+
+/// 管理メッセージ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ControlMessage {
+    Heartbeat(SlaveHeartbeat),
+    #[serde(rename_all = "camelCase")]
+    Command {
+        slave_id: String,
+        command: SlaveCommand,
+    },
+}
+
+impl ControlMessage {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = CONTROL_MAGIC.to_vec();
+        // シリアライズ可能な型のみなので失敗しない
+        serde_json::to_writer(&mut buf, self).expect("control message serialization");
+        buf
+    }
+
+    /// 受信データをデコード（管理メッセージでなければNone）
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        let body = buf.strip_prefix(&CONTROL_MAGIC)?;
+        serde_json::from_slice(body).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_roundtrip() {
+        let message = ControlMessage::Command {
+            slave_id: "slave-a".to_string(),
+            command: SlaveCommand::Blackout { enabled: true },
+        };
+
+        match ControlMessage::decode(&message.encode()) {
+            Some(ControlMessage::Command { slave_id, command }) => {
+                assert_eq!(slave_id, "slave-a");
+                assert_eq!(command, SlaveCommand::Blackout { enabled: true });
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert!(ControlMessage::decode(b"TLPS{}").is_none());
+    }
+}
@@ -0,0 +1,309 @@
+//! 同期マスター
+//!
+//! 現在のCue・再生状態・再生位置を一定周期でUDP送信する。
+//! 同じソケットでスレーブの heartbeat を受信し、スレーブ一覧を管理する。
+
+use gstreamer as gst;
+use parking_lot::Mutex;
+use std::net::{Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+use crate::error::{AppError, AppResult};
+use crate::playback;
+use crate::state::AppState;
+use crate::sync::control::ControlMessage;
+use crate::sync::net_clock::NetClockProvider;
+use crate::sync::packet::{cue_id_bytes, PacketType, PlayState, SyncPacket};
+use crate::sync::roster::SlaveRoster;
+#[cfg(feature = "gui")]
+use crate::types::{SlaveCommand, SlavePeer, SyncMode, SyncStatus};
+use crate::types::{SyncConfig, SyncTransport};
+
+/// heartbeat 受信バッファ
+const RECV_BUFFER_SIZE: usize = 8192;
+
+/// 送信用ソケットと送信先を作成
+fn open_transport(transport: &SyncTransport) -> AppResult<(UdpSocket, Vec<SocketAddr>)> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+
+    let targets = match transport {
+        SyncTransport::Broadcast { port } => {
+            socket.set_broadcast(true)?;
+            vec![SocketAddr::from((Ipv4Addr::BROADCAST, *port))]
+        }
+        SyncTransport::Multicast { addr, port } => {
+            let group: Ipv4Addr = addr
+                .parse()
+                .map_err(|_| AppError::Sync(format!("Invalid multicast address: {}", addr)))?;
+            if !group.is_multicast() {
+                return Err(AppError::Sync(format!("Not a multicast address: {}", addr)));
+            }
+            // 同一PC上のスレーブでも受信できるようにループバックを有効化
+            socket.set_multicast_loop_v4(true)?;
+            vec![SocketAddr::from((group, *port))]
+        }
+        SyncTransport::Unicast { targets } => targets
+            .iter()
+            .map(|target| {
+                target
+                    .to_socket_addrs()
+                    .ok()
+                    .and_then(|mut addrs| addrs.next())
+                    .ok_or_else(|| AppError::Sync(format!("Invalid unicast target: {}", target)))
+            })
+            .collect::<AppResult<Vec<_>>>()?,
+    };
+
+    if targets.is_empty() {
+        return Err(AppError::Sync("No sync targets configured".to_string()));
+    }
+
+    Ok((socket, targets))
+}
+
+/// 送信スレッドと共有する状態
+struct MasterShared {
+    packets_sent: u64,
+    roster: SlaveRoster,
+}
+
+/// 同期マスター
+pub struct SyncMaster {
+    state: AppState,
+    net_clock: Option<NetClockProvider>,
+    #[cfg(feature = "gui")]
+    socket: UdpSocket,
+    #[cfg(feature = "gui")]
+    shared: Arc<Mutex<MasterShared>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SyncMaster {
+    pub fn start(config: &SyncConfig, state: AppState) -> AppResult<Self> {
+        let (socket, targets) = open_transport(&config.transport)?;
+        let interval = Duration::from_secs_f64(1.0 / config.send_interval_hz.max(1) as f64);
+
+        // パイプラインクロックを配信し、自身も同じクロックで再生する
+        let net_clock = if config.net_clock {
+            let provider = NetClockProvider::start(config.clock_port)?;
+            playback::set_clock(&state, Some(provider.clock()))?;
+            Some(provider)
+        } else {
+            None
+        };
+        let clock = net_clock.as_ref().map(|p| p.clock());
+
+        let shared = Arc::new(Mutex::new(MasterShared {
+            packets_sent: 0,
+            roster: SlaveRoster::new(Duration::from_millis(config.slave_timeout_ms)),
+        }));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let state = state.clone();
+            let socket = socket.try_clone()?;
+            let shared = shared.clone();
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name("sync-master".to_string())
+                .spawn(move || Self::run(state, socket, targets, interval, clock, shared, running))
+                .map_err(AppError::Io)?
+        };
+
+        info!("[SyncMaster] Started ({:?})", config.transport);
+
+        Ok(Self {
+            state,
+            net_clock,
+            #[cfg(feature = "gui")]
+            socket,
+            #[cfg(feature = "gui")]
+            shared,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run(
+        state: AppState,
+        socket: UdpSocket,
+        targets: Vec<SocketAddr>,
+        interval: Duration,
+        clock: Option<gst::Clock>,
+        shared: Arc<Mutex<MasterShared>>,
+        running: Arc<AtomicBool>,
+    ) {
+        let mut sequence: u16 = 0;
+        let mut last_cue_index = None;
+        let mut next_send = Instant::now();
+        let mut buf = [0u8; RECV_BUFFER_SIZE];
+
+        while running.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            if now >= next_send {
+                let packet =
+                    Self::build_packet(&state, clock.as_ref(), sequence, &mut last_cue_index);
+                let data = packet.encode();
+
+                for target in &targets {
+                    if let Err(e) = socket.send_to(&data, target) {
+                        warn!("[SyncMaster] Failed to send to {}: {}", target, e);
+                    }
+                }
+
+                shared.lock().packets_sent += 1;
+                sequence = sequence.wrapping_add(1);
+                next_send += interval;
+                if next_send < now {
+                    next_send = now + interval;
+                }
+            }
+
+            // 次の送信まで heartbeat を待つ
+            let wait = next_send
+                .saturating_duration_since(Instant::now())
+                .max(Duration::from_millis(1));
+            let _ = socket.set_read_timeout(Some(wait));
+            if let Ok((len, addr)) = socket.recv_from(&mut buf) {
+                if let Some(ControlMessage::Heartbeat(heartbeat)) =
+                    ControlMessage::decode(&buf[..len])
+                {
+                    let event = shared
+                        .lock()
+                        .roster
+                        .on_heartbeat(addr, heartbeat, Instant::now());
+                    if let Some(event) = event {
+                        info!("[SyncMaster] {:?}", event);
+                        state.events.emit(event);
+                    }
+                }
+            }
+
+            let silent = shared.lock().roster.check(Instant::now());
+            for event in silent {
+                warn!("[SyncMaster] {:?}", event);
+                state.events.emit(event);
+            }
+        }
+
+        debug!("[SyncMaster] Send thread finished");
+    }
+
+    /// 現在の再生状態からパケットを作成
+    fn build_packet(
+        state: &AppState,
+        clock: Option<&gst::Clock>,
+        sequence: u16,
+        last_cue_index: &mut Option<i32>,
+    ) -> SyncPacket {
+        let player_state = playback::player_state(state);
+        let cue_index = player_state.current_cue_index;
+
+        let cue_id = state
+            .project
+            .lock()
+            .as_ref()
+            .and_then(|p| usize::try_from(cue_index).ok().and_then(|i| p.cues.get(i)))
+            .map(|cue| cue_id_bytes(&cue.id))
+            .unwrap_or([0; 16]);
+
+        let packet_type = if *last_cue_index != Some(cue_index) {
+            *last_cue_index = Some(cue_index);
+            PacketType::CueChange
+        } else {
+            PacketType::Sync
+        };
+
+        let mut position_us = (player_state.current_time.max(0.0) * 1_000_000.0) as u64;
+        let master_timestamp_us = match clock {
+            // 共有クロックの時刻と位置を同時に取得し、エポックを正確に伝える
+            Some(clock) => match playback::clock_position(state) {
+                Some((now, position)) => {
+                    position_us = position.useconds();
+                    now.useconds()
+                }
+                None => clock.time().map(|t| t.useconds()).unwrap_or(0),
+            },
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_micros() as u64)
+                .unwrap_or(0),
+        };
+
+        SyncPacket {
+            packet_type,
+            sequence,
+            master_timestamp_us,
+            cue_id,
+            cue_index: u16::try_from(cue_index).ok(),
+            position_us,
+            state: PlayState::from(&player_state.status),
+            speed: 1.0,
+        }
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> SyncStatus {
+        let shared = self.shared.lock();
+        SyncStatus {
+            mode: SyncMode::Master,
+            running: self.running.load(Ordering::Relaxed),
+            packets_sent: shared.packets_sent,
+            slaves_online: shared.roster.online_count(),
+            playback_rate: 1.0,
+            net_clock: self.net_clock.as_ref().map(|p| p.status()),
+            ..Default::default()
+        }
+    }
+
+    /// スレーブ一覧
+    #[cfg(feature = "gui")]
+    pub fn slaves(&self) -> Vec<SlavePeer> {
+        self.shared.lock().roster.peers(Instant::now())
+    }
+
+    /// スレーブへリモート操作を送信
+    #[cfg(feature = "gui")]
+    pub fn send_command(&self, slave_id: &str, command: SlaveCommand) -> AppResult<()> {
+        let address = self
+            .shared
+            .lock()
+            .roster
+            .address(slave_id)
+            .ok_or_else(|| AppError::NotFound(format!("Slave not found: {}", slave_id)))?;
+
+        let message = ControlMessage::Command {
+            slave_id: slave_id.to_string(),
+            command,
+        };
+        self.socket.send_to(&message.encode(), address)?;
+
+        info!(
+            "[SyncMaster] Sent {:?} to {} ({})",
+            message, slave_id, address
+        );
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[SyncMaster] Stopped");
+        }
+        if self.net_clock.take().is_some() {
+            let _ = playback::set_clock(&self.state, None);
+        }
+    }
+}
+
+impl Drop for SyncMaster {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,86 @@
+//! マルチPC同期
+//!
+//! マスターが現在のCue・再生状態・再生位置をUDPで送信し、
+//! スレーブが同じCueを読み込んで再生位置を追従する。
+//! ネットワーククロック共有を有効にすると、スレーブはマスターと同じクロック・
+//! エポックで再生し、位置の追従ではなくクロック基準で揃う。
+
+pub mod control;
+pub mod master;
+pub mod net_clock;
+pub mod packet;
+pub mod roster;
+pub mod slave;
+
+#[cfg(feature = "gui")]
+use crate::error::AppError;
+use crate::error::AppResult;
+use crate::state::AppState;
+#[cfg(feature = "gui")]
+use crate::types::{SlaveCommand, SlavePeer, SyncStatus};
+use crate::types::{SyncConfig, SyncMode};
+
+use master::SyncMaster;
+use slave::SyncSlave;
+
+/// 実行中の同期サービス
+pub enum SyncService {
+    Master(SyncMaster),
+    Slave(SyncSlave),
+}
+
+/// 実行中の同期を停止し、指定の設定で開始し直す
+pub fn restart(state: &AppState, config: &SyncConfig) -> AppResult<()> {
+    let mut sync_guard = state.sync.lock();
+    if let Some(mut existing) = sync_guard.take() {
+        existing.stop();
+    }
+    *sync_guard = SyncService::start(config, state.clone())?;
+    Ok(())
+}
+
+impl SyncService {
+    /// 設定のモードで同期を開始（Offの場合はNone）
+    pub fn start(config: &SyncConfig, state: AppState) -> AppResult<Option<Self>> {
+        Ok(match config.mode {
+            SyncMode::Off => None,
+            SyncMode::Master => Some(SyncService::Master(SyncMaster::start(config, state)?)),
+            SyncMode::Slave => Some(SyncService::Slave(SyncSlave::start(config, state)?)),
+        })
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> SyncStatus {
+        match self {
+            SyncService::Master(master) => master.status(),
+            SyncService::Slave(slave) => slave.status(),
+        }
+    }
+
+    /// スレーブ一覧（マスター時のみ）
+    #[cfg(feature = "gui")]
+    pub fn slaves(&self) -> Vec<SlavePeer> {
+        match self {
+            SyncService::Master(master) => master.slaves(),
+            SyncService::Slave(_) => Vec::new(),
+        }
+    }
+
+    /// スレーブへリモート操作を送信（マスター時のみ）
+    #[cfg(feature = "gui")]
+    pub fn send_command(&self, slave_id: &str, command: SlaveCommand) -> AppResult<()> {
+        match self {
+            SyncService::Master(master) => master.send_command(slave_id, command),
+            SyncService::Slave(_) => Err(AppError::InvalidState(
+                "Remote commands are only available in master mode".to_string(),
+            )),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        match self {
+            SyncService::Master(master) => master.stop(),
+            SyncService::Slave(slave) => slave.stop(),
+        }
+    }
+}
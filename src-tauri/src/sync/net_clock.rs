@@ -0,0 +1,120 @@
+//! ネットワーククロック共有
+//!
+//! マスターはシステムクロックをGstNetTimeProviderで配信し、自身のパイプラインも
+//! そのクロックで動かす。スレーブはGstNetClientClockでマスターのクロックに同期し、
+//! 同じエポック（再生位置0に対応するクロック時刻）からbase_timeを決めて再生する。
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_net as gst_net;
+use tracing::info;
+
+use crate::error::{AppError, AppResult};
+use crate::types::NetClockStatus;
+
+/// NetClientClockが統計情報を投げるメッセージ名
+const STATISTICS_MESSAGE: &str = "gst-netclock-statistics";
+
+/// クロック配信（マスター）
+pub struct NetClockProvider {
+    clock: gst::Clock,
+    _provider: gst_net::NetTimeProvider,
+    port: u16,
+}
+
+impl NetClockProvider {
+    pub fn start(port: u16) -> AppResult<Self> {
+        let clock = gst::SystemClock::obtain();
+        let provider = gst_net::NetTimeProvider::new(&clock, None, port as i32)
+            .map_err(|e| AppError::Sync(format!("Failed to start clock provider: {:?}", e)))?;
+
+        info!("[NetClock] Providing pipeline clock on port {}", port);
+
+        Ok(Self {
+            clock,
+            _provider: provider,
+            port,
+        })
+    }
+
+    pub fn clock(&self) -> gst::Clock {
+        self.clock.clone()
+    }
+
+    pub fn status(&self) -> NetClockStatus {
+        NetClockStatus {
+            synced: true,
+            address: format!("0.0.0.0:{}", self.port),
+            ..Default::default()
+        }
+    }
+}
+
+/// マスターのクロックに追従するクライアント（スレーブ）
+pub struct NetClockClient {
+    clock: gst_net::NetClientClock,
+    bus: gst::Bus,
+    status: NetClockStatus,
+}
+
+impl NetClockClient {
+    pub fn connect(address: &str, port: u16) -> Self {
+        let clock = gst_net::NetClientClock::new(
+            Some("sync-net-clock"),
+            address,
+            port as i32,
+            gst::ClockTime::ZERO,
+        );
+
+        // 同期状態・RTTなどの統計を受け取る
+        let bus = gst::Bus::new();
+        clock.set_property("bus", &bus);
+
+        info!("[NetClock] Slaving to {}:{}", address, port);
+
+        Self {
+            clock,
+            bus,
+            status: NetClockStatus {
+                address: format!("{}:{}", address, port),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn clock(&self) -> gst::Clock {
+        self.clock.clone().upcast()
+    }
+
+    /// マスターのクロックに同期済みか
+    pub fn is_synced(&self) -> bool {
+        self.clock.is_synced()
+    }
+
+    /// 溜まった統計メッセージを取り込む
+    pub fn poll(&mut self) {
+        while let Some(msg) = self.bus.pop_filtered(&[gst::MessageType::Element]) {
+            let Some(s) = msg.structure() else {
+                continue;
+            };
+            if s.name() != STATISTICS_MESSAGE {
+                continue;
+            }
+
+            if let Ok(rtt) = s.get::<u64>("rtt-average") {
+                self.status.rtt_ms = Some(rtt as f64 / 1_000_000.0);
+            }
+            if let (Ok(local), Ok(remote)) = (s.get::<u64>("local"), s.get::<u64>("remote")) {
+                self.status.offset_ms = Some((remote as i64 - local as i64) as f64 / 1_000_000.0);
+            }
+            if let Ok(r_squared) = s.get::<f64>("r-squared") {
+                self.status.quality = Some(r_squared);
+            }
+        }
+        self.status.synced = self.is_synced();
+    }
+
+    pub fn status(&self) -> NetClockStatus {
+        self.status.clone()
+    }
+}
@@ -0,0 +1,184 @@
+//! 同期パケット
+//!
+//! 固定長48バイト、ビッグエンディアン。
+//!
+//! | offset | size | field               |
+//! |--------|------|---------------------|
+//! | 0      | 4    | magic "TLPS"        |
+//! | 4      | 1    | version             |
+//! | 5      | 1    | packet_type         |
+//! | 6      | 2    | sequence            |
+//! | 8      | 8    | master_timestamp_us |
+//! | 16     | 16   | cue_id (UUID)       |
+//! | 32     | 8    | position_us         |
+//! | 40     | 1    | state               |
+//! | 41     | 4    | speed (f32)         |
+//! | 45     | 2    | cue_index (0xFFFF = なし) |
+//! | 47     | 1    | reserved            |
+//!
+//! `master_timestamp_us` はネットワーククロック共有時は共有クロックの時刻、
+//! それ以外はUNIX時刻。共有時は `master_timestamp_us - position_us` が
+//! 再生位置0に対応するクロック時刻（エポック）になる。
+
+use crate::types::PlayerStatus;
+
+pub const PACKET_SIZE: usize = 48;
+pub const MAGIC: [u8; 4] = *b"TLPS";
+pub const PROTOCOL_VERSION: u8 = 1;
+
+const NO_CUE_INDEX: u16 = 0xFFFF;
+
+/// パケット種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PacketType {
+    /// 通常同期（定期送信）
+    Sync = 0x01,
+    /// Cue変更通知（即時）
+    CueChange = 0x02,
+    /// 緊急停止
+    EmergencyStop = 0x03,
+}
+
+impl PacketType {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x01 => Some(PacketType::Sync),
+            0x02 => Some(PacketType::CueChange),
+            0x03 => Some(PacketType::EmergencyStop),
+            _ => None,
+        }
+    }
+}
+
+/// マスターの再生状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PlayState {
+    Stopped = 0,
+    Paused = 1,
+    Playing = 2,
+}
+
+impl PlayState {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(PlayState::Stopped),
+            1 => Some(PlayState::Paused),
+            2 => Some(PlayState::Playing),
+            _ => None,
+        }
+    }
+}
+
+impl From<&PlayerStatus> for PlayState {
+    fn from(status: &PlayerStatus) -> Self {
+        match status {
+            PlayerStatus::Playing => PlayState::Playing,
+            PlayerStatus::Paused | PlayerStatus::Ready => PlayState::Paused,
+            _ => PlayState::Stopped,
+        }
+    }
+}
+
+/// 同期パケット
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncPacket {
+    pub packet_type: PacketType,
+    pub sequence: u16,
+    pub master_timestamp_us: u64,
+    pub cue_id: [u8; 16],
+    pub cue_index: Option<u16>,
+    pub position_us: u64,
+    pub state: PlayState,
+    pub speed: f32,
+}
+
+impl SyncPacket {
+    pub fn encode(&self) -> [u8; PACKET_SIZE] {
+        let mut buf = [0u8; PACKET_SIZE];
+        buf[0..4].copy_from_slice(&MAGIC);
+        buf[4] = PROTOCOL_VERSION;
+        buf[5] = self.packet_type as u8;
+        buf[6..8].copy_from_slice(&self.sequence.to_be_bytes());
+        buf[8..16].copy_from_slice(&self.master_timestamp_us.to_be_bytes());
+        buf[16..32].copy_from_slice(&self.cue_id);
+        buf[32..40].copy_from_slice(&self.position_us.to_be_bytes());
+        buf[40] = self.state as u8;
+        buf[41..45].copy_from_slice(&self.speed.to_be_bytes());
+        buf[45..47].copy_from_slice(&self.cue_index.unwrap_or(NO_CUE_INDEX).to_be_bytes());
+        buf
+    }
+
+    /// 受信データをデコード（不正なパケットはNone）
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() != PACKET_SIZE || buf[0..4] != MAGIC || buf[4] != PROTOCOL_VERSION {
+            return None;
+        }
+
+        let cue_index = u16::from_be_bytes([buf[45], buf[46]]);
+
+        Some(Self {
+            packet_type: PacketType::from_u8(buf[5])?,
+            sequence: u16::from_be_bytes([buf[6], buf[7]]),
+            master_timestamp_us: u64::from_be_bytes(buf[8..16].try_into().ok()?),
+            cue_id: buf[16..32].try_into().ok()?,
+            cue_index: (cue_index != NO_CUE_INDEX).then_some(cue_index),
+            position_us: u64::from_be_bytes(buf[32..40].try_into().ok()?),
+            state: PlayState::from_u8(buf[40])?,
+            speed: f32::from_be_bytes(buf[41..45].try_into().ok()?),
+        })
+    }
+
+    /// 再生位置（秒）
+    pub fn position(&self) -> f64 {
+        self.position_us as f64 / 1_000_000.0
+    }
+
+    /// 共有クロック上のエポック (us)
+    pub fn epoch_us(&self) -> u64 {
+        self.master_timestamp_us.saturating_sub(self.position_us)
+    }
+}
+
+/// Cue ID (UUID文字列) をパケット用の16バイトに変換
+///
+/// UUIDでないIDは全て0になり、スレーブはcue_indexで照合する
+pub fn cue_id_bytes(cue_id: &str) -> [u8; 16] {
+    uuid::Uuid::parse_str(cue_id)
+        .map(|u| *u.as_bytes())
+        .unwrap_or([0; 16])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_roundtrip() {
+        let packet = SyncPacket {
+            packet_type: PacketType::CueChange,
+            sequence: 65535,
+            master_timestamp_us: 1_700_000_000_000_000,
+            cue_id: cue_id_bytes("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            cue_index: Some(3),
+            position_us: 12_345_678,
+            state: PlayState::Playing,
+            speed: 1.0,
+        };
+
+        let buf = packet.encode();
+        assert_eq!(buf.len(), PACKET_SIZE);
+        assert_eq!(SyncPacket::decode(&buf), Some(packet));
+    }
+
+    #[test]
+    fn test_reject_invalid_packet() {
+        let mut buf = [0u8; PACKET_SIZE];
+        assert_eq!(SyncPacket::decode(&buf), None);
+        buf[0..4].copy_from_slice(&MAGIC);
+        buf[4] = PROTOCOL_VERSION + 1;
+        assert_eq!(SyncPacket::decode(&buf), None);
+        assert_eq!(SyncPacket::decode(&buf[..10]), None);
+    }
+}
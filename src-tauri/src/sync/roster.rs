@@ -0,0 +1,222 @@
+//! スレーブ一覧（マスター側）
+//!
+//! heartbeat を受信したスレーブを記録し、途絶・復帰をイベントとして通知する。
+//! 途絶したスレーブはしばらく一覧に残し、長く途絶えたものは外す。
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use crate::types::{AppEvent, SlaveHeartbeat};
+#[cfg(feature = "gui")]
+use crate::types::{SlavePeer, SlaveStatus};
+
+/// 途絶したスレーブを一覧から外すまでの時間（途絶の判定時間の倍数）
+const PRUNE_AFTER_TIMEOUTS: u32 = 10;
+
+struct SlaveEntry {
+    address: SocketAddr,
+    heartbeat: SlaveHeartbeat,
+    last_seen: Instant,
+    online: bool,
+}
+
+/// スレーブ一覧
+pub struct SlaveRoster {
+    timeout: Duration,
+    slaves: HashMap<String, SlaveEntry>,
+}
+
+impl SlaveRoster {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            slaves: HashMap::new(),
+        }
+    }
+
+    /// heartbeat 受信（新規・復帰時はイベントを返す）
+    ///
+    /// 同じアドレスの別のIDは再起動前の同じスレーブなので外す。
+    pub fn on_heartbeat(
+        &mut self,
+        address: SocketAddr,
+        heartbeat: SlaveHeartbeat,
+        now: Instant,
+    ) -> Option<AppEvent> {
+        self.slaves
+            .retain(|id, entry| entry.address != address || *id == heartbeat.slave_id);
+        let event = match self.slaves.get(&heartbeat.slave_id) {
+            Some(entry) if entry.online => None,
+            _ => Some(AppEvent::SlaveOnline {
+                slave_id: heartbeat.slave_id.clone(),
+                name: heartbeat.name.clone(),
+                address: address.to_string(),
+            }),
+        };
+
+        self.slaves.insert(
+            heartbeat.slave_id.clone(),
+            SlaveEntry {
+                address,
+                heartbeat,
+                last_seen: now,
+                online: true,
+            },
+        );
+
+        event
+    }
+
+    /// 途絶したスレーブを検出してイベントを返し、長く途絶えたスレーブを外す
+    pub fn check(&mut self, now: Instant) -> Vec<AppEvent> {
+        let timeout = self.timeout;
+        let events = self
+            .slaves
+            .values_mut()
+            .filter(|entry| entry.online && now.duration_since(entry.last_seen) > timeout)
+            .map(|entry| {
+                entry.online = false;
+                AppEvent::SlaveSilent {
+                    slave_id: entry.heartbeat.slave_id.clone(),
+                    name: entry.heartbeat.name.clone(),
+                    address: entry.address.to_string(),
+                    silent_ms: now.duration_since(entry.last_seen).as_millis() as u64,
+                }
+            })
+            .collect();
+
+        let prune_after = timeout * PRUNE_AFTER_TIMEOUTS;
+        self.slaves
+            .retain(|_, entry| now.duration_since(entry.last_seen) <= prune_after);
+        events
+    }
+
+    /// スレーブのアドレス
+    #[cfg(feature = "gui")]
+    pub fn address(&self, slave_id: &str) -> Option<SocketAddr> {
+        self.slaves.get(slave_id).map(|entry| entry.address)
+    }
+
+    /// 名前順のスレーブ一覧
+    #[cfg(feature = "gui")]
+    pub fn peers(&self, now: Instant) -> Vec<SlavePeer> {
+        let mut peers: Vec<SlavePeer> = self
+            .slaves
+            .values()
+            .map(|entry| {
+                let hb = &entry.heartbeat;
+                SlavePeer {
+                    slave_id: hb.slave_id.clone(),
+                    name: hb.name.clone(),
+                    address: entry.address.to_string(),
+                    online: entry.online,
+                    last_seen_ms: now.duration_since(entry.last_seen).as_millis() as u64,
+                    status: if entry.online {
+                        hb.status
+                    } else {
+                        SlaveStatus::Disconnected
+                    },
+                    cue_index: hb.cue_index,
+                    position: hb.position,
+                    position_delta_ms: hb.position_error_ms,
+                    dropped_frames: hb.dropped_frames,
+                    missing_media: hb.missing_media.clone(),
+                    blackout: hb.blackout,
+                    clock_synced: hb.clock_synced,
+                }
+            })
+            .collect();
+        peers.sort_by(|a, b| a.name.cmp(&b.name).then(a.slave_id.cmp(&b.slave_id)));
+        peers
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn online_count(&self) -> usize {
+        self.slaves.values().filter(|entry| entry.online).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heartbeat(slave_id: &str) -> SlaveHeartbeat {
+        SlaveHeartbeat {
+            slave_id: slave_id.to_string(),
+            name: format!("PC-{}", slave_id),
+            status: SlaveStatus::Synced,
+            cue_index: Some(0),
+            position: 1.0,
+            position_error_ms: Some(2.0),
+            dropped_frames: 0,
+            missing_media: Vec::new(),
+            blackout: false,
+            clock_synced: None,
+        }
+    }
+
+    #[test]
+    fn test_silent_and_recovered_slave() {
+        let mut roster = SlaveRoster::new(Duration::from_secs(3));
+        let addr: SocketAddr = "192.168.1.101:7000".parse().unwrap();
+        let now = Instant::now();
+
+        assert!(matches!(
+            roster.on_heartbeat(addr, heartbeat("a"), now),
+            Some(AppEvent::SlaveOnline { .. })
+        ));
+        assert!(roster.on_heartbeat(addr, heartbeat("a"), now).is_none());
+        assert!(roster.check(now + Duration::from_secs(1)).is_empty());
+
+        let later = now + Duration::from_secs(4);
+        assert!(matches!(
+            roster.check(later).as_slice(),
+            [AppEvent::SlaveSilent {
+                silent_ms: 4000,
+                ..
+            }]
+        ));
+        // 一度だけ通知
+        assert!(roster.check(later).is_empty());
+        assert_eq!(roster.peers(later)[0].status, SlaveStatus::Disconnected);
+        assert_eq!(roster.online_count(), 0);
+
+        assert!(matches!(
+            roster.on_heartbeat(addr, heartbeat("a"), later),
+            Some(AppEvent::SlaveOnline { .. })
+        ));
+        assert_eq!(roster.online_count(), 1);
+    }
+
+    #[test]
+    fn test_stale_slaves_are_pruned() {
+        let mut roster = SlaveRoster::new(Duration::from_secs(3));
+        let addr: SocketAddr = "192.168.1.101:7000".parse().unwrap();
+        let other: SocketAddr = "192.168.1.102:7000".parse().unwrap();
+        let now = Instant::now();
+
+        roster.on_heartbeat(addr, heartbeat("a"), now);
+        roster.on_heartbeat(other, heartbeat("b"), now);
+        // 再起動して別のIDで名乗ったスレーブは置き換える
+        roster.on_heartbeat(addr, heartbeat("c"), now);
+        let ids = |roster: &SlaveRoster| -> Vec<String> {
+            roster
+                .peers(now)
+                .into_iter()
+                .map(|peer| peer.slave_id)
+                .collect()
+        };
+        assert_eq!(ids(&roster), vec!["b", "c"]);
+
+        // 途絶直後は残し、長く途絶えたら外す
+        roster.on_heartbeat(addr, heartbeat("c"), now + Duration::from_secs(20));
+        assert_eq!(roster.check(now + Duration::from_secs(20)).len(), 1);
+        assert_eq!(ids(&roster), vec!["b", "c"]);
+        assert!(matches!(
+            roster.check(now + Duration::from_secs(31)).as_slice(),
+            [AppEvent::SlaveSilent { slave_id, .. }] if slave_id == "c"
+        ));
+        assert_eq!(ids(&roster), vec!["c"]);
+    }
+}
@@ -0,0 +1,1063 @@
+//! 同期スレーブ
+//!
+//! マスターのパケットを受信して同じCueを読み込み、再生位置を追従する。
+//! 大きなズレはseek（最小間隔で制限）、小さなズレは再生速度の微調整で補正する。
+//! ネットワーククロックに同期済みの場合は、マスターのエポックに合わせて再生する。
+//! 定期的にマスターへ heartbeat を返し、マスターからのリモート操作を受け付ける。
+
+use gstreamer as gst;
+use parking_lot::Mutex;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::{self, error::TryRecvError};
+use tracing::{debug, info, warn};
+
+use crate::error::{AppError, AppResult};
+use crate::local_config;
+use crate::playback;
+use crate::state::AppState;
+use crate::sync::control::ControlMessage;
+use crate::sync::net_clock::NetClockClient;
+use crate::sync::packet::{PacketType, PlayState, SyncPacket};
+use crate::types::{
+    AppEvent, NetClockStatus, SlaveCommand, SlaveHeartbeat, SlaveStatus, SyncConfig,
+};
+#[cfg(feature = "gui")]
+use crate::types::{SyncMode, SyncStatus};
+
+/// 受信待ちのタイムアウト（チェイス判定の周期を兼ねる）
+const RECV_TIMEOUT: Duration = Duration::from_millis(20);
+
+/// 速度補正の最大幅 (±5%)
+const MAX_RATE_ADJUST: f64 = 0.05;
+
+/// 速度補正でズレを解消するまでの目標時間（秒）
+const RATE_CORRECTION_SECS: f64 = 1.0;
+
+/// これ未満の速度変化は適用しない
+const RATE_EPSILON: f64 = 0.002;
+
+/// seek後に「seek中」と報告する時間
+const SEEK_SETTLE: Duration = Duration::from_millis(500);
+
+/// 受信バッファ（同期パケットと管理メッセージ）
+const RECV_BUFFER_SIZE: usize = 8192;
+
+/// スレーブがプレイヤーに対して行う操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlaveAction {
+    LoadCue(usize),
+    Play,
+    Pause,
+    Stop,
+    Seek(f64),
+    SetRate(f64),
+    /// 共有クロック上のエポック (us) に合わせて再生
+    PlayAt(u64),
+}
+
+/// ローカルプレイヤーの状態
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalPlayback {
+    pub cue_index: Option<usize>,
+    pub state: PlayState,
+    pub position: f64,
+}
+
+/// 最後に受信したマスターの状態
+struct MasterSnapshot {
+    packet: SyncPacket,
+    /// ローカルプロジェクト上の対応するCue
+    target_cue: Option<usize>,
+    received_at: Instant,
+}
+
+/// スレーブのチェイス判定
+///
+/// ソケットやプレイヤーに依存しない純粋なロジックで、受信パケットと
+/// ローカルの再生状態から次に行う操作を決める。
+pub struct SlaveChaser {
+    tolerance: f64,
+    seek_threshold: f64,
+    min_seek_interval: Duration,
+    timeout: Duration,
+    net_clock: bool,
+    /// 再生中にマスターより先行させる時間（秒）
+    latency_offset: f64,
+
+    master: Option<MasterSnapshot>,
+    packets_received: u64,
+    packets_lost: u64,
+    emergency_stopped: bool,
+    master_lost: bool,
+    last_seek: Option<Instant>,
+    seek_count: u64,
+    rate: f64,
+    position_error: Option<f64>,
+    clock_synced: bool,
+    /// 最後に合わせたエポック (us)
+    aligned_epoch: Option<u64>,
+    /// 次のtickで閾値に関係なく合わせ直す
+    resync_requested: bool,
+}
+
+impl SlaveChaser {
+    pub fn new(config: &SyncConfig) -> Self {
+        let frame = 1.0 / config.frame_rate.max(1.0);
+        Self {
+            tolerance: config.tolerance_frames * frame,
+            seek_threshold: config.seek_threshold_frames * frame,
+            min_seek_interval: Duration::from_millis(config.min_seek_interval_ms),
+            timeout: Duration::from_millis(config.timeout_ms),
+            net_clock: config.net_clock,
+            latency_offset: 0.0,
+            master: None,
+            packets_received: 0,
+            packets_lost: 0,
+            emergency_stopped: false,
+            master_lost: false,
+            last_seek: None,
+            seek_count: 0,
+            rate: 1.0,
+            position_error: None,
+            clock_synced: false,
+            aligned_epoch: None,
+            resync_requested: false,
+        }
+    }
+
+    /// 再生中にマスターより先行させる時間（秒）。負の値で遅らせる
+    pub fn set_latency_offset(&mut self, seconds: f64) {
+        self.latency_offset = seconds;
+    }
+
+    /// 次のtickで閾値・seek間隔に関係なくマスターへ合わせ直す
+    pub fn request_resync(&mut self) {
+        self.resync_requested = true;
+        self.aligned_epoch = None;
+        self.last_seek = None;
+    }
+
+    /// heartbeat で報告する同期状態
+    pub fn health(&self, now: Instant) -> SlaveStatus {
+        if self.master.is_none() {
+            SlaveStatus::Idle
+        } else if self.master_lost {
+            SlaveStatus::MasterLost
+        } else if self
+            .last_seek
+            .is_some_and(|t| now.duration_since(t) < SEEK_SETTLE)
+            || self
+                .position_error
+                .is_some_and(|e| e.abs() > self.seek_threshold)
+        {
+            SlaveStatus::Seeking
+        } else {
+            SlaveStatus::Synced
+        }
+    }
+
+    /// ネットワーククロックの同期状態を更新
+    pub fn set_clock_synced(&mut self, synced: bool) {
+        if !synced {
+            self.aligned_epoch = None;
+        }
+        self.clock_synced = synced;
+    }
+
+    /// パケット受信
+    pub fn on_packet(&mut self, packet: SyncPacket, target_cue: Option<usize>, now: Instant) {
+        if let Some(last) = &self.master {
+            let gap = packet.sequence.wrapping_sub(last.packet.sequence);
+            let fresh = now.duration_since(last.received_at) < self.timeout;
+            if gap == 0 || (gap >= 0x8000 && fresh) {
+                // 重複・順序の入れ替わったパケットは破棄
+                return;
+            }
+            if gap < 0x8000 && fresh {
+                self.packets_lost += u64::from(gap - 1);
+            }
+        }
+
+        self.packets_received += 1;
+
+        match packet.packet_type {
+            PacketType::EmergencyStop => self.emergency_stopped = true,
+            PacketType::CueChange => self.emergency_stopped = false,
+            PacketType::Sync => {}
+        }
+
+        self.master = Some(MasterSnapshot {
+            packet,
+            target_cue,
+            received_at: now,
+        });
+    }
+
+    /// 現在のマスター位置の推定値
+    fn master_position(&self, now: Instant) -> Option<f64> {
+        self.master.as_ref().map(|m| {
+            let mut position = m.packet.position();
+            if m.packet.state == PlayState::Playing {
+                position += now.duration_since(m.received_at).as_secs_f64() * m.packet.speed as f64;
+                position += self.latency_offset;
+            }
+            position
+        })
+    }
+
+    fn can_seek(&self, now: Instant) -> bool {
+        self.last_seek
+            .is_none_or(|t| now.duration_since(t) >= self.min_seek_interval)
+    }
+
+    fn seek(&mut self, position: f64, now: Instant) -> SlaveAction {
+        self.last_seek = Some(now);
+        self.seek_count += 1;
+        // seekすると再生速度は1.0に戻る
+        self.rate = 1.0;
+        SlaveAction::Seek(position.max(0.0))
+    }
+
+    fn align(&mut self, epoch: u64, now: Instant) -> SlaveAction {
+        self.last_seek = Some(now);
+        self.seek_count += 1;
+        self.rate = 1.0;
+        self.aligned_epoch = Some(epoch);
+        SlaveAction::PlayAt(epoch)
+    }
+
+    /// 周期的に呼び出し、プレイヤーに適用する操作を返す
+    pub fn tick(&mut self, now: Instant, local: LocalPlayback) -> Vec<SlaveAction> {
+        let Some(master) = &self.master else {
+            return Vec::new();
+        };
+
+        self.master_lost = now.duration_since(master.received_at) > self.timeout;
+
+        if self.emergency_stopped {
+            self.position_error = None;
+            self.aligned_epoch = None;
+            return if local.state != PlayState::Stopped {
+                vec![SlaveAction::Stop]
+            } else {
+                Vec::new()
+            };
+        }
+
+        // マスター途絶中は最後の状態のまま再生を継続
+        if self.master_lost {
+            self.position_error = None;
+            return Vec::new();
+        }
+
+        let master_state = master.packet.state;
+        // 先行させる分だけエポックを早める
+        let master_epoch = (master.packet.epoch_us() as i64
+            - (self.latency_offset * 1_000_000.0) as i64)
+            .max(0) as u64;
+        let target_cue = master.target_cue;
+        let master_position = self.master_position(now).unwrap_or(0.0);
+
+        // Cueが違う、または停止済みのパイプラインは読み込み直す
+        if let Some(cue) = target_cue {
+            let needs_load = local.cue_index != Some(cue)
+                || (local.state == PlayState::Stopped && master_state != PlayState::Stopped);
+            if needs_load {
+                self.rate = 1.0;
+                self.position_error = None;
+                self.aligned_epoch = None;
+                return vec![SlaveAction::LoadCue(cue)];
+            }
+        }
+
+        let error = local.position - master_position;
+        self.position_error = (local.state != PlayState::Stopped).then_some(error);
+        let force = std::mem::take(&mut self.resync_requested);
+
+        if master_state != PlayState::Playing {
+            self.aligned_epoch = None;
+        }
+
+        let mut actions = Vec::new();
+        match master_state {
+            PlayState::Stopped => {
+                if local.state != PlayState::Stopped {
+                    self.rate = 1.0;
+                    actions.push(SlaveAction::Stop);
+                }
+            }
+            PlayState::Paused => {
+                if local.state == PlayState::Playing {
+                    actions.push(SlaveAction::Pause);
+                }
+                if force || (error.abs() > self.tolerance && self.can_seek(now)) {
+                    actions.push(self.seek(master_position, now));
+                }
+            }
+            PlayState::Playing if self.net_clock && self.clock_synced => {
+                // 同じクロック上なので、エポックが変わった（マスターがseekした）時だけ合わせ直す
+                let drift = self
+                    .aligned_epoch
+                    .map(|epoch| epoch.abs_diff(master_epoch) as f64 / 1_000_000.0);
+                if local.state != PlayState::Playing
+                    || (drift.is_none_or(|d| d > self.tolerance) && self.can_seek(now))
+                {
+                    actions.push(self.align(master_epoch, now));
+                }
+            }
+            PlayState::Playing => {
+                if local.state != PlayState::Playing {
+                    // 位置を合わせてから再生開始
+                    if error.abs() > self.tolerance {
+                        actions.push(self.seek(master_position, now));
+                    }
+                    actions.push(SlaveAction::Play);
+                } else if force || error.abs() > self.seek_threshold {
+                    if self.can_seek(now) {
+                        actions.push(self.seek(master_position, now));
+                    }
+                } else {
+                    let rate = if error.abs() > self.tolerance {
+                        // 進んでいれば遅く、遅れていれば速く
+                        (1.0 - error / RATE_CORRECTION_SECS)
+                            .clamp(1.0 - MAX_RATE_ADJUST, 1.0 + MAX_RATE_ADJUST)
+                    } else {
+                        1.0
+                    };
+                    if (rate - self.rate).abs() > RATE_EPSILON || (rate == 1.0 && self.rate != 1.0)
+                    {
+                        self.rate = rate;
+                        actions.push(SlaveAction::SetRate(rate));
+                    }
+                }
+            }
+        }
+
+        actions
+    }
+
+    pub fn master_lost(&self) -> bool {
+        self.master_lost
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+}
+
+/// スレーブスレッドと共有する状態
+struct SlaveShared {
+    chaser: SlaveChaser,
+    master_address: Option<SocketAddr>,
+    net_clock: Option<NetClockStatus>,
+    blackout: bool,
+}
+
+/// 見つからないメディアの確認結果
+///
+/// ハートビートごとに全ファイルを確認しないよう、プロジェクトの変更
+/// （ProjectChangedイベント）・メディアルートの変更・Cueの読み込み時にだけ確認し直す。
+struct MediaCheck {
+    events: broadcast::Receiver<AppEvent>,
+    stale: bool,
+    cue_index: Option<usize>,
+    media_root: Option<String>,
+    missing: Vec<String>,
+    current_missing: bool,
+}
+
+impl MediaCheck {
+    fn new(state: &AppState) -> Self {
+        Self {
+            events: state.events.subscribe(),
+            stale: true,
+            cue_index: None,
+            media_root: None,
+            missing: Vec::new(),
+            current_missing: false,
+        }
+    }
+
+    /// 次の確認でファイルを確認し直す（Reload等）
+    fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    /// 見つからないメディアと、現在のCueのファイルが欠けているか
+    fn get(&mut self, state: &AppState, cue_index: Option<usize>) -> (Vec<String>, bool) {
+        loop {
+            match self.events.try_recv() {
+                Ok(AppEvent::ProjectChanged { .. }) | Err(TryRecvError::Lagged(_)) => {
+                    self.stale = true
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        let media_root = state.local_config.lock().media_root.clone();
+
+        if self.stale || cue_index != self.cue_index || media_root != self.media_root {
+            let (missing, current_missing) = SyncSlave::missing_media(state, cue_index);
+            self.missing = missing;
+            self.current_missing = current_missing;
+            self.stale = false;
+            self.cue_index = cue_index;
+            self.media_root = media_root;
+        }
+        (self.missing.clone(), self.current_missing)
+    }
+}
+
+/// このスレーブの識別情報
+struct SlaveIdentity {
+    id: String,
+    name: String,
+}
+
+impl SlaveIdentity {
+    /// IDはローカル設定から読み、無ければ生成して保存する
+    ///
+    /// 再起動しても同じIDで名乗るため、マスターの一覧に同じPCが重複しない。
+    fn load(state: &AppState) -> Self {
+        let existing = state.local_config.lock().slave_id.clone();
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                // ファイルへの書き込み中はロックを保持しない
+                let mut config = state.local_config.lock().clone();
+                let id = uuid::Uuid::new_v4().to_string();
+                config.slave_id = Some(id.clone());
+                if let Err(e) = local_config::save(&config) {
+                    warn!("[SyncSlave] Failed to save slave id: {}", e);
+                }
+                state.local_config.lock().slave_id = Some(id.clone());
+                id
+            }
+        };
+        let name = std::env::var("COMPUTERNAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| format!("slave-{}", &id[..8]));
+        Self { id, name }
+    }
+}
+
+/// 同期スレーブ
+pub struct SyncSlave {
+    #[cfg(feature = "gui")]
+    shared: Arc<Mutex<SlaveShared>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SyncSlave {
+    /// 受信ポートを開く
+    ///
+    /// 同じPCの別のアプリ（モニター用のスレーブ等）と同じポートで
+    /// マルチキャスト・ブロードキャストを受けられるよう SO_REUSEADDR を付ける。
+    fn bind(port: u16) -> std::io::Result<UdpSocket> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port).into())?;
+        Ok(socket.into())
+    }
+
+    pub fn start(config: &SyncConfig, state: AppState) -> AppResult<Self> {
+        let socket = Self::bind(config.listen_port)?;
+        socket.set_read_timeout(Some(RECV_TIMEOUT))?;
+
+        if let Some(group) = &config.multicast_group {
+            let group: Ipv4Addr = group
+                .parse()
+                .map_err(|_| AppError::Sync(format!("Invalid multicast group: {}", group)))?;
+            socket.join_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED)?;
+        }
+
+        let shared = Arc::new(Mutex::new(SlaveShared {
+            chaser: SlaveChaser::new(config),
+            master_address: None,
+            net_clock: None,
+            blackout: false,
+        }));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let config = config.clone();
+            let shared = shared.clone();
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name("sync-slave".to_string())
+                .spawn(move || Self::run(state, socket, config, shared, running))
+                .map_err(AppError::Io)?
+        };
+
+        info!("[SyncSlave] Listening on port {}", config.listen_port);
+
+        Ok(Self {
+            #[cfg(feature = "gui")]
+            shared,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run(
+        state: AppState,
+        socket: UdpSocket,
+        config: SyncConfig,
+        shared: Arc<Mutex<SlaveShared>>,
+        running: Arc<AtomicBool>,
+    ) {
+        let mut buf = [0u8; RECV_BUFFER_SIZE];
+        let mut was_lost = false;
+        let mut net_clock: Option<NetClockClient> = None;
+        let identity = SlaveIdentity::load(&state);
+        let heartbeat_interval = Duration::from_millis(config.heartbeat_interval_ms.max(100));
+        let mut last_heartbeat: Option<Instant> = None;
+        let mut media = MediaCheck::new(&state);
+
+        info!("[SyncSlave] Slave id {} ({})", identity.id, identity.name);
+
+        while running.load(Ordering::Relaxed) {
+            match socket.recv_from(&mut buf) {
+                Ok((len, addr)) => {
+                    if let Some(packet) = SyncPacket::decode(&buf[..len]) {
+                        // 最初のパケットでマスターのクロックに接続
+                        if config.net_clock && net_clock.is_none() {
+                            let address = config
+                                .clock_address
+                                .clone()
+                                .unwrap_or_else(|| addr.ip().to_string());
+                            let client = NetClockClient::connect(&address, config.clock_port);
+                            if let Err(e) = playback::set_clock(&state, Some(client.clock())) {
+                                warn!("[SyncSlave] Failed to set network clock: {}", e);
+                            }
+                            net_clock = Some(client);
+                        }
+
+                        let target_cue = Self::resolve_cue(&state, &packet);
+                        let mut shared = shared.lock();
+                        shared.chaser.on_packet(packet, target_cue, Instant::now());
+                        shared.master_address = Some(addr);
+                    } else if let Some(ControlMessage::Command { slave_id, command }) =
+                        ControlMessage::decode(&buf[..len])
+                    {
+                        if slave_id == identity.id {
+                            if command == SlaveCommand::Reload {
+                                media.invalidate();
+                            }
+                            Self::handle_command(&state, &shared, command);
+                            // 操作結果をすぐに報告
+                            last_heartbeat = None;
+                        }
+                    } else {
+                        debug!("[SyncSlave] Ignored invalid packet from {}", addr);
+                    }
+                }
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => {
+                    warn!("[SyncSlave] Receive error: {}", e);
+                    std::thread::sleep(RECV_TIMEOUT);
+                }
+            }
+
+            let local = Self::local_playback(&state);
+            let latency_offset = state.local_config.lock().latency_offset_ms / 1000.0;
+            let (actions, lost) = {
+                let mut shared = shared.lock();
+                shared.chaser.set_latency_offset(latency_offset);
+                if let Some(client) = net_clock.as_mut() {
+                    client.poll();
+                    shared.chaser.set_clock_synced(client.is_synced());
+                    shared.net_clock = Some(client.status());
+                }
+                let actions = shared.chaser.tick(Instant::now(), local);
+                (actions, shared.chaser.master_lost())
+            };
+
+            if lost != was_lost {
+                if lost {
+                    warn!("[SyncSlave] Master lost, continuing in last state");
+                } else {
+                    info!("[SyncSlave] Master recovered");
+                }
+                was_lost = lost;
+            }
+
+            for action in actions {
+                if let Err(e) = Self::apply(&state, action) {
+                    warn!("[SyncSlave] Failed to apply {:?}: {}", action, e);
+                }
+            }
+
+            let master_address = shared.lock().master_address;
+            if let Some(master_address) = master_address {
+                if last_heartbeat.is_none_or(|t| t.elapsed() >= heartbeat_interval) {
+                    let heartbeat = Self::build_heartbeat(&state, &shared, &identity, &mut media);
+                    let message = ControlMessage::Heartbeat(heartbeat);
+                    if let Err(e) = socket.send_to(&message.encode(), master_address) {
+                        warn!("[SyncSlave] Failed to send heartbeat: {}", e);
+                    }
+                    last_heartbeat = Some(Instant::now());
+                }
+            }
+        }
+
+        if net_clock.is_some() {
+            let _ = playback::set_clock(&state, None);
+        }
+        if shared.lock().blackout {
+            let _ = playback::set_blackout(&state, false);
+        }
+
+        debug!("[SyncSlave] Receive thread finished");
+    }
+
+    /// マスターからのリモート操作
+    fn handle_command(state: &AppState, shared: &Mutex<SlaveShared>, command: SlaveCommand) {
+        info!("[SyncSlave] Remote command: {:?}", command);
+        let result = match command {
+            SlaveCommand::Reload => {
+                shared.lock().chaser.request_resync();
+                match Self::local_playback(state).cue_index {
+                    Some(index) => playback::load_cue(state, index),
+                    None => Ok(()),
+                }
+            }
+            SlaveCommand::Resync => {
+                shared.lock().chaser.request_resync();
+                Ok(())
+            }
+            SlaveCommand::Blackout { enabled } => {
+                playback::set_blackout(state, enabled).map(|_| shared.lock().blackout = enabled)
+            }
+        };
+        if let Err(e) = result {
+            warn!("[SyncSlave] Remote command failed: {}", e);
+        }
+    }
+
+    fn build_heartbeat(
+        state: &AppState,
+        shared: &Mutex<SlaveShared>,
+        identity: &SlaveIdentity,
+        media: &mut MediaCheck,
+    ) -> SlaveHeartbeat {
+        let local = Self::local_playback(state);
+        let (missing_media, current_missing) = media.get(state, local.cue_index);
+        let dropped_frames = playback::dropped_frames(state);
+
+        let shared = shared.lock();
+        let status = if current_missing {
+            SlaveStatus::FileNotFound
+        } else {
+            shared.chaser.health(Instant::now())
+        };
+
+        SlaveHeartbeat {
+            slave_id: identity.id.clone(),
+            name: identity.name.clone(),
+            status,
+            cue_index: local.cue_index.map(|i| i as i32),
+            position: local.position,
+            position_error_ms: shared.chaser.position_error.map(|e| e * 1000.0),
+            dropped_frames,
+            missing_media,
+            blackout: shared.blackout,
+            clock_synced: shared.net_clock.as_ref().map(|c| c.synced),
+        }
+    }
+
+    /// プロジェクト内で見つからないメディアファイル
+    ///
+    /// 2つ目の値は現在のCueのファイルが欠けているか
+    fn missing_media(state: &AppState, cue_index: Option<usize>) -> (Vec<String>, bool) {
+        let media_root = state.local_config.lock().media_root.clone();
+        let items: Vec<(usize, String)> = match state.project.lock().as_ref() {
+            Some(project) => project
+                .cues
+                .iter()
+                .enumerate()
+                .flat_map(|(i, cue)| cue.items.iter().map(move |item| (i, item.path.clone())))
+                .collect(),
+            None => return (Vec::new(), false),
+        };
+
+        let mut missing: Vec<String> = Vec::new();
+        let mut current_missing = false;
+        for (index, path) in items {
+            if local_config::resolve_media_path(&path, media_root.as_deref()).exists() {
+                continue;
+            }
+            current_missing |= Some(index) == cue_index;
+            if !missing.contains(&path) {
+                missing.push(path);
+            }
+        }
+
+        (missing, current_missing)
+    }
+
+    /// マスターのCueに対応するローカルのCueを探す
+    ///
+    /// IDで照合し、見つからなければインデックスで照合する
+    fn resolve_cue(state: &AppState, packet: &SyncPacket) -> Option<usize> {
+        let project_guard = state.project.lock();
+        let cues = &project_guard.as_ref()?.cues;
+
+        if packet.cue_id != [0; 16] {
+            let uuid = uuid::Uuid::from_bytes(packet.cue_id).to_string();
+            if let Some(index) = cues.iter().position(|c| c.id == uuid) {
+                return Some(index);
+            }
+        }
+
+        packet
+            .cue_index
+            .map(usize::from)
+            .filter(|&index| index < cues.len())
+    }
+
+    fn local_playback(state: &AppState) -> LocalPlayback {
+        let player_state = playback::player_state(state);
+        LocalPlayback {
+            cue_index: usize::try_from(player_state.current_cue_index).ok(),
+            state: PlayState::from(&player_state.status),
+            position: player_state.current_time,
+        }
+    }
+
+    fn apply(state: &AppState, action: SlaveAction) -> AppResult<()> {
+        debug!("[SyncSlave] {:?}", action);
+        match action {
+            SlaveAction::LoadCue(index) => playback::load_cue(state, index),
+            SlaveAction::Play => playback::play(state),
+            SlaveAction::Pause => playback::pause(state),
+            SlaveAction::Stop => playback::stop(state),
+            SlaveAction::Seek(position) => playback::seek(state, position),
+            SlaveAction::SetRate(rate) => playback::set_rate(state, rate),
+            SlaveAction::PlayAt(epoch) => {
+                playback::play_at_epoch(state, gst::ClockTime::from_useconds(epoch))
+            }
+        }
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> SyncStatus {
+        let shared = self.shared.lock();
+        let chaser = &shared.chaser;
+        let now = Instant::now();
+
+        SyncStatus {
+            mode: SyncMode::Slave,
+            running: self.running.load(Ordering::Relaxed),
+            packets_sent: 0,
+            slaves_online: 0,
+            packets_received: chaser.packets_received,
+            packets_lost: chaser.packets_lost,
+            master_address: shared.master_address.map(|a| a.to_string()),
+            master_cue_index: chaser
+                .master
+                .as_ref()
+                .and_then(|m| m.packet.cue_index)
+                .map(i32::from),
+            master_position: chaser.master_position(now),
+            position_error_ms: chaser.position_error.map(|e| e * 1000.0),
+            last_packet_age_ms: chaser
+                .master
+                .as_ref()
+                .map(|m| now.duration_since(m.received_at).as_millis() as u64),
+            master_lost: chaser.master_lost,
+            seek_count: chaser.seek_count,
+            playback_rate: chaser.rate(),
+            net_clock: shared.net_clock.clone(),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[SyncSlave] Stopped");
+        }
+    }
+}
+
+impl Drop for SyncSlave {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Cue, HistoryStatus, MediaItem, MediaType, Project, ProjectChange};
+
+    fn packet(
+        packet_type: PacketType,
+        sequence: u16,
+        position: f64,
+        state: PlayState,
+    ) -> SyncPacket {
+        SyncPacket {
+            packet_type,
+            sequence,
+            master_timestamp_us: 0,
+            cue_id: [0; 16],
+            cue_index: Some(0),
+            position_us: (position * 1_000_000.0) as u64,
+            state,
+            speed: 1.0,
+        }
+    }
+
+    fn local(state: PlayState, position: f64) -> LocalPlayback {
+        LocalPlayback {
+            cue_index: Some(0),
+            state,
+            position,
+        }
+    }
+
+    #[test]
+    fn test_load_then_start_playing() {
+        let mut chaser = SlaveChaser::new(&SyncConfig::default());
+        let now = Instant::now();
+        chaser.on_packet(
+            packet(PacketType::CueChange, 0, 5.0, PlayState::Playing),
+            Some(0),
+            now,
+        );
+
+        let idle = LocalPlayback {
+            cue_index: None,
+            state: PlayState::Stopped,
+            position: 0.0,
+        };
+        assert_eq!(chaser.tick(now, idle), vec![SlaveAction::LoadCue(0)]);
+        assert_eq!(
+            chaser.tick(now, local(PlayState::Paused, 0.0)),
+            vec![SlaveAction::Seek(5.0), SlaveAction::Play]
+        );
+    }
+
+    #[test]
+    fn test_rate_nudge_and_seek_limit() {
+        let mut chaser = SlaveChaser::new(&SyncConfig::default());
+        let now = Instant::now();
+        chaser.on_packet(
+            packet(PacketType::Sync, 0, 10.0, PlayState::Playing),
+            Some(0),
+            now,
+        );
+
+        // 3フレーム進んでいる → 減速
+        match chaser.tick(now, local(PlayState::Playing, 10.1)).as_slice() {
+            [SlaveAction::SetRate(rate)] => assert!(*rate < 1.0 && *rate >= 0.95),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // 許容範囲内に戻れば等速
+        assert_eq!(
+            chaser.tick(now, local(PlayState::Playing, 10.0)),
+            vec![SlaveAction::SetRate(1.0)]
+        );
+
+        // 大きなズレはseek、ただし最小間隔内は再seekしない
+        assert_eq!(
+            chaser.tick(now, local(PlayState::Playing, 12.0)),
+            vec![SlaveAction::Seek(10.0)]
+        );
+        assert!(chaser.tick(now, local(PlayState::Playing, 12.0)).is_empty());
+    }
+
+    #[test]
+    fn test_net_clock_aligns_on_epoch_change() {
+        let config = SyncConfig {
+            net_clock: true,
+            timeout_ms: 10_000,
+            ..Default::default()
+        };
+        let mut chaser = SlaveChaser::new(&config);
+        let now = Instant::now();
+        let mut sync = packet(PacketType::Sync, 0, 2.0, PlayState::Playing);
+        sync.master_timestamp_us = 100_000_000;
+        chaser.on_packet(sync.clone(), Some(0), now);
+
+        // クロック未同期の間は位置で追従
+        assert_eq!(
+            chaser.tick(now, local(PlayState::Paused, 0.0)),
+            vec![SlaveAction::Seek(2.0), SlaveAction::Play]
+        );
+
+        chaser.set_clock_synced(true);
+        let later = now + Duration::from_millis(config.min_seek_interval_ms);
+        assert_eq!(
+            chaser.tick(later, local(PlayState::Playing, 2.0)),
+            vec![SlaveAction::PlayAt(98_000_000)]
+        );
+        assert!(chaser
+            .tick(later, local(PlayState::Playing, 2.0))
+            .is_empty());
+
+        // マスターがseekしてエポックが変わったら合わせ直す
+        sync.sequence = 1;
+        sync.position_us = 30_000_000;
+        let after_seek = later + Duration::from_millis(config.min_seek_interval_ms);
+        chaser.on_packet(sync, Some(0), after_seek);
+        assert_eq!(
+            chaser.tick(after_seek, local(PlayState::Playing, 2.0)),
+            vec![SlaveAction::PlayAt(70_000_000)]
+        );
+    }
+
+    #[test]
+    fn test_latency_offset_leads_master() {
+        let config = SyncConfig {
+            net_clock: true,
+            timeout_ms: 10_000,
+            ..Default::default()
+        };
+        let mut chaser = SlaveChaser::new(&config);
+        chaser.set_latency_offset(0.5);
+        let now = Instant::now();
+        let mut sync = packet(PacketType::Sync, 0, 2.0, PlayState::Playing);
+        sync.master_timestamp_us = 100_000_000;
+        chaser.on_packet(sync, Some(0), now);
+
+        assert_eq!(
+            chaser.tick(now, local(PlayState::Paused, 0.0)),
+            vec![SlaveAction::Seek(2.5), SlaveAction::Play]
+        );
+
+        chaser.set_clock_synced(true);
+        let later = now + Duration::from_millis(config.min_seek_interval_ms);
+        assert_eq!(
+            chaser.tick(later, local(PlayState::Playing, 2.5)),
+            vec![SlaveAction::PlayAt(97_500_000)]
+        );
+    }
+
+    #[test]
+    fn test_resync_ignores_thresholds() {
+        let mut chaser = SlaveChaser::new(&SyncConfig::default());
+        let now = Instant::now();
+        chaser.on_packet(
+            packet(PacketType::Sync, 0, 10.0, PlayState::Paused),
+            Some(0),
+            now,
+        );
+        assert!(chaser.tick(now, local(PlayState::Paused, 10.01)).is_empty());
+        assert_eq!(chaser.health(now), SlaveStatus::Synced);
+
+        chaser.request_resync();
+        assert_eq!(
+            chaser.tick(now, local(PlayState::Paused, 10.01)),
+            vec![SlaveAction::Seek(10.0)]
+        );
+        assert_eq!(chaser.health(now), SlaveStatus::Seeking);
+    }
+
+    #[test]
+    fn test_listen_port_is_shared() {
+        let first = SyncSlave::bind(0).unwrap();
+        let port = first.local_addr().unwrap().port();
+        let second = SyncSlave::bind(port).unwrap();
+        assert_eq!(second.local_addr().unwrap().port(), port);
+    }
+
+    #[test]
+    fn test_saved_slave_id_is_reused() {
+        let state = AppState::new();
+        state.local_config.lock().slave_id = Some("pc-a".to_string());
+        assert_eq!(SlaveIdentity::load(&state).id, "pc-a");
+        assert_eq!(SlaveIdentity::load(&state).id, "pc-a");
+    }
+
+    #[test]
+    fn test_packet_loss_and_master_timeout() {
+        let config = SyncConfig::default();
+        let mut chaser = SlaveChaser::new(&config);
+        let now = Instant::now();
+        chaser.on_packet(
+            packet(PacketType::Sync, 65534, 1.0, PlayState::Playing),
+            Some(0),
+            now,
+        );
+        chaser.on_packet(
+            packet(PacketType::Sync, 2, 1.0, PlayState::Playing),
+            Some(0),
+            now,
+        );
+        assert_eq!(chaser.packets_lost, 3);
+
+        let later = now + Duration::from_millis(config.timeout_ms + 1);
+        assert!(chaser
+            .tick(later, local(PlayState::Playing, 0.0))
+            .is_empty());
+        assert!(chaser.master_lost());
+    }
+
+    #[test]
+    fn test_media_check_is_cached() {
+        let dir = std::env::temp_dir().join(format!("slave-media-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.mp4"), b"a").unwrap();
+
+        let item = |name: &str| MediaItem {
+            id: name.to_string(),
+            media_type: MediaType::Video,
+            name: name.to_string(),
+            path: name.to_string(),
+            output_id: "screen".to_string(),
+            ..Default::default()
+        };
+        let state = AppState::new();
+        state.local_config.lock().media_root = Some(dir.to_string_lossy().into_owned());
+        *state.project.lock() = Some(Project {
+            cues: vec![Cue {
+                id: "c1".to_string(),
+                name: "Opening".to_string(),
+                items: vec![item("a.mp4"), item("b.mp4")],
+                duration: 10.0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let mut media = MediaCheck::new(&state);
+        assert_eq!(
+            media.get(&state, Some(0)),
+            (vec!["b.mp4".to_string()], true)
+        );
+
+        // ファイルが揃っても変更の通知まではファイルを確認しない
+        std::fs::write(dir.join("b.mp4"), b"b").unwrap();
+        assert_eq!(media.get(&state, Some(0)).0, vec!["b.mp4".to_string()]);
+
+        state.events.emit(AppEvent::ProjectChanged {
+            change: ProjectChange::Edit,
+            label: None,
+            history: HistoryStatus {
+                undo_label: None,
+                redo_label: None,
+                undo_count: 0,
+                redo_count: 0,
+            },
+        });
+        assert_eq!(media.get(&state, Some(0)), (Vec::new(), false));
+
+        std::fs::remove_file(dir.join("a.mp4")).unwrap();
+        media.invalidate();
+        assert_eq!(media.get(&state, None), (vec!["a.mp4".to_string()], false));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+//! タイムコードチェイス
+//!
+//! 外部タイムコードを受け取り、CuePlayerの再生位置をどう追従させるかを決定する。
+//! プレイヤーへの操作は呼び出し側が行い、ここでは判断のみを担当する。
+
+use std::time::{Duration, Instant};
+
+use crate::types::{ChaseLockState, FrameRate, LtcChaseConfig, Timecode};
+
+/// seek後、次の位置判定までの待機時間
+const SEEK_HOLDOFF: Duration = Duration::from_millis(500);
+
+/// 検出したフレームレートがこのフレーム数続いたら設定より優先する
+const RATE_CONFIRM_FRAMES: u32 = 10;
+
+/// チェイスの結果としてプレイヤーに行う操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChaseAction {
+    None,
+    /// 指定位置へseekし、`play` が true なら再生、false なら一時停止
+    Seek {
+        position: f64,
+        play: bool,
+    },
+    Pause,
+}
+
+/// 最後に受信したタイムコード
+#[derive(Debug, Clone, Copy)]
+struct Received {
+    timecode: Timecode,
+    /// Cue内の位置（秒）
+    position: f64,
+    received_at: Instant,
+}
+
+/// タイムコードチェイスの状態機械
+pub struct Chaser {
+    frame_rate: FrameRate,
+    start_timecode: Timecode,
+    start_offset: f64,
+    seek_threshold: f64,
+    freewheel: Duration,
+    lock_frames: u32,
+
+    last: Option<Received>,
+    /// 連続して正しく受信したフレーム数
+    consecutive: u32,
+    /// タイムコードが進行中か（同じ値の繰り返しは停止とみなす）
+    advancing: bool,
+    lock: ChaseLockState,
+    last_seek: Option<Instant>,
+    last_error: Option<f64>,
+    seek_count: u64,
+    /// 入力から検出したフレームレートと連続して同じだったフレーム数
+    detected: Option<(FrameRate, u32)>,
+}
+
+impl Chaser {
+    pub fn new(config: &LtcChaseConfig) -> Self {
+        Self {
+            frame_rate: config.frame_rate,
+            start_timecode: config.start_timecode,
+            start_offset: config.start_timecode.to_seconds(config.frame_rate),
+            seek_threshold: config.seek_threshold_ms / 1000.0,
+            freewheel: Duration::from_millis(config.freewheel_ms),
+            lock_frames: config.lock_frames.max(1),
+            last: None,
+            consecutive: 0,
+            advancing: false,
+            lock: ChaseLockState::Unlocked,
+            last_seek: None,
+            last_error: None,
+            seek_count: 0,
+            detected: None,
+        }
+    }
+
+    /// 入力から検出したフレームレートを1フレーム分受け取る
+    ///
+    /// 同じレートが続けば設定のレートの代わりに使い、切り替えたらtrueを返す。
+    pub fn on_frame_rate(&mut self, detected: FrameRate) -> bool {
+        let count = match self.detected {
+            Some((rate, count)) if rate == detected => count + 1,
+            _ => 1,
+        };
+        self.detected = Some((detected, count));
+        if count < RATE_CONFIRM_FRAMES || detected == self.frame_rate {
+            return false;
+        }
+
+        self.frame_rate = detected;
+        self.start_offset = self.start_timecode.to_seconds(detected);
+        // 前のレートで数えたフレームとは比べられないのでロックをやり直す
+        self.last = None;
+        self.consecutive = 0;
+        true
+    }
+
+    /// タイムコードを1フレーム受信
+    pub fn on_timecode(&mut self, timecode: Timecode, now: Instant) {
+        let frames = timecode.to_frames(self.frame_rate);
+
+        match self.last {
+            Some(prev) => {
+                let prev_frames = prev.timecode.to_frames(self.frame_rate);
+                if frames == prev_frames + 1 {
+                    self.consecutive += 1;
+                    self.advancing = true;
+                } else if frames == prev_frames {
+                    self.consecutive += 1;
+                    self.advancing = false;
+                } else {
+                    // ジャンプ（ロケート）: ロックをやり直す
+                    self.consecutive = 1;
+                    self.advancing = false;
+                }
+            }
+            None => {
+                self.consecutive = 1;
+                self.advancing = false;
+            }
+        }
+
+        self.last = Some(Received {
+            timecode,
+            position: timecode.to_seconds(self.frame_rate) - self.start_offset,
+            received_at: now,
+        });
+    }
+
+    /// 現在のプレイヤー状態から次の操作を決定
+    pub fn tick(
+        &mut self,
+        now: Instant,
+        player_position: Option<f64>,
+        playing: bool,
+    ) -> ChaseAction {
+        let Some(last) = self.last else {
+            self.lock = ChaseLockState::Unlocked;
+            return ChaseAction::None;
+        };
+
+        let frame_duration = 1.0 / self.frame_rate.fps();
+        let since = now.saturating_duration_since(last.received_at);
+
+        // 3フレーム以上届かなければ途絶とみなす
+        if since.as_secs_f64() > frame_duration * 3.0 {
+            self.last_error = None;
+            if since <= self.freewheel && self.lock == ChaseLockState::Locked {
+                self.lock = ChaseLockState::Freewheel;
+            }
+            if self.lock == ChaseLockState::Freewheel && since <= self.freewheel {
+                return ChaseAction::None;
+            }
+            let was_chasing = self.lock != ChaseLockState::Unlocked;
+            self.lock = ChaseLockState::Unlocked;
+            self.consecutive = 0;
+            return if was_chasing && playing {
+                ChaseAction::Pause
+            } else {
+                ChaseAction::None
+            };
+        }
+
+        if self.consecutive < self.lock_frames {
+            self.lock = ChaseLockState::Locking;
+            return ChaseAction::None;
+        }
+        self.lock = ChaseLockState::Locked;
+
+        let target = if self.advancing {
+            last.position + since.as_secs_f64()
+        } else {
+            last.position
+        };
+        let position = player_position.unwrap_or(0.0);
+        let error = position - target.max(0.0);
+        self.last_error = Some(error);
+
+        // Cue開始前のタイムコードは先頭で待機
+        if target < 0.0 {
+            return if playing || position > frame_duration {
+                self.seek(now, 0.0, false)
+            } else {
+                ChaseAction::None
+            };
+        }
+
+        let holdoff = self
+            .last_seek
+            .is_some_and(|t| now.saturating_duration_since(t) < SEEK_HOLDOFF);
+
+        // 進行中なら再生、停止中（同じ値の繰り返し）なら一時停止で追従
+        let play = self.advancing;
+        if play != playing || (error.abs() > self.seek_threshold && !holdoff) {
+            return self.seek(now, target, play);
+        }
+
+        ChaseAction::None
+    }
+
+    fn seek(&mut self, now: Instant, position: f64, play: bool) -> ChaseAction {
+        self.last_seek = Some(now);
+        self.seek_count += 1;
+        ChaseAction::Seek { position, play }
+    }
+
+    /// チェイスに使っているフレームレート
+    pub fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    pub fn lock_state(&self) -> ChaseLockState {
+        self.lock
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn last_timecode(&self) -> Option<Timecode> {
+        self.last.map(|r| r.timecode)
+    }
+
+    /// 再生位置 - タイムコード位置（秒）
+    #[cfg(feature = "gui")]
+    pub fn position_error(&self) -> Option<f64> {
+        self.last_error
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn seek_count(&self) -> u64 {
+        self.seek_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> LtcChaseConfig {
+        LtcChaseConfig {
+            frame_rate: FrameRate::Fps25,
+            start_timecode: Timecode {
+                hours: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn feed(chaser: &mut Chaser, start: Instant, first_frame: u64, count: u64) -> Instant {
+        let rate = FrameRate::Fps25;
+        let mut now = start;
+        for i in 0..count {
+            now = start + Duration::from_millis(40 * i);
+            chaser.on_timecode(Timecode::from_frames(first_frame + i, rate), now);
+        }
+        now
+    }
+
+    #[test]
+    fn test_lock_and_seek_to_timecode() {
+        let mut chaser = Chaser::new(&config());
+        let start = Instant::now();
+        // 01:00:10:00 から
+        let first = 25 * 3610;
+
+        let now = feed(&mut chaser, start, first, 2);
+        assert_eq!(chaser.tick(now, Some(0.0), false), ChaseAction::None);
+        assert_eq!(chaser.lock_state(), ChaseLockState::Locking);
+
+        let now = feed(&mut chaser, start, first, 3);
+        match chaser.tick(now, Some(0.0), false) {
+            ChaseAction::Seek { position, play } => {
+                assert!((position - 10.08).abs() < 1e-6);
+                assert!(play);
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+        assert_eq!(chaser.lock_state(), ChaseLockState::Locked);
+
+        // 許容範囲内なら何もしない
+        assert_eq!(chaser.tick(now, Some(10.1), true), ChaseAction::None);
+    }
+
+    #[test]
+    fn test_freewheel_then_unlock() {
+        let mut chaser = Chaser::new(&config());
+        let start = Instant::now();
+        let now = feed(&mut chaser, start, 25 * 3600, 5);
+        chaser.tick(now, Some(0.16), true);
+        assert_eq!(chaser.lock_state(), ChaseLockState::Locked);
+
+        let now = now + Duration::from_millis(1000);
+        assert_eq!(chaser.tick(now, Some(1.16), true), ChaseAction::None);
+        assert_eq!(chaser.lock_state(), ChaseLockState::Freewheel);
+
+        let now = now + Duration::from_millis(1500);
+        assert_eq!(chaser.tick(now, Some(2.66), true), ChaseAction::Pause);
+        assert_eq!(chaser.lock_state(), ChaseLockState::Unlocked);
+    }
+
+    #[test]
+    fn test_follow_detected_frame_rate() {
+        let mut chaser = Chaser::new(&config());
+
+        // 一時的な誤検出では切り替えない
+        for _ in 0..RATE_CONFIRM_FRAMES - 1 {
+            assert!(!chaser.on_frame_rate(FrameRate::Fps30));
+        }
+        assert!(!chaser.on_frame_rate(FrameRate::Fps25));
+        assert_eq!(chaser.frame_rate(), FrameRate::Fps25);
+
+        for _ in 0..RATE_CONFIRM_FRAMES - 1 {
+            assert!(!chaser.on_frame_rate(FrameRate::Fps30));
+        }
+        assert!(chaser.on_frame_rate(FrameRate::Fps30));
+        assert_eq!(chaser.frame_rate(), FrameRate::Fps30);
+        assert!(!chaser.on_frame_rate(FrameRate::Fps30));
+
+        // 30fpsの01:00:10:15は開始から10.5秒
+        let start = Instant::now();
+        let first = 30 * 3610 + 15;
+        let mut now = start;
+        for i in 0..5 {
+            now = start + Duration::from_millis(33 * i);
+            chaser.on_timecode(Timecode::from_frames(first + i, FrameRate::Fps30), now);
+        }
+        match chaser.tick(now, Some(0.0), false) {
+            ChaseAction::Seek { position, .. } => {
+                assert!((position - 10.5 - 4.0 / 30.0).abs() < 1e-6)
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! チェイススレッド
+//!
+//! 入力（LTC・MTC等）から受け取ったタイムコードをChaserに渡し、専用スレッドで
+//! CuePlayerの再生位置を追従させる。入力側は `ChaseFeed` でタイムコードを渡す。
+
+use gstreamer as gst;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
+
+use crate::error::{AppError, AppResult};
+use crate::pipeline::cue_player::CuePlayer;
+use crate::timecode::chase::{ChaseAction, Chaser};
+#[cfg(feature = "gui")]
+use crate::types::LtcChaseStatus;
+use crate::types::{FrameRate, LtcChaseConfig, Timecode};
+
+/// チェイス判定の周期
+const CHASE_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 入力コールバックとチェイススレッドで共有する状態
+struct ChaseShared {
+    chaser: Chaser,
+    frames_decoded: u64,
+    detected_frame_rate: Option<FrameRate>,
+}
+
+/// 入力側からタイムコードを渡すハンドル
+#[derive(Clone)]
+pub struct ChaseFeed {
+    label: &'static str,
+    configured_rate: FrameRate,
+    shared: Arc<Mutex<ChaseShared>>,
+}
+
+impl ChaseFeed {
+    pub fn new(label: &'static str, config: &LtcChaseConfig) -> Self {
+        Self {
+            label,
+            configured_rate: config.frame_rate,
+            shared: Arc::new(Mutex::new(ChaseShared {
+                chaser: Chaser::new(config),
+                frames_decoded: 0,
+                detected_frame_rate: None,
+            })),
+        }
+    }
+
+    /// タイムコードを1フレーム受信
+    pub fn on_timecode(&self, timecode: Timecode, detected: Option<FrameRate>) {
+        let mut shared = self.shared.lock();
+        shared.frames_decoded += 1;
+
+        if let Some(rate) = detected {
+            shared.detected_frame_rate = Some(rate);
+            if shared.chaser.on_frame_rate(rate) {
+                warn!(
+                    "[{}] Chasing at detected {:?} instead of configured {:?}",
+                    self.label,
+                    shared.chaser.frame_rate(),
+                    self.configured_rate
+                );
+            }
+        }
+
+        shared.chaser.on_timecode(timecode, Instant::now());
+    }
+}
+
+/// チェイススレッド
+pub struct ChaseRunner {
+    feed: ChaseFeed,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ChaseRunner {
+    /// チェイススレッドを起動（busには入力パイプラインのエラーを流す）
+    pub fn start(
+        thread_name: &str,
+        feed: ChaseFeed,
+        player: Arc<Mutex<Option<CuePlayer>>>,
+        bus: Option<gst::Bus>,
+    ) -> AppResult<Self> {
+        let running = Arc::new(AtomicBool::new(true));
+        let thread = {
+            let feed = feed.clone();
+            let running = running.clone();
+            std::thread::Builder::new()
+                .name(thread_name.to_string())
+                .spawn(move || Self::run(feed, player, running, bus))
+                .map_err(AppError::Io)?
+        };
+
+        Ok(Self {
+            feed,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run(
+        feed: ChaseFeed,
+        player: Arc<Mutex<Option<CuePlayer>>>,
+        running: Arc<AtomicBool>,
+        bus: Option<gst::Bus>,
+    ) {
+        let label = feed.label;
+        let mut last_lock = None;
+
+        while running.load(Ordering::Relaxed) {
+            if let Some(bus) = &bus {
+                Self::drain_bus(label, bus);
+            }
+
+            {
+                let player_guard = player.lock();
+                if let Some(player) = player_guard.as_ref() {
+                    let playing = player.state() == gst::State::Playing;
+                    let position = player.position();
+
+                    let (action, lock) = {
+                        let mut shared = feed.shared.lock();
+                        let action = shared.chaser.tick(Instant::now(), position, playing);
+                        (action, shared.chaser.lock_state())
+                    };
+
+                    if last_lock != Some(lock) {
+                        info!("[{}] Lock state: {:?}", label, lock);
+                        last_lock = Some(lock);
+                    }
+
+                    if let Err(e) = Self::apply(label, player, action) {
+                        warn!("[{}] Failed to apply {:?}: {}", label, action, e);
+                    }
+                }
+            }
+
+            std::thread::sleep(CHASE_INTERVAL);
+        }
+
+        debug!("[{}] Chase thread finished", label);
+    }
+
+    /// チェイス結果をプレイヤーに適用
+    fn apply(label: &str, player: &CuePlayer, action: ChaseAction) -> AppResult<()> {
+        match action {
+            ChaseAction::None => {}
+            ChaseAction::Seek { position, play } => {
+                debug!("[{}] Seek to {:.3}s (play={})", label, position, play);
+                let playing = player.state() == gst::State::Playing;
+                if play {
+                    player.seek(position)?;
+                    if !playing {
+                        player.play()?;
+                    }
+                } else {
+                    if playing {
+                        player.pause()?;
+                    }
+                    player.seek(position)?;
+                }
+            }
+            ChaseAction::Pause => {
+                debug!("[{}] Timecode lost, pausing", label);
+                player.pause()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn drain_bus(label: &str, bus: &gst::Bus) {
+        while let Some(msg) = bus.pop_filtered(&[gst::MessageType::Error, gst::MessageType::Eos]) {
+            match msg.view() {
+                gst::MessageView::Error(err) => {
+                    error!(
+                        "[{}] Input error: {} ({:?})",
+                        label,
+                        err.error(),
+                        err.debug()
+                    );
+                }
+                gst::MessageView::Eos(_) => {
+                    info!("[{}] Input reached end of stream", label);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 現在のチェイス状態
+    #[cfg(feature = "gui")]
+    pub fn status(&self) -> LtcChaseStatus {
+        let shared = self.feed.shared.lock();
+        LtcChaseStatus {
+            running: self.running.load(Ordering::Relaxed),
+            lock: shared.chaser.lock_state(),
+            timecode: shared.chaser.last_timecode(),
+            frame_rate: shared.chaser.frame_rate(),
+            detected_frame_rate: shared.detected_frame_rate,
+            position_error_ms: shared.chaser.position_error().map(|e| e * 1000.0),
+            frames_decoded: shared.frames_decoded,
+            seek_count: shared.chaser.seek_count(),
+        }
+    }
+
+    /// チェイススレッドを停止
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            info!("[{}] Stopped", self.feed.label);
+        }
+    }
+}
+
+impl Drop for ChaseRunner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
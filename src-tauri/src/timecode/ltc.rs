@@ -0,0 +1,444 @@
+//! SMPTE LTC エンコーダ / デコーダ
+//!
+//! デコーダはオーディオサンプルからバイフェーズマーク符号を復調し、80bitのLTCフレームを
+//! 取り出す。ビット周期はゼロクロス間隔から適応的に推定するため、24/25/29.97DF/30fps の
+//! いずれにも設定なしで追従できる。
+//! エンコーダは指定位置からのLTC波形を連続生成する。
+
+use crate::types::{FrameRate, Timecode};
+
+/// LTCフレームのビット数
+pub const LTC_FRAME_BITS: u32 = 80;
+
+/// 同期ワード (bit 64-79, LSBがbit 64)
+const SYNC_WORD: u128 = 0xBFFC;
+
+/// デコードされたLTCフレーム
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LtcFrame {
+    pub timecode: Timecode,
+    pub drop_frame: bool,
+    #[allow(dead_code)]
+    pub user_bits: u32,
+    /// フレーム周期から測定したフレームレート（未測定時はNone）
+    pub measured_fps: Option<f64>,
+}
+
+#[cfg(feature = "gui")]
+impl LtcFrame {
+    /// 測定値とドロップフレームフラグから推定したフレームレート
+    pub fn detected_frame_rate(&self) -> Option<FrameRate> {
+        self.measured_fps
+            .and_then(|fps| FrameRate::detect(fps, self.drop_frame))
+    }
+}
+
+/// バイフェーズマーク復調によるLTCデコーダ
+#[cfg(feature = "gui")]
+pub struct LtcDecoder {
+    sample_rate: u32,
+    /// 信号のピーク追従値（ヒステリシス閾値の基準）
+    envelope: f32,
+    /// 現在の信号極性
+    high: bool,
+    /// 直前のエッジからのサンプル数
+    samples_since_edge: u32,
+    /// 推定ビット周期（サンプル数）
+    bit_period: f32,
+    /// 短いパルス1つ目を受信済み（ビット1の前半）
+    half_bit_pending: bool,
+    /// 受信ビットのシフトレジスタ（bit 0 が最古）
+    shift: u128,
+    bits_since_sync: u32,
+    samples_total: u64,
+    last_sync_sample: Option<u64>,
+    /// フレーム周期の移動平均（サンプル数）
+    frame_period: Option<f32>,
+}
+
+#[cfg(feature = "gui")]
+impl LtcDecoder {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            envelope: 0.0,
+            high: false,
+            samples_since_edge: 0,
+            bit_period: Self::initial_bit_period(sample_rate),
+            half_bit_pending: false,
+            shift: 0,
+            bits_since_sync: 0,
+            samples_total: 0,
+            last_sync_sample: None,
+            frame_period: None,
+        }
+    }
+
+    /// 24fps(1920bps)と30fps(2400bps)の中間から推定を開始
+    fn initial_bit_period(sample_rate: u32) -> f32 {
+        sample_rate as f32 / 2150.0
+    }
+
+    /// モノラルのサンプル列を処理し、デコードできたフレームを返す
+    pub fn process(&mut self, samples: &[f32]) -> Vec<LtcFrame> {
+        let mut frames = Vec::new();
+
+        for &sample in samples {
+            self.samples_total += 1;
+            self.samples_since_edge = self.samples_since_edge.saturating_add(1);
+
+            // ピーク追従（約20msで減衰）
+            let level = sample.abs();
+            self.envelope = if level > self.envelope {
+                level
+            } else {
+                self.envelope * 0.9995
+            };
+
+            // 無音・ノイズは無視
+            if self.envelope < 0.01 {
+                continue;
+            }
+
+            let threshold = self.envelope * 0.25;
+            let edge = if self.high {
+                sample < -threshold
+            } else {
+                sample > threshold
+            };
+
+            if edge {
+                self.high = !self.high;
+                let interval = self.samples_since_edge as f32;
+                self.samples_since_edge = 0;
+                if let Some(frame) = self.on_edge(interval) {
+                    frames.push(frame);
+                }
+            }
+        }
+
+        frames
+    }
+
+    /// エッジ間隔からビットを判定
+    fn on_edge(&mut self, interval: f32) -> Option<LtcFrame> {
+        if interval > self.bit_period * 1.5 || interval < self.bit_period * 0.25 {
+            // 信号の途切れ・ノイズ: ビット境界を見失ったので同期をやり直す
+            self.half_bit_pending = false;
+            self.bits_since_sync = 0;
+            return None;
+        }
+
+        if interval > self.bit_period * 0.75 {
+            // 長いパルス = ビット0
+            self.half_bit_pending = false;
+            self.adapt_bit_period(interval);
+            self.push_bit(false)
+        } else if self.half_bit_pending {
+            // 短いパルス2つ = ビット1
+            self.half_bit_pending = false;
+            self.adapt_bit_period(interval * 2.0);
+            self.push_bit(true)
+        } else {
+            self.half_bit_pending = true;
+            None
+        }
+    }
+
+    fn adapt_bit_period(&mut self, measured: f32) {
+        let min = self.sample_rate as f32 / 2600.0;
+        let max = self.sample_rate as f32 / 1800.0;
+        self.bit_period = (self.bit_period * 0.9 + measured * 0.1).clamp(min, max);
+    }
+
+    fn push_bit(&mut self, bit: bool) -> Option<LtcFrame> {
+        self.shift = (self.shift >> 1) | ((bit as u128) << (LTC_FRAME_BITS - 1));
+        self.bits_since_sync = self.bits_since_sync.saturating_add(1);
+
+        if self.bits_since_sync < LTC_FRAME_BITS || (self.shift >> 64) & 0xFFFF != SYNC_WORD {
+            return None;
+        }
+        self.bits_since_sync = 0;
+
+        let measured_fps = self.update_frame_period(self.samples_total);
+
+        let (timecode, drop_frame, user_bits) = unpack_frame(self.shift)?;
+        Some(LtcFrame {
+            timecode,
+            drop_frame,
+            user_bits,
+            measured_fps,
+        })
+    }
+
+    /// 同期ワード間隔からフレームレートを測定
+    fn update_frame_period(&mut self, end_sample: u64) -> Option<f64> {
+        if let Some(last) = self.last_sync_sample.replace(end_sample) {
+            let period = (end_sample - last) as f32;
+            // 20-35fps の範囲外（ドロップアウト後など）は無視
+            let min = self.sample_rate as f32 / 35.0;
+            let max = self.sample_rate as f32 / 20.0;
+            if (min..=max).contains(&period) {
+                self.frame_period = Some(match self.frame_period {
+                    Some(avg) => avg * 0.8 + period * 0.2,
+                    None => period,
+                });
+            }
+        }
+
+        self.frame_period
+            .map(|period| self.sample_rate as f64 / period as f64)
+    }
+}
+
+/// バイフェーズマーク変調によるLTCエンコーダ
+///
+/// `seek()` で指定した位置（Cue先頭からの秒数）から、連続したLTC波形を生成する。
+pub struct LtcEncoder {
+    frame_rate: FrameRate,
+    sample_rate: u32,
+    amplitude: f32,
+    /// Cue先頭に対応する通算フレーム数
+    start_frame: u64,
+    /// 生成開始位置（秒）
+    origin: f64,
+    /// 生成開始位置からのサンプル数
+    sample_index: u64,
+    high: bool,
+    /// 直前のサンプルの (フレーム, ビット, 後半か)
+    last_half: Option<(u64, u32, bool)>,
+    /// 現在フレームのビット列キャッシュ
+    current_frame: Option<(u64, u128)>,
+}
+
+impl LtcEncoder {
+    pub fn new(
+        frame_rate: FrameRate,
+        start_timecode: Timecode,
+        sample_rate: u32,
+        amplitude: f32,
+    ) -> Self {
+        Self {
+            frame_rate,
+            sample_rate,
+            amplitude,
+            start_frame: start_timecode.to_frames(frame_rate),
+            origin: 0.0,
+            sample_index: 0,
+            high: false,
+            last_half: None,
+            current_frame: None,
+        }
+    }
+
+    /// 生成位置を移動（Cue先頭からの秒数）
+    pub fn seek(&mut self, position: f64) {
+        self.origin = position.max(0.0);
+        self.sample_index = 0;
+        self.last_half = None;
+    }
+
+    /// 次に生成するサンプルの位置（秒）
+    pub fn position(&self) -> f64 {
+        self.origin + self.sample_index as f64 / self.sample_rate as f64
+    }
+
+    /// モノラルのLTC波形を生成
+    pub fn fill(&mut self, out: &mut [f32]) {
+        let bits_per_frame = LTC_FRAME_BITS as f64;
+
+        for sample in out.iter_mut() {
+            let t = self.position() * self.frame_rate.fps();
+            let frame = t.floor() as u64;
+            let bit_pos = (t - frame as f64) * bits_per_frame;
+            let bit = (bit_pos.floor() as u32).min(LTC_FRAME_BITS - 1);
+            let second_half = bit_pos - bit as f64 >= 0.5;
+
+            let half = (frame, bit, second_half);
+            if self.last_half != Some(half) {
+                // ビット先頭では必ず反転、ビット1は中央でも反転
+                if !second_half || (self.frame_bits(frame) >> bit) & 1 == 1 {
+                    self.high = !self.high;
+                }
+                self.last_half = Some(half);
+            }
+
+            *sample = if self.high {
+                self.amplitude
+            } else {
+                -self.amplitude
+            };
+            self.sample_index += 1;
+        }
+    }
+
+    fn frame_bits(&mut self, frame: u64) -> u128 {
+        match self.current_frame {
+            Some((cached, bits)) if cached == frame => bits,
+            _ => {
+                let timecode = Timecode::from_frames(self.start_frame + frame, self.frame_rate);
+                let bits = pack_frame(timecode, self.frame_rate, 0);
+                self.current_frame = Some((frame, bits));
+                bits
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+fn field(reg: u128, start: u32, len: u32) -> u8 {
+    ((reg >> start) & ((1u128 << len) - 1)) as u8
+}
+
+/// タイムコードを80bitのフレームに詰める
+pub fn pack_frame(timecode: Timecode, rate: FrameRate, user_bits: u32) -> u128 {
+    let mut reg: u128 = SYNC_WORD << 64;
+    let mut put = |start: u32, value: u32| reg |= (value as u128) << start;
+
+    put(0, timecode.frames as u32 % 10);
+    put(8, timecode.frames as u32 / 10);
+    put(10, rate.is_drop_frame() as u32);
+    put(16, timecode.seconds as u32 % 10);
+    put(24, timecode.seconds as u32 / 10);
+    put(32, timecode.minutes as u32 % 10);
+    put(40, timecode.minutes as u32 / 10);
+    put(48, timecode.hours as u32 % 10);
+    put(56, timecode.hours as u32 / 10);
+    for group in 0..8 {
+        put(4 + group * 8, (user_bits >> (group * 4)) & 0xF);
+    }
+
+    // 極性補正ビット: フレーム内の1の数を偶数にして、毎フレーム同じ極性で始まるようにする
+    let polarity_bit = if rate == FrameRate::Fps25 { 59 } else { 27 };
+    if reg.count_ones() % 2 == 1 {
+        reg |= 1u128 << polarity_bit;
+    }
+
+    reg
+}
+
+/// 80bitのフレームからタイムコード・DFフラグ・ユーザービットを取り出す
+#[cfg(feature = "gui")]
+pub fn unpack_frame(reg: u128) -> Option<(Timecode, bool, u32)> {
+    let timecode = Timecode {
+        frames: field(reg, 0, 4) + 10 * field(reg, 8, 2),
+        seconds: field(reg, 16, 4) + 10 * field(reg, 24, 3),
+        minutes: field(reg, 32, 4) + 10 * field(reg, 40, 3),
+        hours: field(reg, 48, 4) + 10 * field(reg, 56, 2),
+    };
+
+    if timecode.frames >= 30
+        || timecode.seconds >= 60
+        || timecode.minutes >= 60
+        || timecode.hours >= 24
+    {
+        return None;
+    }
+
+    let drop_frame = field(reg, 10, 1) == 1;
+    let user_bits = (0..8).fold(0u32, |acc, group| {
+        acc | (field(reg, 4 + group * 8, 4) as u32) << (group * 4)
+    });
+
+    Some((timecode, drop_frame, user_bits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip_at(rate: FrameRate) {
+        let sample_rate = 48000;
+        let start = Timecode {
+            hours: 1,
+            minutes: 0,
+            seconds: 59,
+            frames: 20,
+        };
+        let first = start.to_frames(rate);
+
+        let mut encoder = LtcEncoder::new(rate, start, sample_rate, 0.5);
+        let mut samples = vec![0.0f32; (sample_rate as f64 * 20.0 / rate.fps()) as usize];
+        encoder.fill(&mut samples);
+
+        let mut decoder = LtcDecoder::new(sample_rate);
+        let decoded = decoder.process(&samples);
+
+        // 最終ビットは次フレームの先頭エッジで確定するため、最終フレームは出てこない
+        assert_eq!(decoded.len(), 19, "decoded {} frames", decoded.len());
+        for (i, frame) in decoded.iter().enumerate() {
+            assert_eq!(
+                frame.timecode,
+                Timecode::from_frames(first + i as u64, rate)
+            );
+            assert_eq!(frame.drop_frame, rate.is_drop_frame());
+        }
+        assert_eq!(decoded.last().unwrap().detected_frame_rate(), Some(rate));
+    }
+
+    #[test]
+    fn test_roundtrip_all_frame_rates() {
+        roundtrip_at(FrameRate::Fps24);
+        roundtrip_at(FrameRate::Fps25);
+        roundtrip_at(FrameRate::Fps2997Df);
+        roundtrip_at(FrameRate::Fps30);
+    }
+
+    #[test]
+    fn test_decode_drop_frame_minute() {
+        let rate = FrameRate::Fps2997Df;
+        let start = Timecode {
+            hours: 0,
+            minutes: 9,
+            seconds: 59,
+            frames: 25,
+        };
+        let mut samples = vec![0.0f32; 48000 / 2];
+        LtcEncoder::new(rate, start, 48000, 0.5).fill(&mut samples);
+
+        // 10分ごとの分はフレームを飛ばさず、それ以外の分は00・01を飛ばす
+        let decoded: Vec<String> = LtcDecoder::new(48000)
+            .process(&samples)
+            .iter()
+            .map(|f| f.timecode.to_string())
+            .collect();
+        assert_eq!(decoded[4..7], ["00:09:59:29", "00:10:00:00", "00:10:00:01"]);
+
+        let start = Timecode {
+            minutes: 10,
+            seconds: 59,
+            ..start
+        };
+        LtcEncoder::new(rate, start, 48000, 0.5).fill(&mut samples);
+        let decoded: Vec<String> = LtcDecoder::new(48000)
+            .process(&samples)
+            .iter()
+            .map(|f| f.timecode.to_string())
+            .collect();
+        assert_eq!(decoded[4..6], ["00:10:59:29", "00:11:00:02"]);
+    }
+
+    #[test]
+    fn test_encoder_seek() {
+        let rate = FrameRate::Fps25;
+        let mut encoder = LtcEncoder::new(rate, Timecode::default(), 48000, 0.5);
+        encoder.seek(10.0);
+        let mut samples = vec![0.0f32; 48000];
+        encoder.fill(&mut samples);
+
+        let decoded = LtcDecoder::new(48000).process(&samples);
+        assert_eq!(decoded[0].timecode.to_string(), "00:00:10:00");
+    }
+
+    #[test]
+    fn test_drop_frame_timecode_roundtrip() {
+        let rate = FrameRate::Fps2997Df;
+        // 00:01:00:00 と 00:01:00:01 は存在しない
+        let tc = Timecode::from_frames(1800, rate);
+        assert_eq!(tc.to_string(), "00:01:00:02");
+        assert_eq!(tc.to_frames(rate), 1800);
+        let tc = Timecode::from_frames(17982, rate);
+        assert_eq!(tc.to_string(), "00:10:00:00");
+    }
+}
@@ -0,0 +1,71 @@
+//! LTCチェイス
+//!
+//! LtcInputで受信したタイムコードをChaseRunnerに渡し、
+//! CuePlayerの再生位置を追従させる。タイムコードトリガーも同じ入力で判定する。
+
+use tracing::{info, warn};
+
+use crate::control::TimecodeTriggers;
+use crate::error::AppResult;
+use crate::state::AppState;
+use crate::timecode::chase_runner::{ChaseFeed, ChaseRunner};
+use crate::timecode::ltc_input::LtcInput;
+use crate::types::{LtcChaseConfig, LtcChaseStatus};
+
+/// LTCチェイスサービス
+pub struct LtcChase {
+    input: LtcInput,
+    runner: ChaseRunner,
+}
+
+impl LtcChase {
+    /// LTC入力を開始し、チェイススレッドを起動
+    pub fn start(config: &LtcChaseConfig, state: &AppState) -> AppResult<Self> {
+        let feed = ChaseFeed::new("LtcChase", config);
+
+        let input = {
+            let feed = feed.clone();
+            let state = state.clone();
+            let frame_rate = config.frame_rate;
+            let triggers = TimecodeTriggers::new();
+            LtcInput::new(&config.source, config.channel, move |frame| {
+                let detected = frame.detected_frame_rate();
+                feed.on_timecode(frame.timecode, detected);
+                let rate = detected.unwrap_or(frame_rate);
+                if let Err(e) = triggers.on_timecode(&state, frame.timecode, rate) {
+                    warn!(
+                        "[LtcChase] Timecode trigger at {} failed: {}",
+                        frame.timecode, e
+                    );
+                }
+            })?
+        };
+        input.start()?;
+
+        let runner = ChaseRunner::start("ltc-chase", feed, state.player.clone(), input.bus())?;
+
+        info!(
+            "[LtcChase] Started ({:?}, start={})",
+            config.frame_rate, config.start_timecode
+        );
+
+        Ok(Self { input, runner })
+    }
+
+    /// 現在のチェイス状態
+    pub fn status(&self) -> LtcChaseStatus {
+        self.runner.status()
+    }
+
+    /// 入力とチェイススレッドを停止
+    pub fn stop(&mut self) {
+        self.input.stop();
+        self.runner.stop();
+    }
+}
+
+impl Drop for LtcChase {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,290 @@
+//! LTC入力パイプライン
+//!
+//! オーディオ入力デバイスまたはオーディオファイルからサンプルを取り出し、
+//! LtcDecoderでデコードしたフレームをコールバックに渡す。
+//!
+//! ```text
+//! [audiosrc | filesrc → decodebin] → audioconvert → audioresample → appsink(F32LE, 48kHz)
+//! ```
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_audio as gst_audio;
+use tracing::{debug, error, info};
+
+use crate::audio::source::create_audio_source;
+use crate::error::{AppError, AppResult};
+use crate::timecode::ltc::{LtcDecoder, LtcFrame};
+use crate::types::LtcInputSource;
+
+/// デコード時のサンプルレート
+const SAMPLE_RATE: u32 = 48000;
+
+/// LTC入力
+pub struct LtcInput {
+    pipeline: gst::Pipeline,
+}
+
+impl LtcInput {
+    /// 入力パイプラインを構築（開始は `start()`）
+    ///
+    /// `channel` はマルチチャンネル入力のうちLTCが載っているチャンネル (0始まり)
+    pub fn new<F>(source: &LtcInputSource, channel: u32, mut on_frame: F) -> AppResult<Self>
+    where
+        F: FnMut(LtcFrame) + Send + 'static,
+    {
+        let pipeline = gst::Pipeline::new();
+
+        let convert = gst::ElementFactory::make("audioconvert")
+            .build()
+            .map_err(|e| AppError::GStreamer(format!("Failed to create audioconvert: {:?}", e)))?;
+        let resample = gst::ElementFactory::make("audioresample")
+            .build()
+            .map_err(|e| AppError::GStreamer(format!("Failed to create audioresample: {:?}", e)))?;
+
+        let caps = gst::Caps::builder("audio/x-raw")
+            .field("format", "F32LE")
+            .field("layout", "interleaved")
+            .field("rate", SAMPLE_RATE as i32)
+            .build();
+
+        // ファイル入力でも実時間で流すため sync=true
+        let appsink = gst_app::AppSink::builder()
+            .caps(&caps)
+            .sync(true)
+            .max_buffers(8)
+            .build();
+
+        pipeline
+            .add_many([&convert, &resample, appsink.upcast_ref()])
+            .map_err(|e| AppError::Pipeline(format!("Failed to add elements: {:?}", e)))?;
+        gst::Element::link_many([&convert, &resample, appsink.upcast_ref()])
+            .map_err(|e| AppError::Pipeline(format!("Failed to link LTC input: {:?}", e)))?;
+
+        match source {
+            LtcInputSource::Device {
+                audio_driver,
+                audio_device,
+            } => {
+                let src = create_audio_source(audio_driver.as_ref(), audio_device.as_deref())
+                    .map_err(|e| {
+                        AppError::GStreamer(format!("Failed to create audio source: {:?}", e))
+                    })?;
+                pipeline
+                    .add(&src)
+                    .map_err(|e| AppError::Pipeline(format!("Failed to add source: {:?}", e)))?;
+                src.link(&convert).map_err(|e| {
+                    AppError::Pipeline(format!("Failed to link source to convert: {:?}", e))
+                })?;
+                info!("[LtcInput] Using audio input device {:?}", audio_device);
+            }
+            LtcInputSource::File { path } => {
+                Self::add_file_source(&pipeline, path, &convert)?;
+                info!("[LtcInput] Using audio file '{}'", path);
+            }
+        }
+
+        let mut decoder = LtcDecoder::new(SAMPLE_RATE);
+        let channel = channel as usize;
+
+        appsink.set_callbacks(
+            gst_app::AppSinkCallbacks::builder()
+                .new_sample(move |sink| {
+                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                    let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
+                    let channels = sample
+                        .caps()
+                        .and_then(|caps| gst_audio::AudioInfo::from_caps(caps).ok())
+                        .map(|info| info.channels() as usize)
+                        .unwrap_or(1)
+                        .max(1);
+                    let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
+
+                    // インターリーブされたサンプルから対象チャンネルのみ取り出す
+                    let offset = channel.min(channels - 1) * 4;
+                    let samples: Vec<f32> = map
+                        .as_slice()
+                        .chunks_exact(channels * 4)
+                        .map(|frame| {
+                            f32::from_le_bytes([
+                                frame[offset],
+                                frame[offset + 1],
+                                frame[offset + 2],
+                                frame[offset + 3],
+                            ])
+                        })
+                        .collect();
+
+                    for frame in decoder.process(&samples) {
+                        on_frame(frame);
+                    }
+
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .build(),
+        );
+
+        Ok(Self { pipeline })
+    }
+
+    /// filesrc → decodebin を追加し、オーディオパッドを convert に接続
+    fn add_file_source(
+        pipeline: &gst::Pipeline,
+        path: &str,
+        convert: &gst::Element,
+    ) -> AppResult<()> {
+        let src = gst::ElementFactory::make("filesrc")
+            .property("location", path)
+            .build()
+            .map_err(|e| AppError::GStreamer(format!("Failed to create filesrc: {:?}", e)))?;
+        let decode = gst::ElementFactory::make("decodebin")
+            .build()
+            .map_err(|e| AppError::GStreamer(format!("Failed to create decodebin: {:?}", e)))?;
+
+        pipeline
+            .add_many([&src, &decode])
+            .map_err(|e| AppError::Pipeline(format!("Failed to add elements: {:?}", e)))?;
+        src.link(&decode)
+            .map_err(|e| AppError::Pipeline(format!("Failed to link src to decode: {:?}", e)))?;
+
+        let convert_weak = convert.downgrade();
+        decode.connect_pad_added(move |_, src_pad| {
+            let Some(convert) = convert_weak.upgrade() else {
+                return;
+            };
+            let is_audio = src_pad
+                .current_caps()
+                .and_then(|caps| caps.structure(0).map(|s| s.name().starts_with("audio/")))
+                .unwrap_or(false);
+            if !is_audio {
+                return;
+            }
+
+            let Some(sink_pad) = convert.static_pad("sink") else {
+                return;
+            };
+            if sink_pad.is_linked() {
+                debug!("[LtcInput] Ignoring additional audio stream");
+                return;
+            }
+            if let Err(e) = src_pad.link(&sink_pad) {
+                error!("[LtcInput] Failed to link decoded audio: {:?}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    pub fn start(&self) -> AppResult<()> {
+        self.pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|e| AppError::Pipeline(format!("Failed to start LTC input: {:?}", e)))?;
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        let _ = self.pipeline.set_state(gst::State::Null);
+    }
+
+    /// エラー・EOS監視用のバス
+    pub fn bus(&self) -> Option<gst::Bus> {
+        self.pipeline.bus()
+    }
+}
+
+impl Drop for LtcInput {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timecode::ltc::LtcEncoder;
+    use crate::types::{FrameRate, Timecode};
+    use parking_lot::Mutex;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// インターリーブされたサンプルを16bit PCMのWAVとして書き出す
+    fn write_wav(path: &Path, channels: u16, samples: &[f32]) {
+        let data_len = samples.len() as u32 * 2;
+        let mut wav = Vec::with_capacity(44 + data_len as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&channels.to_le_bytes());
+        wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        wav.extend_from_slice(&(SAMPLE_RATE * channels as u32 * 2).to_le_bytes());
+        wav.extend_from_slice(&(channels * 2).to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            wav.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+        }
+        std::fs::write(path, wav).unwrap();
+    }
+
+    #[test]
+    fn test_file_source_drop_frame_minute() {
+        gst::init().unwrap();
+
+        // 00:00:59:20 から約1秒。00:01:00:00 と 00:01:00:01 は飛ばされる
+        let rate = FrameRate::Fps2997Df;
+        let start = Timecode {
+            hours: 0,
+            minutes: 0,
+            seconds: 59,
+            frames: 20,
+        };
+        let mut ltc = vec![0.0f32; SAMPLE_RATE as usize];
+        LtcEncoder::new(rate, start, SAMPLE_RATE, 0.5).fill(&mut ltc);
+
+        // LTCは2チャンネル目（1チャンネル目は無音）
+        let stereo: Vec<f32> = ltc.iter().flat_map(|&s| [0.0, s]).collect();
+        let path = std::env::temp_dir().join(format!("ltc-{}.wav", uuid::Uuid::new_v4()));
+        write_wav(&path, 2, &stereo);
+
+        let frames = Arc::new(Mutex::new(Vec::new()));
+        let received = frames.clone();
+        let source = LtcInputSource::File {
+            path: path.to_string_lossy().into_owned(),
+        };
+        let input = LtcInput::new(&source, 1, move |frame| received.lock().push(frame)).unwrap();
+        input.start().unwrap();
+        let message = input.bus().unwrap().timed_pop_filtered(
+            gst::ClockTime::from_seconds(10),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        );
+        input.stop();
+        std::fs::remove_file(&path).unwrap();
+        assert!(
+            matches!(
+                message.as_ref().map(|m| m.view()),
+                Some(gst::MessageView::Eos(_))
+            ),
+            "{:?}",
+            message
+        );
+
+        let frames = frames.lock();
+        let timecodes: Vec<String> = frames.iter().map(|f| f.timecode.to_string()).collect();
+        assert!(frames.len() >= 25, "decoded {:?}", timecodes);
+        assert!(frames.iter().all(|f| f.drop_frame));
+        for pair in frames.windows(2) {
+            assert_eq!(
+                pair[1].timecode.to_frames(rate),
+                pair[0].timecode.to_frames(rate) + 1,
+                "{:?}",
+                timecodes
+            );
+        }
+        let boundary = timecodes.iter().position(|t| t == "00:00:59:29").unwrap();
+        assert_eq!(timecodes[boundary + 1], "00:01:00:02");
+    }
+}
@@ -0,0 +1,166 @@
+//! LTC出力
+//!
+//! CuePlayerのパイプラインにappsrcでLTC波形を生成するブランチを追加する。
+//! メディアと同じパイプラインクロック・セグメントで動作するため再生位置と常に一致し、
+//! seekにも追従する。パイプラインはCueごとに再構築されるので、LTCもCueごとに
+//! 開始タイムコードへ戻る。
+//!
+//! LTCは出力先のオーディオバス（`audio::bus`）に合成し、同じデバイスに
+//! 別のシンクを開かない。メディアの音声と同じストリームの指定チャンネルに載る。
+//!
+//! ```text
+//! appsrc(LtcEncoder) → audiomixer（出力のオーディオバス）
+//! ```
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::audio::bus::{self, SAMPLE_RATE};
+use crate::error::{AppError, AppResult};
+use crate::timecode::ltc::LtcEncoder;
+use crate::types::{LtcOutputConfig, OutputTarget, OutputType};
+
+/// 1バッファあたりのサンプル数 (10ms)
+const SAMPLES_PER_BUFFER: usize = 480;
+
+/// モノラルのLTCをバスのインターリーブの指定チャンネルに載せる（他のチャンネルは無音）
+fn interleave(mono: &[f32], channels: usize, channel: usize) -> Vec<u8> {
+    let mut data = vec![0u8; mono.len() * channels * 4];
+    for (i, sample) in mono.iter().enumerate() {
+        let offset = (i * channels + channel) * 4;
+        data[offset..offset + 4].copy_from_slice(&sample.to_le_bytes());
+    }
+    data
+}
+
+/// LTC生成ブランチを出力のオーディオバスに追加
+///
+/// `channels` はバスのチャンネル数（`bus::channel_count`）。
+/// `duration` が指定されていればその位置でEOSを送る（パイプラインのEOS・duration用）
+pub fn add_ltc_output(
+    pipeline: &gst::Pipeline,
+    config: &LtcOutputConfig,
+    output: &OutputTarget,
+    channels: u32,
+    duration: Option<f64>,
+) -> AppResult<()> {
+    if output.output_type != OutputType::Audio {
+        return Err(AppError::Output(format!(
+            "LTC output '{}' is not an audio output",
+            output.name
+        )));
+    }
+    if config.channel >= channels {
+        return Err(AppError::Output(format!(
+            "LTC channel {} is out of range for '{}' ({} channels)",
+            config.channel + 1,
+            output.name,
+            channels
+        )));
+    }
+
+    let appsrc = gst_app::AppSrc::builder()
+        .caps(&bus::caps(channels))
+        .format(gst::Format::Time)
+        .stream_type(gst_app::AppStreamType::Seekable)
+        .build();
+    if let Some(duration) = duration {
+        appsrc.set_duration(gst::ClockTime::from_seconds_f64(duration));
+    }
+
+    let amplitude = 10f64.powf(config.level_db / 20.0) as f32;
+    let encoder = Arc::new(Mutex::new(LtcEncoder::new(
+        config.frame_rate,
+        config.start_timecode,
+        SAMPLE_RATE,
+        amplitude,
+    )));
+
+    let channel = config.channel as usize;
+    let channels = channels as usize;
+    let encoder_need = encoder.clone();
+    let encoder_seek = encoder;
+    let buffer_duration = gst::ClockTime::from_nseconds(
+        SAMPLES_PER_BUFFER as u64 * 1_000_000_000 / SAMPLE_RATE as u64,
+    );
+
+    appsrc.set_callbacks(
+        gst_app::AppSrcCallbacks::builder()
+            .need_data(move |appsrc, _| {
+                let mut encoder = encoder_need.lock();
+                let position = encoder.position();
+                if duration.is_some_and(|end| position >= end) {
+                    let _ = appsrc.end_of_stream();
+                    return;
+                }
+
+                let mut mono = [0.0f32; SAMPLES_PER_BUFFER];
+                encoder.fill(&mut mono);
+
+                let data = interleave(&mono, channels, channel);
+
+                let mut buffer = gst::Buffer::from_mut_slice(data);
+                if let Some(buffer) = buffer.get_mut() {
+                    buffer.set_pts(gst::ClockTime::from_seconds_f64(position));
+                    buffer.set_duration(buffer_duration);
+                }
+
+                if let Err(e) = appsrc.push_buffer(buffer) {
+                    debug!("[LtcOutput] push_buffer stopped: {:?}", e);
+                }
+            })
+            .seek_data(move |_, offset| {
+                let position = offset as f64 / 1_000_000_000.0;
+                debug!("[LtcOutput] Seek to {:.3}s", position);
+                encoder_seek.lock().seek(position);
+                true
+            })
+            .build(),
+    );
+
+    pipeline
+        .add(&appsrc)
+        .map_err(|e| AppError::Pipeline(format!("Failed to add LTC source: {:?}", e)))?;
+    let mixer_pad = bus::request_pad(pipeline, output)?;
+    let src_pad = appsrc
+        .static_pad("src")
+        .ok_or_else(|| AppError::Pipeline("Failed to get LTC source pad".to_string()))?;
+    src_pad
+        .link(&mixer_pad)
+        .map_err(|e| AppError::Pipeline(format!("Failed to link LTC source: {:?}", e)))?;
+
+    info!(
+        "[LtcOutput] LTC {:?} from {} -> '{}' channel {}",
+        config.frame_rate, config.start_timecode, output.name, config.channel
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(data: &[u8]) -> Vec<f32> {
+        data.chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn test_interleave() {
+        let mono = [0.5f32, -0.25];
+
+        // 4チャンネルのバスの3チャンネル目
+        let data = samples(&interleave(&mono, 4, 2));
+        assert_eq!(data, vec![0.0, 0.0, 0.5, 0.0, 0.0, 0.0, -0.25, 0.0]);
+
+        // ステレオの左
+        let data = samples(&interleave(&mono, 2, 0));
+        assert_eq!(data, vec![0.5, 0.0, -0.25, 0.0]);
+    }
+}
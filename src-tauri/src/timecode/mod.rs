@@ -0,0 +1,14 @@
+//! タイムコード（LTC・MTC）の入出力とチェイス
+
+pub mod chase;
+pub mod chase_runner;
+pub mod ltc;
+#[cfg(feature = "gui")]
+pub mod ltc_chase;
+#[cfg(feature = "gui")]
+pub mod ltc_input;
+pub mod ltc_output;
+pub mod mtc;
+
+#[cfg(feature = "gui")]
+pub use ltc_chase::LtcChase;
@@ -0,0 +1,160 @@
+//! MIDIタイムコード (MTC) のデコード
+//!
+//! クォーターフレームは8個で1つのタイムコードを表し、2フレーム分の時間がかかる。
+//! 8個揃った時点（2フレーム後）と、次の周期の4個目（3フレーム後）で
+//! 現在のタイムコードを出力し、1フレームおきに連続した値をChaserへ渡す。
+
+use crate::types::{FrameRate, Timecode};
+
+/// レートビット (0rr) からフレームレートを取得
+fn rate_from_bits(bits: u8) -> FrameRate {
+    match bits & 0x03 {
+        0 => FrameRate::Fps24,
+        1 => FrameRate::Fps25,
+        2 => FrameRate::Fps2997Df,
+        _ => FrameRate::Fps30,
+    }
+}
+
+/// フルフレームメッセージ (F0 7F <dev> 01 01 hr mn sc fr F7) を解釈
+pub fn parse_full_frame(data: &[u8]) -> Option<(Timecode, FrameRate)> {
+    match data {
+        [0xF0, 0x7F, _, 0x01, 0x01, hr, mn, sc, fr, 0xF7, ..] => Some((
+            Timecode {
+                hours: hr & 0x1F,
+                minutes: mn & 0x3F,
+                seconds: sc & 0x3F,
+                frames: fr & 0x1F,
+            },
+            rate_from_bits(hr >> 5),
+        )),
+        _ => None,
+    }
+}
+
+/// クォーターフレームのデコーダー
+#[derive(Default)]
+pub struct MtcDecoder {
+    pieces: [u8; 8],
+    /// 次に期待するピース番号
+    expected: u8,
+    /// ピース0から連続して受信した数
+    received: u8,
+    /// 最後に揃ったタイムコード（通算フレーム数）
+    last: Option<(u64, FrameRate)>,
+}
+
+impl MtcDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 受信状態を破棄（フルフレーム受信・ロケート時）
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// クォーターフレーム (F1 の後のデータバイト) を1つ処理
+    pub fn on_quarter_frame(&mut self, data: u8) -> Option<(Timecode, FrameRate)> {
+        let piece = (data >> 4) & 0x07;
+
+        // 欠落・逆方向再生は揃え直す
+        if piece != self.expected {
+            self.reset();
+            if piece != 0 {
+                return None;
+            }
+        }
+        if piece == 0 {
+            self.received = 0;
+        }
+
+        self.pieces[piece as usize] = data & 0x0F;
+        self.received += 1;
+        self.expected = (piece + 1) % 8;
+
+        match piece {
+            3 if self.received == 4 => self
+                .last
+                .map(|(frames, rate)| (Timecode::from_frames(frames + 3, rate), rate)),
+            7 if self.received == 8 => {
+                let p = &self.pieces;
+                let rate = rate_from_bits(p[7] >> 1);
+                let timecode = Timecode {
+                    hours: p[6] | ((p[7] & 0x01) << 4),
+                    minutes: p[4] | ((p[5] & 0x03) << 4),
+                    seconds: p[2] | ((p[3] & 0x03) << 4),
+                    frames: p[0] | ((p[1] & 0x01) << 4),
+                };
+                let frames = timecode.to_frames(rate);
+                self.last = Some((frames, rate));
+                Some((Timecode::from_frames(frames + 2, rate), rate))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// タイムコードをクォーターフレーム8個に分解
+    fn quarter_frames(tc: Timecode, rate_bits: u8) -> [u8; 8] {
+        [
+            tc.frames & 0x0F,
+            0x10 | (tc.frames >> 4),
+            0x20 | (tc.seconds & 0x0F),
+            0x30 | (tc.seconds >> 4),
+            0x40 | (tc.minutes & 0x0F),
+            0x50 | (tc.minutes >> 4),
+            0x60 | (tc.hours & 0x0F),
+            0x70 | (rate_bits << 1) | (tc.hours >> 4),
+        ]
+    }
+
+    #[test]
+    fn test_quarter_frames_yield_every_frame() {
+        let rate = FrameRate::Fps25;
+        let mut decoder = MtcDecoder::new();
+        let mut decoded = Vec::new();
+
+        // 01:00:00:00 から 3周期分
+        let start = Timecode {
+            hours: 1,
+            ..Default::default()
+        }
+        .to_frames(rate);
+        for cycle in 0..3 {
+            let tc = Timecode::from_frames(start + cycle * 2, rate);
+            for data in quarter_frames(tc, 1) {
+                if let Some((tc, detected)) = decoder.on_quarter_frame(data) {
+                    assert_eq!(detected, rate);
+                    decoded.push(tc.to_frames(rate) - start);
+                }
+            }
+        }
+
+        assert_eq!(decoded, vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_dropped_piece_resyncs() {
+        let mut decoder = MtcDecoder::new();
+        let frames = quarter_frames(Timecode::default(), 3);
+        for data in &frames[..5] {
+            decoder.on_quarter_frame(*data);
+        }
+        // ピース5が欠落
+        assert_eq!(decoder.on_quarter_frame(frames[6]), None);
+        assert_eq!(decoder.on_quarter_frame(frames[7]), None);
+    }
+
+    #[test]
+    fn test_full_frame() {
+        let data = [0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x41, 0x02, 0x03, 0x04, 0xF7];
+        let (tc, rate) = parse_full_frame(&data).unwrap();
+        assert_eq!(rate, FrameRate::Fps2997Df);
+        assert_eq!(tc.to_string(), "01:02:03:04");
+    }
+}
@@ -0,0 +1,121 @@
+//! トリガーとアクションのバインディング関連の型定義
+
+use serde::{Deserialize, Serialize};
+
+use super::control::MidiTrigger;
+use super::timecode::Timecode;
+
+/// フェード等で操作するレベル
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LevelTarget {
+    MasterBrightness,
+    MasterVolume,
+    #[serde(rename_all = "camelCase")]
+    OutputBrightness {
+        output_id: String,
+    },
+}
+
+/// 入力トリガー
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ControlTrigger {
+    /// OSCアドレス（完全一致）。最初の引数を値として使う
+    Osc { address: String },
+    /// MIDIメッセージ
+    Midi { message: MidiTrigger },
+    /// DMXチャンネル (1〜512) がしきい値をまたいで上がった時。
+    /// universeがNoneなら全ユニバース
+    Dmx {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        universe: Option<u16>,
+        address: u16,
+        threshold: u8,
+    },
+    /// `POST /api/trigger/{name}`
+    Http { name: String },
+    /// UIのホットキー
+    Hotkey { id: String },
+    /// チェイス中のタイムコードがこの時刻を通過した時
+    Timecode { timecode: Timecode },
+}
+
+/// バインディングで実行する操作
+///
+/// 値を取る操作で値を省略した場合は入力の値（0〜100）を使う。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ControlAction {
+    Go,
+    Play,
+    Pause,
+    Stop,
+    /// Cueを読み込み。cue (0始まり) がNoneなら入力の値をCue番号として使う
+    LoadCue {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cue: Option<usize>,
+    },
+    /// Cueを読み込んで再生
+    GoCue {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cue: Option<usize>,
+    },
+    MasterBrightness {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<f64>,
+    },
+    MasterVolume {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<f64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    OutputBrightness {
+        output_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<f64>,
+    },
+    /// 現在の値から目標値まで直線でフェード
+    #[serde(rename_all = "camelCase")]
+    Fade {
+        target: LevelTarget,
+        value: f64,
+        duration_ms: u64,
+    },
+    /// 暗転（enabledがNoneなら切り替え）
+    Blackout {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        enabled: Option<bool>,
+    },
+    #[serde(rename_all = "camelCase")]
+    RunMacro {
+        macro_id: String,
+    },
+}
+
+/// トリガーと操作の組
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlBinding {
+    pub trigger: ControlTrigger,
+    pub action: ControlAction,
+}
+
+/// マクロの1ステップ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroStep {
+    /// 前のステップからの待ち時間 (ms)
+    #[serde(default)]
+    pub delay_ms: u64,
+    pub action: ControlAction,
+}
+
+/// 順に実行する操作の並び
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlMacro {
+    pub id: String,
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
@@ -0,0 +1,40 @@
+//! ショーの収集関連の型定義
+
+use serde::{Deserialize, Serialize};
+
+/// 収集の書き出し形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectMode {
+    /// 収集先フォルダにプロジェクト・メディア・マニフェストを置く
+    #[default]
+    Folder,
+    /// 収集先フォルダに1つのZIPアーカイブ（無圧縮）を書き出す
+    Archive,
+}
+
+/// 収集の進捗
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectProgress {
+    /// 処理中のメディア（収集先での相対パス）
+    pub file: String,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// 収集の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectResult {
+    /// 書き出したプロジェクトファイル（アーカイブの場合はZIPファイル）
+    pub path: String,
+    /// コピーしたメディアの数
+    pub copied: usize,
+    /// 同じ内容のファイルが既にあったためコピーしなかったメディアの数
+    pub skipped: usize,
+    /// メディアの合計サイズ
+    pub bytes: u64,
+}
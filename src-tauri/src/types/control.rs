@@ -0,0 +1,227 @@
+//! 外部制御（OSC・HTTP・MIDI等）関連の型定義
+
+use serde::{Deserialize, Serialize};
+
+use super::binding::ControlAction;
+#[cfg(feature = "gui")]
+use super::timecode::LtcChaseStatus;
+
+/// OSC over TCP のフレーミング
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OscTcpFraming {
+    /// OSC 1.0: 先頭に int32 のパケット長
+    Length,
+    /// OSC 1.1: SLIP (RFC 1055)
+    #[default]
+    Slip,
+}
+
+/// OSCサーバー設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OscConfig {
+    /// プロジェクト読み込み時に自動で起動
+    pub enabled: bool,
+    pub bind_address: String,
+    /// UDP・TCP共通の待ち受けポート
+    pub port: u16,
+    pub udp: bool,
+    pub tcp: bool,
+    pub tcp_framing: OscTcpFraming,
+    /// 常にフィードバックを送る宛先（"host:port"）
+    pub feedback_targets: Vec<String>,
+    /// フィードバックの定期送信レート（0で変化時のみ）
+    pub feedback_rate_hz: u32,
+}
+
+impl Default for OscConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "0.0.0.0".to_string(),
+            port: 8000,
+            udp: true,
+            tcp: true,
+            tcp_framing: OscTcpFraming::default(),
+            feedback_targets: Vec::new(),
+            feedback_rate_hz: 10,
+        }
+    }
+}
+
+/// OSCサーバーの状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OscStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub udp_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_address: Option<String>,
+    pub tcp_clients: usize,
+    /// フィードバック送信先（設定分 + /feedback/subscribe で登録されたもの）
+    pub feedback_clients: usize,
+    pub messages_received: u64,
+    /// 解釈・実行できなかったメッセージ数
+    pub errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// HTTP / WebSocket APIサーバー設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HttpConfig {
+    /// プロジェクト読み込み時に自動で起動
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+    /// 設定するとリクエストに `Authorization: Bearer <token>`
+    /// （WebSocketは `?token=<token>` も可）を要求する
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "0.0.0.0".to_string(),
+            port: 8080,
+            token: None,
+        }
+    }
+}
+
+/// HTTP / WebSocket APIサーバーの状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// 接続中のWebSocketクライアント数
+    pub ws_clients: usize,
+    pub requests: u64,
+}
+
+/// MIDIバインディングのトリガー
+///
+/// チャンネルは1〜16（Noneで全チャンネル）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MidiTrigger {
+    /// ノートオン（ベロシティ0は無視）。noteがNoneなら全ノート
+    Note {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        channel: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<u8>,
+    },
+    /// コントロールチェンジ
+    ControlChange {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        channel: Option<u8>,
+        controller: u8,
+    },
+    /// プログラムチェンジ。programがNoneなら全プログラム
+    ProgramChange {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        channel: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        program: Option<u8>,
+    },
+}
+
+/// MIDIバインディング
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiBinding {
+    pub trigger: MidiTrigger,
+    pub action: ControlAction,
+}
+
+/// MIDI入力設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MidiConfig {
+    /// プロジェクト読み込み時に自動で起動
+    pub enabled: bool,
+    /// 接続する入力ポート名（部分一致、Noneで最初のポート）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port_name: Option<String>,
+    /// 既存ポートの代わりに仮想ポートを作成（Linux ALSA / macOS）
+    pub virtual_port: bool,
+    pub virtual_port_name: String,
+    /// 既定の対応（プロジェクトのバインディングが一致したメッセージには使わない）
+    pub bindings: Vec<MidiBinding>,
+    /// MIDIショーコントロール (GO/STOP/RESUME/LOAD) を受け付ける
+    pub msc: bool,
+    /// MSCのデバイスID（0x7Fの全体宛ても受け付ける、Noneで全て）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msc_device_id: Option<u8>,
+    /// MIDIタイムコードで再生位置をチェイス（LTCチェイスの設定を使用）
+    pub mtc_chase: bool,
+}
+
+impl Default for MidiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port_name: None,
+            virtual_port: false,
+            virtual_port_name: "Tauri Live Player".to_string(),
+            bindings: vec![
+                MidiBinding {
+                    trigger: MidiTrigger::ProgramChange {
+                        channel: None,
+                        program: None,
+                    },
+                    action: ControlAction::LoadCue { cue: None },
+                },
+                MidiBinding {
+                    trigger: MidiTrigger::Note {
+                        channel: None,
+                        note: None,
+                    },
+                    action: ControlAction::Go,
+                },
+                MidiBinding {
+                    trigger: MidiTrigger::ControlChange {
+                        channel: None,
+                        controller: 7,
+                    },
+                    action: ControlAction::MasterVolume { value: None },
+                },
+            ],
+            msc: true,
+            msc_device_id: None,
+            mtc_chase: false,
+        }
+    }
+}
+
+/// MIDI入力の状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    pub messages_received: u64,
+    /// 実行できなかった操作の数
+    pub errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// MTCチェイスの状態
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtc_chase: Option<LtcChaseStatus>,
+}
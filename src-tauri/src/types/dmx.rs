@@ -0,0 +1,221 @@
+//! DMX入力（Art-Net・sACN）とArt-Netタイムコード関連の型定義
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "gui")]
+use super::timecode::LtcChaseStatus;
+use super::timecode::{FrameRate, Timecode};
+
+/// DMXチャンネルの割り当て先
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DmxChannelFunction {
+    /// 0〜255をマスター明るさ0〜100に
+    MasterBrightness,
+    /// 0〜255をマスター音量0〜100に
+    MasterVolume,
+    /// 0〜255を出力の明るさ0〜100に
+    #[serde(rename_all = "camelCase")]
+    OutputBrightness { output_id: String },
+    /// GOで再生するCue（0=次のCue、1〜255=Cue番号）
+    CueSelect,
+    /// 128以上になった瞬間にGO
+    Go,
+}
+
+/// フィクスチャとしてのチャンネル割り当て
+///
+/// プロジェクトのバインディングのトリガーに使われているチャンネルは無視する。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DmxPatch {
+    /// 先頭チャンネル (1〜512)
+    pub start_address: u16,
+    /// 先頭から順に割り当てる機能
+    pub channels: Vec<DmxChannelFunction>,
+}
+
+impl Default for DmxPatch {
+    fn default() -> Self {
+        Self {
+            start_address: 1,
+            channels: vec![
+                DmxChannelFunction::MasterBrightness,
+                DmxChannelFunction::MasterVolume,
+                DmxChannelFunction::CueSelect,
+                DmxChannelFunction::Go,
+            ],
+        }
+    }
+}
+
+/// Art-Net入力設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ArtNetConfig {
+    /// プロジェクト読み込み時に自動で起動
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+    /// 受信するユニバース（15bitのPort-Address: Net・Sub-Net・Universe）
+    pub universe: u16,
+    pub patch: DmxPatch,
+    /// ArtTimeCodeで再生位置をチェイス（LTCチェイスの設定を使用）
+    pub timecode_chase: bool,
+}
+
+impl Default for ArtNetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "0.0.0.0".to_string(),
+            port: 6454,
+            universe: 0,
+            patch: DmxPatch::default(),
+            timecode_chase: false,
+        }
+    }
+}
+
+/// Art-Net入力の状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtNetStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// 受信したArt-Netパケット数
+    pub packets_received: u64,
+    /// 設定ユニバースのArtDmx数
+    pub dmx_frames: u64,
+    /// 最後に設定ユニバースを送ってきた送信元
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_source: Option<String>,
+    /// パッチしたチャンネルの現在値
+    pub channel_values: Vec<u8>,
+    pub errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// ArtTimeCodeチェイスの状態
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timecode_chase: Option<LtcChaseStatus>,
+}
+
+/// 全ての送信元が途絶えた時の動作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DmxSourceLoss {
+    /// 最後の値を保持
+    #[default]
+    Hold,
+    /// 受信開始前の値に戻し、ローカル操作に任せる
+    Release,
+}
+
+/// sACN (E1.31) 入力設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SacnConfig {
+    /// プロジェクト読み込み時に自動で起動
+    pub enabled: bool,
+    /// 受信するユニバース (1〜63999)
+    pub universe: u16,
+    /// マルチキャストグループ (239.255.x.x) に参加する（ユニキャストは常に受信）
+    pub multicast: bool,
+    /// マルチキャストを受信するインターフェースのIPv4アドレス（Noneで既定）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    pub source_loss: DmxSourceLoss,
+    /// 送信元が途絶えたとみなす時間 (ms)
+    pub source_timeout_ms: u64,
+    pub patch: DmxPatch,
+}
+
+impl Default for SacnConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            universe: 1,
+            multicast: true,
+            interface: None,
+            source_loss: DmxSourceLoss::default(),
+            source_timeout_ms: 2500,
+            patch: DmxPatch::default(),
+        }
+    }
+}
+
+/// sACNの送信元
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SacnSource {
+    /// 送信元のCID (UUID)
+    pub cid: String,
+    pub name: String,
+    pub address: String,
+    pub priority: u8,
+    /// 現在この送信元の値を使っているか
+    pub active: bool,
+}
+
+/// sACN入力の状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SacnStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub packets_received: u64,
+    /// 採用した送信元のDMXフレーム数
+    pub dmx_frames: u64,
+    pub sources: Vec<SacnSource>,
+    /// パッチしたチャンネルの現在値
+    pub channel_values: Vec<u8>,
+    pub errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// ArtTimeCode送信設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ArtTimecodeOutputConfig {
+    /// プロジェクト読み込み時に自動で起動
+    pub enabled: bool,
+    /// 送信先（ブロードキャストアドレスまたは卓のIP）
+    pub target_address: String,
+    pub port: u16,
+    pub frame_rate: FrameRate,
+    /// Cue先頭で送信するタイムコード
+    pub start_timecode: Timecode,
+    /// Art-Net 4のStreamId
+    pub stream_id: u8,
+}
+
+impl Default for ArtTimecodeOutputConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_address: "255.255.255.255".to_string(),
+            port: 6454,
+            frame_rate: FrameRate::default(),
+            start_timecode: Timecode::default(),
+            stream_id: 0,
+        }
+    }
+}
+
+/// ArtTimeCode送信の状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtTimecodeOutputStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub packets_sent: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timecode: Option<Timecode>,
+}
@@ -0,0 +1,40 @@
+//! アプリケーションイベントの型定義
+
+use serde::Serialize;
+
+#[cfg(feature = "gui")]
+use super::collect::CollectProgress;
+use super::project::{HistoryStatus, ProjectChange};
+
+/// バックグラウンドサービスからフロントエンド等へ通知するイベント
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AppEvent {
+    /// スレーブから heartbeat を受信し始めた（復帰を含む）
+    #[serde(rename_all = "camelCase")]
+    SlaveOnline {
+        slave_id: String,
+        name: String,
+        address: String,
+    },
+    /// スレーブの heartbeat が途絶えた
+    #[serde(rename_all = "camelCase")]
+    SlaveSilent {
+        slave_id: String,
+        name: String,
+        address: String,
+        silent_ms: u64,
+    },
+    /// 現在のプロジェクトが変更された（リモートからの編集を含む）
+    #[serde(rename_all = "camelCase")]
+    ProjectChanged {
+        change: ProjectChange,
+        /// 編集の内容（読み込み時はNone）
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        history: HistoryStatus,
+    },
+    /// ショーの収集の進捗
+    #[cfg(feature = "gui")]
+    CollectProgress(CollectProgress),
+}
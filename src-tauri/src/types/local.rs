@@ -0,0 +1,82 @@
+//! PC固有のローカル設定関連の型定義
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::output::AudioDriver;
+#[cfg(feature = "gui")]
+use super::project::Project;
+use super::sync::SyncMode;
+
+/// PC固有の設定（プロジェクトファイルとは別にこのPCに保存）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LocalConfig {
+    /// 相対パスのメディアを解決するフォルダ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_root: Option<String>,
+    /// 同期スレーブ時にこのPCの再生を先行させる時間 (ms)。
+    /// 出力機器の遅延の補正に使い、負の値で遅らせる
+    pub latency_offset_ms: f64,
+    /// 同期スレーブとしてマスターに名乗るID（初回のスレーブ起動時に生成して保存）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slave_id: Option<String>,
+
+    // 以下は実行時にプロジェクトの設定へ上書きする（プロジェクトファイルは変更しない）
+    /// 全オーディオ出力のドライバ（出力ごとの上書きが優先）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_driver: Option<AudioDriver>,
+    /// 出力ごとの上書き
+    pub outputs: Vec<OutputOverride>,
+    /// プロジェクトのディスプレイ番号 → このPCのディスプレイ番号
+    pub display_map: BTreeMap<usize, usize>,
+    /// NDI送信名の先頭に付ける文字列（例: "BACKUP "）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ndi_name_prefix: Option<String>,
+    /// 同期モード（設定するとプロジェクト読み込み時にこのモードで同期を開始）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_role: Option<SyncMode>,
+    pub ports: ControlPorts,
+}
+
+/// 出力ごとの上書き（プロジェクトの出力IDで対応）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutputOverride {
+    pub output_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_driver: Option<AudioDriver>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_device: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_channels: Option<Vec<u32>>,
+}
+
+/// 外部制御の受信ポートの上書き
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ControlPorts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub osc: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub art_net: Option<u16>,
+    /// MIDI入力ポート名（部分一致）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub midi: Option<String>,
+}
+
+/// パス変換の結果
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathConversion {
+    pub project: Project,
+    /// 変換したアイテム数
+    pub converted: usize,
+    /// 変換できなかったパス（メディアルートの外など）
+    pub skipped: Vec<String>,
+}
@@ -3,13 +3,14 @@
 use serde::{Deserialize, Serialize};
 
 /// メディアアイテム
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaItem {
     pub id: String,
     #[serde(rename = "type")]
     pub media_type: MediaType,
     pub name: String,
+    /// メディアファイル。相対パスはPCごとのメディアルートを基準に解決（local_config）
     pub path: String,
     pub output_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -18,18 +19,25 @@ pub struct MediaItem {
     pub trim_start: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trim_end: Option<f64>,
+    /// ファイルサイズ（追加・変更・再リンク・収集時に記録し、再リンクの候補の照合に使う）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// ファイルのSHA-256（収集時に記録）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
 }
 
 /// メディアタイプ
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MediaType {
+    #[default]
     Video,
     Audio,
 }
 
 /// キュー
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Cue {
     pub id: String,
@@ -42,3 +50,55 @@ pub struct Cue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
 }
+
+/// 再リンクの候補がどこまで一致したか
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelinkMatch {
+    /// ファイル名だけ一致（サイズ・ハッシュの記録がない）
+    Name,
+    /// ファイル名と記録されたサイズが一致
+    Size,
+    /// 記録されたSHA-256まで一致
+    Hash,
+}
+
+/// 再リンクの候補
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkCandidate {
+    /// プロジェクトに設定するパス（メディアルート内なら相対パス）
+    pub path: String,
+    pub size: u64,
+    #[serde(rename = "match")]
+    pub match_level: RelinkMatch,
+    /// 元のパスと末尾から一致したフォルダ名の数
+    pub matching_folders: usize,
+}
+
+/// 見つからないメディアの再リンク案
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkProposal {
+    /// プロジェクトに設定されているパス
+    pub path: String,
+    /// このパスを使うアイテム
+    pub item_ids: Vec<String>,
+    /// 有力な順
+    pub candidates: Vec<RelinkCandidate>,
+    /// 候補が1つに絞れた場合のパス
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposed: Option<String>,
+}
+
+/// パスの置き換え
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Relink {
+    pub from: String,
+    pub to: String,
+}
@@ -1,12 +1,34 @@
 //! 型定義モジュール
 
+mod binding;
+#[cfg(feature = "gui")]
+mod collect;
+mod control;
+mod dmx;
+mod event;
+mod local;
 mod media;
 mod output;
 mod player;
 mod project;
+mod sync;
+mod timecode;
+#[cfg(feature = "gui")]
+mod validation;
 
 // 全ての型を再エクスポート
+pub use binding::*;
+#[cfg(feature = "gui")]
+pub use collect::*;
+pub use control::*;
+pub use dmx::*;
+pub use event::*;
+pub use local::*;
 pub use media::*;
 pub use output::*;
 pub use player::*;
 pub use project::*;
+pub use sync::*;
+pub use timecode::*;
+#[cfg(feature = "gui")]
+pub use validation::*;
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 /// 出力先
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OutputTarget {
     pub id: String,
@@ -43,9 +43,10 @@ pub struct OutputTarget {
 }
 
 /// 出力タイプ
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum OutputType {
+    #[default]
     Display,
     Ndi,
     Audio,
@@ -82,6 +83,7 @@ pub struct MonitorInfo {
 }
 
 /// NDIソース情報
+#[cfg(feature = "gui")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NdiSource {
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 /// プレイヤーステータス
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PlayerStatus {
     Idle,
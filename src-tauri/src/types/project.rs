@@ -2,8 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::binding::{ControlBinding, ControlMacro};
+use super::control::{HttpConfig, MidiConfig, OscConfig};
+use super::dmx::{ArtNetConfig, ArtTimecodeOutputConfig, SacnConfig};
 use super::media::Cue;
 use super::output::OutputTarget;
+use super::sync::SyncConfig;
+use super::timecode::{LtcChaseConfig, LtcOutputConfig};
 
 /// プロジェクト設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,7 +16,29 @@ use super::output::OutputTarget;
 pub struct ProjectSettings {
     pub default_brightness: f64,
     pub auto_save: bool,
+    /// 保存時に残す以前のファイルの数（0で残さない）
+    #[serde(default = "default_backup_count")]
+    pub backup_count: usize,
+    /// 元に戻せる編集の数（0で記録しない）
+    #[serde(default = "default_history_depth")]
+    pub history_depth: usize,
     pub preview_quality: PreviewQuality,
+    #[serde(default)]
+    pub ltc_chase: LtcChaseConfig,
+    #[serde(default)]
+    pub ltc_output: LtcOutputConfig,
+    #[serde(default)]
+    pub osc: OscConfig,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default)]
+    pub midi: MidiConfig,
+    #[serde(default)]
+    pub art_net: ArtNetConfig,
+    #[serde(default)]
+    pub sacn: SacnConfig,
+    #[serde(default)]
+    pub art_timecode_output: ArtTimecodeOutputConfig,
 }
 
 /// プレビュー品質
@@ -28,7 +55,17 @@ impl Default for ProjectSettings {
         Self {
             default_brightness: 100.0,
             auto_save: true,
+            backup_count: default_backup_count(),
+            history_depth: default_history_depth(),
             preview_quality: PreviewQuality::Medium,
+            ltc_chase: LtcChaseConfig::default(),
+            ltc_output: LtcOutputConfig::default(),
+            osc: OscConfig::default(),
+            http: HttpConfig::default(),
+            midi: MidiConfig::default(),
+            art_net: ArtNetConfig::default(),
+            sacn: SacnConfig::default(),
+            art_timecode_output: ArtTimecodeOutputConfig::default(),
         }
     }
 }
@@ -37,6 +74,9 @@ impl Default for ProjectSettings {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Project {
+    /// プロジェクトファイルのスキーマバージョン（project::migration）
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     pub id: String,
     pub name: String,
     pub master_brightness: f64,
@@ -46,15 +86,105 @@ pub struct Project {
     pub cues: Vec<Cue>,
     #[serde(default)]
     pub settings: ProjectSettings,
+    #[serde(default)]
+    pub sync: SyncConfig,
+    /// 外部制御の入力と操作の対応
+    #[serde(default)]
+    pub bindings: Vec<ControlBinding>,
+    #[serde(default)]
+    pub macros: Vec<ControlMacro>,
+}
+
+/// プロジェクトファイルのバックアップ
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub path: String,
+    pub file_name: String,
+    /// 作成日時（UNIXエポックからのms）
+    pub modified_at_ms: u64,
+    pub size: u64,
+}
+
+/// 編集履歴の状態（元に戻す・やり直しのメニュー表示用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryStatus {
+    /// 次に元に戻す編集
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undo_label: Option<String>,
+    /// 次にやり直す編集
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redo_label: Option<String>,
+    pub undo_count: usize,
+    pub redo_count: usize,
+}
+
+/// プロジェクトが変更された理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectChange {
+    /// 読み込み・新規作成・復元（履歴は破棄）
+    Load,
+    Edit,
+    Undo,
+    Redo,
+}
+
+/// 自動保存で書き出すリカバリーファイル
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryFile {
+    pub project: Project,
+    /// 元のプロジェクトファイル（未保存ならNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cue_index: Option<usize>,
+    /// Cueの再生位置（秒）
+    pub position: f64,
+    /// 書き出した日時（UNIXエポックからのms）
+    pub saved_at_ms: u64,
+}
+
+/// 前回異常終了した時に残ったリカバリーファイルの情報
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryInfo {
+    /// リカバリーファイル
+    pub path: String,
+    pub project_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cue_index: Option<usize>,
+    pub position: f64,
+    pub saved_at_ms: u64,
+}
+
+fn default_backup_count() -> usize {
+    10
+}
+
+fn default_history_depth() -> usize {
+    100
 }
 
 fn default_volume() -> f64 {
     100.0
 }
 
+fn current_schema_version() -> u32 {
+    crate::project::migration::CURRENT_SCHEMA_VERSION
+}
+
 impl Default for Project {
     fn default() -> Self {
         Self {
+            schema_version: current_schema_version(),
             id: uuid::Uuid::new_v4().to_string(),
             name: "Untitled Project".to_string(),
             master_brightness: 100.0,
@@ -62,6 +192,9 @@ impl Default for Project {
             outputs: Vec::new(),
             cues: Vec::new(),
             settings: ProjectSettings::default(),
+            sync: SyncConfig::default(),
+            bindings: Vec::new(),
+            macros: Vec::new(),
         }
     }
 }
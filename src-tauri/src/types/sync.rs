@@ -0,0 +1,224 @@
+//! マルチPC同期関連の型定義
+
+use serde::{Deserialize, Serialize};
+
+/// 同期モード
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncMode {
+    #[default]
+    Off,
+    Master,
+    Slave,
+}
+
+/// 同期パケットの送信方式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SyncTransport {
+    /// 同一サブネット全体 (255.255.255.255)
+    Broadcast { port: u16 },
+    /// マルチキャストグループ (239.x.x.x)
+    Multicast { addr: String, port: u16 },
+    /// 特定IPリスト ("ip:port")
+    Unicast { targets: Vec<String> },
+}
+
+impl Default for SyncTransport {
+    fn default() -> Self {
+        SyncTransport::Broadcast { port: 7000 }
+    }
+}
+
+/// 同期設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncConfig {
+    pub mode: SyncMode,
+
+    // 送信設定（マスター時）
+    pub transport: SyncTransport,
+    pub send_interval_hz: u32,
+
+    // 受信設定（スレーブ時）
+    pub listen_port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multicast_group: Option<String>,
+    /// マスター途絶とみなすまでの時間 (ms)
+    pub timeout_ms: u64,
+
+    // 共通
+    /// 許容誤差（フレーム数）。これを超えると再生速度で補正
+    pub tolerance_frames: f64,
+    /// これを超えるズレはseekで補正（フレーム数）
+    pub seek_threshold_frames: f64,
+    /// seekの最小間隔 (ms)
+    pub min_seek_interval_ms: u64,
+    /// 位置計算に使うフレームレート
+    pub frame_rate: f64,
+
+    // ネットワーククロック共有
+    /// マスターのパイプラインクロックを配信し、スレーブは同じクロックで再生する
+    pub net_clock: bool,
+    /// クロック配信ポート
+    pub clock_port: u16,
+    /// マスターのクロックアドレス（スレーブ時、省略時は同期パケットの送信元）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock_address: Option<String>,
+
+    // スレーブ管理
+    /// スレーブ → マスターの heartbeat 送信間隔 (ms)
+    pub heartbeat_interval_ms: u64,
+    /// heartbeat がこの時間途絶えたスレーブを警告 (ms)
+    pub slave_timeout_ms: u64,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            mode: SyncMode::Off,
+            transport: SyncTransport::default(),
+            send_interval_hz: 60,
+            listen_port: 7000,
+            multicast_group: None,
+            timeout_ms: 500,
+            tolerance_frames: 1.0,
+            seek_threshold_frames: 10.0,
+            min_seek_interval_ms: 1000,
+            frame_rate: 30.0,
+            net_clock: false,
+            clock_port: 7001,
+            clock_address: None,
+            heartbeat_interval_ms: 1000,
+            slave_timeout_ms: 3000,
+        }
+    }
+}
+
+/// 同期状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub mode: SyncMode,
+    pub running: bool,
+    pub packets_sent: u64,
+    /// heartbeat を受信中のスレーブ数（マスター時）
+    pub slaves_online: usize,
+    pub packets_received: u64,
+    /// sequenceの欠番から検知したパケットロス数
+    pub packets_lost: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_cue_index: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_position: Option<f64>,
+    /// ローカル位置 - マスター位置 (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_error_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_packet_age_ms: Option<u64>,
+    /// タイムアウトによりマスター途絶と判定中
+    pub master_lost: bool,
+    pub seek_count: u64,
+    /// 補正中の再生速度
+    pub playback_rate: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_clock: Option<NetClockStatus>,
+}
+
+/// ネットワーククロックの状態
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetClockStatus {
+    /// マスター: 配信中 / スレーブ: マスターのクロックに同期済み
+    pub synced: bool,
+    /// マスター: 配信アドレス / スレーブ: 接続先
+    pub address: String,
+    /// 往復遅延の平均 (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtt_ms: Option<f64>,
+    /// ローカルクロックとのオフセット (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_ms: Option<f64>,
+    /// クロック推定の当てはまり (r², 1.0に近いほど安定)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
+}
+
+/// スレーブの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SlaveStatus {
+    /// マスター未受信
+    #[default]
+    Idle,
+    /// 正常同期中
+    Synced,
+    /// seek・再調整中
+    Seeking,
+    /// 現在のCueのメディアファイルが見つからない
+    FileNotFound,
+    /// スレーブ側でマスター途絶を検知
+    MasterLost,
+    /// マスター側で heartbeat 途絶を検知
+    Disconnected,
+}
+
+/// スレーブ → マスターの heartbeat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlaveHeartbeat {
+    pub slave_id: String,
+    pub name: String,
+    pub status: SlaveStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cue_index: Option<i32>,
+    pub position: f64,
+    /// スレーブ位置 - マスター位置 (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_error_ms: Option<f64>,
+    pub dropped_frames: u64,
+    /// プロジェクト内で見つからないメディアファイル
+    pub missing_media: Vec<String>,
+    pub blackout: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock_synced: Option<bool>,
+}
+
+/// マスターからスレーブへのリモート操作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SlaveCommand {
+    /// 現在のCueを読み込み直す
+    Reload,
+    /// 閾値に関係なく即座にマスターへ合わせ直す
+    Resync,
+    /// 出力を暗転（解除で元の明るさに戻す）
+    Blackout { enabled: bool },
+}
+
+/// マスターから見たスレーブ一覧の1件
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlavePeer {
+    pub slave_id: String,
+    pub name: String,
+    pub address: String,
+    pub online: bool,
+    /// 最後の heartbeat からの経過時間 (ms)
+    pub last_seen_ms: u64,
+    pub status: SlaveStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cue_index: Option<i32>,
+    pub position: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_delta_ms: Option<f64>,
+    pub dropped_frames: u64,
+    pub missing_media: Vec<String>,
+    pub blackout: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock_synced: Option<bool>,
+}
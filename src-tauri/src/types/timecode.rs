@@ -0,0 +1,257 @@
+//! タイムコード関連の型定義
+
+use serde::{Deserialize, Serialize};
+
+use super::output::AudioDriver;
+
+/// SMPTEフレームレート
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FrameRate {
+    #[serde(rename = "24")]
+    Fps24,
+    #[serde(rename = "25")]
+    Fps25,
+    /// 29.97fps ドロップフレーム
+    #[serde(rename = "29.97df")]
+    Fps2997Df,
+    #[default]
+    #[serde(rename = "30")]
+    Fps30,
+}
+
+impl FrameRate {
+    /// 実際のフレームレート（29.97DFは30000/1001）
+    pub fn fps(self) -> f64 {
+        match self {
+            FrameRate::Fps24 => 24.0,
+            FrameRate::Fps25 => 25.0,
+            FrameRate::Fps2997Df => 30000.0 / 1001.0,
+            FrameRate::Fps30 => 30.0,
+        }
+    }
+
+    /// タイムコード上の1秒あたりのフレーム数
+    pub fn nominal_fps(self) -> u32 {
+        match self {
+            FrameRate::Fps24 => 24,
+            FrameRate::Fps25 => 25,
+            FrameRate::Fps2997Df | FrameRate::Fps30 => 30,
+        }
+    }
+
+    pub fn is_drop_frame(self) -> bool {
+        self == FrameRate::Fps2997Df
+    }
+
+    /// 測定したフレームレートとドロップフレームフラグから推定
+    #[cfg(feature = "gui")]
+    pub fn detect(measured_fps: f64, drop_frame: bool) -> Option<Self> {
+        if drop_frame {
+            return Some(FrameRate::Fps2997Df);
+        }
+        [FrameRate::Fps24, FrameRate::Fps25, FrameRate::Fps30]
+            .into_iter()
+            .find(|rate| (rate.fps() - measured_fps).abs() < 0.6)
+    }
+}
+
+/// SMPTEタイムコード (HH:MM:SS:FF)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timecode {
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub frames: u8,
+}
+
+impl Timecode {
+    /// 00:00:00:00 からの通算フレーム数
+    pub fn to_frames(self, rate: FrameRate) -> u64 {
+        let fps = rate.nominal_fps() as u64;
+        let total_minutes = self.hours as u64 * 60 + self.minutes as u64;
+        let frames = (total_minutes * 60 + self.seconds as u64) * fps + self.frames as u64;
+
+        if rate.is_drop_frame() {
+            // 10分ごとを除く毎分、フレーム番号 00, 01 をスキップ
+            frames - 2 * (total_minutes - total_minutes / 10)
+        } else {
+            frames
+        }
+    }
+
+    /// 通算フレーム数からタイムコードを生成（24時間で折り返し）
+    pub fn from_frames(frames: u64, rate: FrameRate) -> Self {
+        let fps = rate.nominal_fps() as u64;
+        let mut n = frames;
+
+        if rate.is_drop_frame() {
+            const FRAMES_PER_10_MIN: u64 = 17982;
+            const FRAMES_PER_MIN: u64 = 1798;
+            let d = n / FRAMES_PER_10_MIN;
+            let m = n % FRAMES_PER_10_MIN;
+            n += 18 * d;
+            if m > 2 {
+                n += 2 * ((m - 2) / FRAMES_PER_MIN);
+            }
+        }
+
+        Self {
+            hours: ((n / (fps * 3600)) % 24) as u8,
+            minutes: ((n / (fps * 60)) % 60) as u8,
+            seconds: ((n / fps) % 60) as u8,
+            frames: (n % fps) as u8,
+        }
+    }
+
+    /// 00:00:00:00 からの経過秒数
+    pub fn to_seconds(self, rate: FrameRate) -> f64 {
+        self.to_frames(rate) as f64 / rate.fps()
+    }
+}
+
+impl std::fmt::Display for Timecode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}:{:02}",
+            self.hours, self.minutes, self.seconds, self.frames
+        )
+    }
+}
+
+/// LTC入力ソース
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LtcInputSource {
+    /// オーディオ入力デバイス
+    #[serde(rename_all = "camelCase")]
+    Device {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        audio_driver: Option<AudioDriver>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        audio_device: Option<String>,
+    },
+    /// LTCを収録したオーディオファイル（テスト用）
+    File { path: String },
+}
+
+impl Default for LtcInputSource {
+    fn default() -> Self {
+        LtcInputSource::Device {
+            audio_driver: None,
+            audio_device: None,
+        }
+    }
+}
+
+/// LTCチェイス設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LtcChaseConfig {
+    pub source: LtcInputSource,
+    /// LTCが載っている入力チャンネル (0始まり)
+    pub channel: u32,
+    pub frame_rate: FrameRate,
+    /// Cue先頭に対応するタイムコード
+    pub start_timecode: Timecode,
+    /// 再生位置とのズレがこの値を超えたらseek (ms)
+    pub seek_threshold_ms: f64,
+    /// LTC途絶後に自走を続ける時間 (ms)
+    pub freewheel_ms: u64,
+    /// ロック判定に必要な連続フレーム数
+    pub lock_frames: u32,
+}
+
+impl Default for LtcChaseConfig {
+    fn default() -> Self {
+        Self {
+            source: LtcInputSource::default(),
+            channel: 0,
+            frame_rate: FrameRate::default(),
+            start_timecode: Timecode::default(),
+            seek_threshold_ms: 80.0,
+            freewheel_ms: 2000,
+            lock_frames: 3,
+        }
+    }
+}
+
+/// LTC出力設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LtcOutputConfig {
+    pub enabled: bool,
+    /// 出力先のAudio OutputTarget
+    pub output_id: String,
+    /// LTCを載せる出力チャンネル (0始まり、他のチャンネルは無音)
+    pub channel: u32,
+    pub frame_rate: FrameRate,
+    /// Cue先頭で出力するタイムコード
+    pub start_timecode: Timecode,
+    /// 出力レベル (dBFS)
+    pub level_db: f64,
+}
+
+impl Default for LtcOutputConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output_id: String::new(),
+            channel: 0,
+            frame_rate: FrameRate::default(),
+            start_timecode: Timecode::default(),
+            level_db: -12.0,
+        }
+    }
+}
+
+/// チェイスのロック状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChaseLockState {
+    /// タイムコード未受信
+    Unlocked,
+    /// 受信中（ロック判定待ち）
+    Locking,
+    /// ロック中
+    Locked,
+    /// タイムコード途絶、自走中
+    Freewheel,
+}
+
+/// LTCチェイス状態
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LtcChaseStatus {
+    pub running: bool,
+    pub lock: ChaseLockState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timecode: Option<Timecode>,
+    /// チェイスに使っているフレームレート（検出したレートが続けば設定より優先）
+    pub frame_rate: FrameRate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detected_frame_rate: Option<FrameRate>,
+    /// 再生位置 - タイムコード位置 (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_error_ms: Option<f64>,
+    pub frames_decoded: u64,
+    pub seek_count: u64,
+}
+
+#[cfg(feature = "gui")]
+impl Default for LtcChaseStatus {
+    fn default() -> Self {
+        Self {
+            running: false,
+            lock: ChaseLockState::Unlocked,
+            timecode: None,
+            frame_rate: FrameRate::default(),
+            detected_frame_rate: None,
+            position_error_ms: None,
+            frames_decoded: 0,
+            seek_count: 0,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! プロジェクト検証関連の型定義
+
+use serde::{Deserialize, Serialize};
+
+/// 問題の重大度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationSeverity {
+    /// 再生時に警告が出る・意図しない出力になる
+    Warning,
+    /// Cueの読み込みに失敗する・再生されない
+    Error,
+}
+
+/// 問題の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidationKind {
+    MissingMedia,
+    UnreadableMedia,
+    UndecodableMedia,
+    UnknownOutput,
+    DuplicateId,
+    MediaTypeMismatch,
+    MissingMonitor,
+}
+
+/// 検証で見つかった問題
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationFinding {
+    pub severity: ValidationSeverity,
+    pub kind: ValidationKind,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cue_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
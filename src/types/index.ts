@@ -5,11 +5,13 @@ export interface MediaItem {
   id: string;
   type: "video" | "audio";
   name: string;
-  path: string;
+  path: string; // 相対パスはPCごとのmediaRootを基準に解決
   outputId: string;
   offset?: number; // 開始オフセット（秒）
   trimStart?: number; // トリム開始位置
   trimEnd?: number; // トリム終了位置
+  size?: number; // ファイルサイズ（追加・変更・再リンク・収集時に記録、再リンクの照合用）
+  sha256?: string; // ファイルのSHA-256（収集時に記録）
 }
 
 // ========================================
@@ -64,19 +66,78 @@ export interface OutputTarget {
   audioChannels?: number[];
 }
 
+// ========================================
+// タイムコード
+// ========================================
+export type FrameRate = "24" | "25" | "29.97df" | "30";
+
+export interface Timecode {
+  hours: number;
+  minutes: number;
+  seconds: number;
+  frames: number;
+}
+
+export type LtcInputSource =
+  | { type: "device"; audioDriver?: AudioDriver; audioDevice?: string }
+  | { type: "file"; path: string }; // テスト用LTCオーディオファイル
+
+export interface LtcChaseConfig {
+  source: LtcInputSource;
+  channel: number; // 入力チャンネル (0始まり)
+  frameRate: FrameRate;
+  startTimecode: Timecode; // Cue先頭に対応するタイムコード
+  seekThresholdMs: number;
+  freewheelMs: number;
+  lockFrames: number;
+}
+
+export interface LtcOutputConfig {
+  enabled: boolean;
+  outputId: string; // Audio OutputTarget
+  channel: number; // 出力チャンネル (0始まり)
+  frameRate: FrameRate;
+  startTimecode: Timecode; // Cue先頭で出力するタイムコード
+  levelDb: number;
+}
+
+export type ChaseLockState = "unlocked" | "locking" | "locked" | "freewheel";
+
+export interface LtcChaseStatus {
+  running: boolean;
+  lock: ChaseLockState;
+  timecode?: Timecode;
+  frameRate: FrameRate; // チェイスに使っているフレームレート
+  detectedFrameRate?: FrameRate;
+  positionErrorMs?: number;
+  framesDecoded: number;
+  seekCount: number;
+}
+
 // ========================================
 // プロジェクト設定
 // ========================================
 export interface ProjectSettings {
   defaultBrightness: number;
   autoSave: boolean;
+  backupCount?: number; // 保存時に残す以前のファイルの数（既定10、0で残さない）
+  historyDepth?: number; // 元に戻せる編集の数（既定100、0で記録しない）
   previewQuality: "low" | "medium" | "high";
+  ltcChase?: LtcChaseConfig;
+  ltcOutput?: LtcOutputConfig;
+  osc?: OscConfig;
+  http?: HttpConfig;
+  midi?: MidiConfig;
+  artNet?: ArtNetConfig;
+  sacn?: SacnConfig;
+  artTimecodeOutput?: ArtTimecodeOutputConfig;
 }
 
 // ========================================
 // プロジェクト
 // ========================================
 export interface Project {
+  schemaVersion?: number; // 保存時に現在のバージョンになる
   id: string;
   name: string;
   masterBrightness: number;
@@ -84,6 +145,88 @@ export interface Project {
   outputs: OutputTarget[];
   cues: Cue[];
   settings: ProjectSettings;
+  sync?: SyncConfig;
+  bindings?: ControlBinding[];
+  macros?: ControlMacro[];
+}
+
+// 保存時に残る以前のプロジェクトファイル（list_backups、新しい順）
+export interface BackupInfo {
+  path: string;
+  fileName: string;
+  modifiedAtMs: number;
+  size: number;
+}
+
+// 前回異常終了した時に残った自動保存（get_recovery）
+export interface RecoveryInfo {
+  path: string; // リカバリーファイル
+  projectName: string;
+  projectPath?: string;
+  cueIndex?: number;
+  position: number;
+  savedAtMs: number;
+}
+
+// ========================================
+// プロジェクト検証
+// ========================================
+export type ValidationSeverity = "warning" | "error";
+
+export type ValidationKind =
+  | "missingMedia"
+  | "unreadableMedia"
+  | "undecodableMedia"
+  | "unknownOutput"
+  | "duplicateId"
+  | "mediaTypeMismatch"
+  | "missingMonitor";
+
+export interface ValidationFinding {
+  severity: ValidationSeverity;
+  kind: ValidationKind;
+  message: string;
+  cueIndex?: number;
+  itemId?: string;
+  outputId?: string;
+  path?: string;
+}
+
+// ========================================
+// PC固有の設定（プロジェクトとは別に保存）
+// ========================================
+export interface LocalConfig {
+  mediaRoot?: string; // 相対パスのメディアを解決するフォルダ
+  latencyOffsetMs: number; // 同期スレーブ時に先行させる時間（負で遅らせる）
+  slaveId?: string; // 同期スレーブのID（初回のスレーブ起動時に生成）
+  // 以下は実行時にプロジェクトの設定へ上書き（プロジェクトファイルは変更しない）
+  audioDriver?: AudioDriver; // 全オーディオ出力（outputsの指定が優先）
+  outputs: OutputOverride[];
+  displayMap: Record<number, number>; // プロジェクトのディスプレイ番号 → このPCの番号
+  ndiNamePrefix?: string;
+  syncRole?: SyncMode; // 設定するとプロジェクト読み込み時に同期を開始
+  ports: ControlPorts;
+}
+
+export interface OutputOverride {
+  outputId: string;
+  displayIndex?: number;
+  audioDriver?: AudioDriver;
+  audioDevice?: string;
+  audioChannels?: number[];
+}
+
+export interface ControlPorts {
+  osc?: number;
+  http?: number;
+  artNet?: number;
+  midi?: string; // MIDI入力ポート名（部分一致）
+}
+
+export interface PathConversion {
+  project: Project;
+  converted: number;
+  skipped: string[]; // メディアルートの外で変換できなかったパス
 }
 
 // ========================================
@@ -105,6 +248,391 @@ export interface PlayerState {
   error?: string;
 }
 
+// ========================================
+// マルチPC同期
+// ========================================
+export type SyncMode = "off" | "master" | "slave";
+
+export type SyncTransport =
+  | { type: "broadcast"; port: number }
+  | { type: "multicast"; addr: string; port: number }
+  | { type: "unicast"; targets: string[] }; // "ip:port"
+
+export interface SyncConfig {
+  mode: SyncMode;
+  // 送信設定（マスター時）
+  transport: SyncTransport;
+  sendIntervalHz: number;
+  // 受信設定（スレーブ時）
+  listenPort: number;
+  multicastGroup?: string;
+  timeoutMs: number;
+  // 共通
+  toleranceFrames: number;
+  seekThresholdFrames: number;
+  minSeekIntervalMs: number;
+  frameRate: number;
+  // ネットワーククロック共有
+  netClock: boolean;
+  clockPort: number;
+  clockAddress?: string;
+  // スレーブ管理
+  heartbeatIntervalMs: number;
+  slaveTimeoutMs: number;
+}
+
+export interface NetClockStatus {
+  synced: boolean;
+  address: string;
+  rttMs?: number;
+  offsetMs?: number;
+  quality?: number;
+}
+
+export interface SyncStatus {
+  mode: SyncMode;
+  running: boolean;
+  packetsSent: number;
+  slavesOnline: number;
+  packetsReceived: number;
+  packetsLost: number;
+  masterAddress?: string;
+  masterCueIndex?: number;
+  masterPosition?: number;
+  positionErrorMs?: number;
+  lastPacketAgeMs?: number;
+  masterLost: boolean;
+  seekCount: number;
+  playbackRate: number;
+  netClock?: NetClockStatus;
+}
+
+export type SlaveStatus =
+  | "idle"
+  | "synced"
+  | "seeking"
+  | "fileNotFound"
+  | "masterLost"
+  | "disconnected";
+
+export interface SlavePeer {
+  slaveId: string;
+  name: string;
+  address: string;
+  online: boolean;
+  lastSeenMs: number;
+  status: SlaveStatus;
+  cueIndex?: number;
+  position: number;
+  positionDeltaMs?: number;
+  droppedFrames: number;
+  missingMedia: string[];
+  blackout: boolean;
+  clockSynced?: boolean;
+}
+
+export type SlaveCommand =
+  | { type: "reload" }
+  | { type: "resync" }
+  | { type: "blackout"; enabled: boolean };
+
+// ========================================
+// OSC制御
+// ========================================
+export type OscTcpFraming = "length" | "slip";
+
+export interface OscConfig {
+  enabled: boolean;
+  bindAddress: string;
+  port: number;
+  udp: boolean;
+  tcp: boolean;
+  tcpFraming: OscTcpFraming;
+  feedbackTargets: string[];
+  feedbackRateHz: number;
+}
+
+export interface OscStatus {
+  running: boolean;
+  udpAddress?: string;
+  tcpAddress?: string;
+  tcpClients: number;
+  feedbackClients: number;
+  messagesReceived: number;
+  errors: number;
+  lastAddress?: string;
+  lastError?: string;
+}
+
+// ========================================
+// HTTP / WebSocket API
+// ========================================
+export interface HttpConfig {
+  enabled: boolean;
+  bindAddress: string;
+  port: number;
+  /** Require `Authorization: Bearer <token>` (or `?token=` for WebSocket) */
+  token?: string;
+}
+
+export interface HttpStatus {
+  running: boolean;
+  address?: string;
+  wsClients: number;
+  requests: number;
+}
+
+/** /api/ws で受信するメッセージ */
+export type HttpWsMessage =
+  | { type: "state"; data: PlayerState }
+  | { type: "event"; data: AppEvent };
+
+// ========================================
+// MIDI入力
+// ========================================
+/** チャンネルは1〜16（省略で全チャンネル） */
+export type MidiTrigger =
+  | { type: "note"; channel?: number; note?: number }
+  | { type: "controlChange"; channel?: number; controller: number }
+  | { type: "programChange"; channel?: number; program?: number };
+
+export interface MidiBinding {
+  trigger: MidiTrigger;
+  action: ControlAction;
+}
+
+export interface MidiConfig {
+  enabled: boolean;
+  portName?: string;
+  virtualPort: boolean;
+  virtualPortName: string;
+  /** プロジェクトのバインディング（bindings）が一致したメッセージには使わない */
+  bindings: MidiBinding[];
+  msc: boolean;
+  mscDeviceId?: number;
+  mtcChase: boolean;
+}
+
+export interface MidiStatus {
+  running: boolean;
+  port?: string;
+  messagesReceived: number;
+  errors: number;
+  lastMessage?: string;
+  lastError?: string;
+  mtcChase?: LtcChaseStatus;
+}
+
+// ========================================
+// DMX入力（Art-Net・sACN）
+// ========================================
+export type DmxChannelFunction =
+  | { type: "masterBrightness" }
+  | { type: "masterVolume" }
+  | { type: "outputBrightness"; outputId: string }
+  | { type: "cueSelect" }
+  | { type: "go" };
+
+/** プロジェクトのバインディングのトリガーに使われているチャンネルは無視される */
+export interface DmxPatch {
+  /** 1〜512 */
+  startAddress: number;
+  channels: DmxChannelFunction[];
+}
+
+export interface ArtNetConfig {
+  enabled: boolean;
+  bindAddress: string;
+  port: number;
+  universe: number;
+  patch: DmxPatch;
+  timecodeChase: boolean;
+}
+
+export interface ArtNetStatus {
+  running: boolean;
+  address?: string;
+  packetsReceived: number;
+  dmxFrames: number;
+  lastSource?: string;
+  channelValues: number[];
+  errors: number;
+  lastError?: string;
+  timecodeChase?: LtcChaseStatus;
+}
+
+export interface ArtTimecodeOutputConfig {
+  enabled: boolean;
+  targetAddress: string;
+  port: number;
+  frameRate: FrameRate;
+  startTimecode: Timecode;
+  streamId: number;
+}
+
+export interface ArtTimecodeOutputStatus {
+  running: boolean;
+  target?: string;
+  packetsSent: number;
+  timecode?: Timecode;
+}
+
+export type DmxSourceLoss = "hold" | "release";
+
+export interface SacnConfig {
+  enabled: boolean;
+  /** 1〜63999 */
+  universe: number;
+  multicast: boolean;
+  interface?: string;
+  sourceLoss: DmxSourceLoss;
+  sourceTimeoutMs: number;
+  patch: DmxPatch;
+}
+
+export interface SacnSource {
+  cid: string;
+  name: string;
+  address: string;
+  priority: number;
+  active: boolean;
+}
+
+export interface SacnStatus {
+  running: boolean;
+  address?: string;
+  packetsReceived: number;
+  dmxFrames: number;
+  sources: SacnSource[];
+  channelValues: number[];
+  errors: number;
+  lastError?: string;
+}
+
+// ========================================
+// トリガーとアクションのバインディング
+// ========================================
+export type LevelTarget =
+  | { type: "masterBrightness" }
+  | { type: "masterVolume" }
+  | { type: "outputBrightness"; outputId: string };
+
+export type ControlTrigger =
+  | { type: "osc"; address: string }
+  | { type: "midi"; message: MidiTrigger }
+  /** address は1〜512、universe省略で全ユニバース */
+  | { type: "dmx"; universe?: number; address: number; threshold: number }
+  /** POST /api/trigger/{name} */
+  | { type: "http"; name: string }
+  | { type: "hotkey"; id: string }
+  | { type: "timecode"; timecode: Timecode };
+
+/** 値・cue（0始まり）省略時は入力の値を使う */
+export type ControlAction =
+  | { type: "go" }
+  | { type: "play" }
+  | { type: "pause" }
+  | { type: "stop" }
+  | { type: "loadCue"; cue?: number }
+  | { type: "goCue"; cue?: number }
+  | { type: "masterBrightness"; value?: number }
+  | { type: "masterVolume"; value?: number }
+  | { type: "outputBrightness"; outputId: string; value?: number }
+  | { type: "fade"; target: LevelTarget; value: number; durationMs: number }
+  /** enabled省略で切り替え */
+  | { type: "blackout"; enabled?: boolean }
+  | { type: "runMacro"; macroId: string };
+
+export interface ControlBinding {
+  trigger: ControlTrigger;
+  action: ControlAction;
+}
+
+export interface MacroStep {
+  delayMs: number;
+  action: ControlAction;
+}
+
+export interface ControlMacro {
+  id: string;
+  name: string;
+  steps: MacroStep[];
+}
+
+// ========================================
+// アプリケーションイベント ("app-event")
+// ========================================
+export type AppEvent =
+  | { type: "slaveOnline"; slaveId: string; name: string; address: string }
+  | {
+      type: "slaveSilent";
+      slaveId: string;
+      name: string;
+      address: string;
+      silentMs: number;
+    }
+  | {
+      type: "projectChanged";
+      change: ProjectChange;
+      label?: string;
+      history: HistoryStatus;
+    }
+  | ({ type: "collectProgress" } & CollectProgress);
+
+// 編集履歴の状態（get_history、projectChangedイベント）
+export interface HistoryStatus {
+  undoLabel?: string;
+  redoLabel?: string;
+  undoCount: number;
+  redoCount: number;
+}
+
+export type ProjectChange = "load" | "edit" | "undo" | "redo";
+
+// ========================================
+// ショーの収集（collect_project）
+// ========================================
+export type CollectMode = "folder" | "archive";
+
+export interface CollectProgress {
+  file: string;
+  filesDone: number;
+  filesTotal: number;
+  bytesDone: number;
+  bytesTotal: number;
+}
+
+export interface CollectResult {
+  path: string; // 書き出したプロジェクトファイル（アーカイブはZIPファイル）
+  copied: number;
+  skipped: number; // 同じ内容のファイルが既にあった数
+  bytes: number;
+}
+
+// ========================================
+// メディアの再リンク（find_relinks / apply_relinks）
+// ========================================
+export type RelinkMatch = "name" | "size" | "hash";
+
+export interface RelinkCandidate {
+  path: string; // メディアルート内なら相対パス
+  size: number;
+  match: RelinkMatch;
+  matchingFolders: number; // 元のパスと末尾から一致したフォルダ名の数
+}
+
+export interface RelinkProposal {
+  path: string;
+  itemIds: string[];
+  candidates: RelinkCandidate[]; // 有力な順
+  proposed?: string; // 候補が1つに絞れた場合
+}
+
+export interface Relink {
+  from: string;
+  to: string;
+}
+
 // ========================================
 // モニター情報
 // ========================================